@@ -166,8 +166,12 @@ impl RunArgs {
                     })?;
                 cloned_projects.push(project);
             }
-            let tweak_map =
-                foundry_tweak::build_tweak_data(&cloned_projects, &self.rpc, self.quick).await?;
+            let tweak_map = foundry_tweak::build_tweak_data(
+                &cloned_projects,
+                &self.rpc,
+                &foundry_tweak::TweakOptions::new(self.quick),
+            )
+            .await?;
             tweak_backend(executor.backend_mut(), &tweak_map)?;
         }
         println!("Executing transaction: {:?}", tx.hash);
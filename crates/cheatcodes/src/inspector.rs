@@ -11,7 +11,7 @@ use crate::{
     script::{Broadcast, ScriptWallets},
     test::expect::{
         self, ExpectedCallData, ExpectedCallTracker, ExpectedCallType, ExpectedEmit,
-        ExpectedRevert, ExpectedRevertKind,
+        ExpectedRevert, ExpectedRevertKind, MatchedExpectedRevert,
     },
     CheatsConfig, CheatsCtxt, DynCheatcode, Error, Result, Vm,
     Vm::AccountAccess,
@@ -249,6 +249,9 @@ pub struct Cheatcodes {
     /// Expected revert information
     pub expected_revert: Option<ExpectedRevert>,
 
+    /// The reverts which matched an expected revert, in the order they happened
+    pub matched_expected_reverts: Vec<MatchedExpectedRevert>,
+
     /// Additional diagnostic for reverts
     pub fork_revert_diagnostic: Option<RevertDiagnostic>,
 
@@ -347,6 +350,7 @@ impl Cheatcodes {
             gas_price: Default::default(),
             prank: Default::default(),
             expected_revert: Default::default(),
+            matched_expected_reverts: Default::default(),
             fork_revert_diagnostic: Default::default(),
             accesses: Default::default(),
             recorded_account_diffs_stack: Default::default(),
@@ -607,6 +611,10 @@ impl Cheatcodes {
                     outcome.result.output.clone(),
                 ) {
                     Ok((address, retdata)) => {
+                        self.matched_expected_reverts.push(MatchedExpectedRevert {
+                            depth: ecx.journaled_state.depth(),
+                            data: outcome.result.output.clone(),
+                        });
                         outcome.result.result = InstructionResult::Return;
                         outcome.result.output = retdata;
                         outcome.address = address;
@@ -1064,6 +1072,10 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                             outcome
                         }
                         Ok((_, retdata)) => {
+                            self.matched_expected_reverts.push(MatchedExpectedRevert {
+                                depth: ecx.journaled_state.depth(),
+                                data: outcome.result.output.clone(),
+                            });
                             outcome.result.result = InstructionResult::Return;
                             outcome.result.output = retdata;
                             outcome
@@ -47,7 +47,7 @@ pub use script::{ScriptWallets, ScriptWalletsInner};
 mod string;
 
 mod test;
-pub use test::expect::ExpectedCallTracker;
+pub use test::expect::{ExpectedCallTracker, MatchedExpectedRevert};
 
 mod toml;
 
@@ -76,6 +76,15 @@ pub struct ExpectedRevert {
     pub kind: ExpectedRevertKind,
 }
 
+/// A revert of a call which was expected with `vm.expectRevert`, and thus turned into a success.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchedExpectedRevert {
+    /// The depth of the reverted call
+    pub depth: u64,
+    /// The data returned by the revert
+    pub data: Bytes,
+}
+
 #[derive(Clone, Debug)]
 pub struct ExpectedEmit {
     /// The depth at which we expect this emit to have occurred
@@ -20,7 +20,7 @@ use foundry_config::{
     Config,
 };
 use serde::Serialize;
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 #[derive(Clone, Debug, Default, Serialize, Parser)]
 #[command(next_help_heading = "Build options")]
@@ -77,11 +77,14 @@ pub struct CoreBuildArgs {
     /// Do not append any metadata to the bytecode.
     ///
     /// This is equivalent to setting `bytecode_hash` to `none` and `cbor_metadata` to `false`.
-    #[arg(long, help_heading = "Compiler options")]
+    #[arg(long, help_heading = "Compiler options", conflicts_with = "bytecode_hash")]
     #[serde(skip)]
     pub no_metadata: bool,
 
     /// The path to the contract artifacts folder.
+    ///
+    /// If it differs from the configured one, the compiler cache is kept in `<PATH>/cache` unless
+    /// `--cache-path` is set, so that builds to separate folders don't invalidate each other.
     #[arg(
         long = "out",
         short,
@@ -101,11 +104,17 @@ pub struct CoreBuildArgs {
     pub revert_strings: Option<RevertStrings>,
 
     /// Don't print anything on startup.
+    ///
+    /// `forge build` does not print the compiler output either, only the errors are printed if
+    /// the compilation fails.
     #[arg(long, help_heading = "Compiler options")]
     #[serde(skip)]
     pub silent: bool,
 
     /// Generate build info files.
+    ///
+    /// These contain the full standard JSON input and output of the compiler, and are written to
+    /// `<out>/build-info` by default, named after a hash of the input.
     #[arg(long, help_heading = "Project options")]
     #[serde(skip)]
     pub build_info: bool,
@@ -121,6 +130,18 @@ pub struct CoreBuildArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub build_info_path: Option<PathBuf>,
 
+    /// Define a key/value pair, substituted for `${KEY}` in the remappings.
+    ///
+    /// Can be specified multiple times. The defines are also recorded in the build info files.
+    #[arg(
+        long = "define",
+        help_heading = "Project options",
+        value_name = "KEY=VALUE",
+        value_parser = parse_define,
+    )]
+    #[serde(skip)]
+    pub defines: Vec<(String, String)>,
+
     /// Skip building files whose names contain the given filter.
     ///
     /// `test` and `script` are aliases for `.t.sol` and `.s.sol`.
@@ -175,7 +196,15 @@ impl<'a> From<&'a CoreBuildArgs> for Figment {
         let mut remappings = Remappings::new_with_remappings(args.project_paths.get_remappings());
         remappings
             .extend(figment.extract_inner::<Vec<Remapping>>("remappings").unwrap_or_default());
-        figment = figment.merge(("remappings", remappings.into_inner())).merge(args);
+        figment = figment.merge(("remappings", remappings.into_inner()));
+
+        if let (Some(out), None) = (&args.out_path, &args.project_paths.cache_path) {
+            let configured = figment.extract_inner::<PathBuf>("out").unwrap_or_default();
+            if *out != configured {
+                figment = figment.merge(("cache_path", out.join("cache")));
+            }
+        }
+        figment = figment.merge(args);
 
         if let Some(skip) = &args.skip {
             let mut skip = skip.iter().map(|s| s.file_pattern().to_string()).collect::<Vec<_>>();
@@ -252,6 +281,11 @@ impl Provider for CoreBuildArgs {
             dict.insert("ast".to_string(), true.into());
         }
 
+        if !self.defines.is_empty() {
+            let defines = self.defines.iter().cloned().collect::<BTreeMap<_, _>>();
+            dict.insert("defines".to_string(), Value::serialize(defines)?);
+        }
+
         if self.compiler.optimize {
             dict.insert("optimizer".to_string(), self.compiler.optimize.into());
         }
@@ -275,3 +309,38 @@ impl Provider for CoreBuildArgs {
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
+
+/// Parses a `KEY=VALUE` define.
+fn parse_define(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid define `{s}`, expected `KEY=VALUE`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_defines() {
+        let args = CoreBuildArgs::parse_from([
+            "foundry-cli",
+            "--define",
+            "NETWORK=mainnet",
+            "--define",
+            "EMPTY=",
+        ]);
+        assert_eq!(
+            args.defines,
+            vec![
+                ("NETWORK".to_string(), "mainnet".to_string()),
+                ("EMPTY".to_string(), "".to_string())
+            ]
+        );
+        assert!(CoreBuildArgs::try_parse_from(["foundry-cli", "--define", "NETWORK"]).is_err());
+
+        let config = Config::from_provider(Figment::from(Config::default()).merge(&args));
+        assert_eq!(config.defines.get("NETWORK").map(String::as_str), Some("mainnet"));
+    }
+}
@@ -1,5 +1,7 @@
 use clap::Parser;
-use foundry_compilers::artifacts::{output_selection::ContractOutputSelection, EvmVersion};
+use foundry_compilers::artifacts::{
+    output_selection::ContractOutputSelection, BytecodeHash, EvmVersion,
+};
 use serde::Serialize;
 
 mod core;
@@ -35,6 +37,13 @@ pub struct CompilerArgs {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub optimizer_runs: Option<usize>,
 
+    /// The hash of the metadata appended to the bytecode: `ipfs`, `bzzr1` or `none`.
+    ///
+    /// Overrides the `bytecode_hash` of the config.
+    #[arg(long, value_name = "MODE")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytecode_hash: Option<BytecodeHash>,
+
     /// Extra output to include in the contract's artifact.
     ///
     /// Example keys: evm.assembly, ewasm, ir, irOptimized, metadata
@@ -63,6 +72,13 @@ mod tests {
         assert_eq!(args.evm_version, Some(EvmVersion::London));
     }
 
+    #[test]
+    fn can_parse_bytecode_hash() {
+        let args: CompilerArgs =
+            CompilerArgs::parse_from(["foundry-cli", "--bytecode-hash", "none"]);
+        assert_eq!(args.bytecode_hash, Some(BytecodeHash::None));
+    }
+
     #[test]
     fn can_parse_extra_output() {
         let args: CompilerArgs =
@@ -22,6 +22,7 @@ use foundry_evm::{
 use std::{
     fmt::Write,
     path::{Path, PathBuf},
+    process::{Command, Stdio},
     str::FromStr,
 };
 use yansi::Paint;
@@ -111,6 +112,48 @@ pub fn get_cached_entry_by_name(
     eyre::bail!(err)
 }
 
+/// Runs the `build.prebuild` command of the config, if any, in the project root.
+///
+/// The stdout of the command is printed once it exits, to stderr if `quiet` is set so that it
+/// does not interleave with machine-readable output, and its stderr is inherited.
+pub fn run_prebuild_hook(config: &Config, quiet: bool) -> Result<()> {
+    let Some(hook) = &config.build.prebuild else { return Ok(()) };
+
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C");
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c");
+        cmd
+    };
+    trace!(target: "forge::build", %hook, "running prebuild hook");
+    let output = cmd
+        .arg(hook)
+        .current_dir(&config.root.0)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .wrap_err_with(|| format!("failed to run the prebuild hook `{hook}`"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.is_empty() {
+        if quiet {
+            eprint!("{stdout}");
+        } else {
+            print!("{stdout}");
+        }
+    }
+    if !output.status.success() {
+        match output.status.code() {
+            Some(code) => eyre::bail!("the prebuild hook `{hook}` exited with code {code}"),
+            None => eyre::bail!("the prebuild hook `{hook}` was terminated by a signal"),
+        }
+    }
+    Ok(())
+}
+
 /// Returns error if constructor has arguments.
 pub fn ensure_clean_constructor(abi: &JsonAbi) -> Result<()> {
     if let Some(constructor) = &abi.constructor {
@@ -247,7 +290,9 @@ where
         // update the fork url if it was an alias
         if let Some(fork_url) = config.get_rpc_url() {
             trace!(target: "forge::config", ?fork_url, "Update EvmOpts fork url");
-            evm_opts.fork_url = Some(fork_url?.into_owned());
+            let fork_url = fork_url?;
+            ensure_known_alias(&config, &fork_url)?;
+            evm_opts.fork_url = Some(fork_url.into_owned());
         }
 
         Ok((config, evm_opts))
@@ -294,6 +339,29 @@ where
     }
 }
 
+/// Returns an error if the fork URL looks like an alias, but is not one of the `[rpc_endpoints]`.
+///
+/// Aliases are resolved before this, so such a fork URL would otherwise fail as an invalid URL.
+fn ensure_known_alias(config: &Config, fork_url: &str) -> Result<()> {
+    let is_alias_like = |s: &str| {
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    if !is_alias_like(fork_url) || Path::new(fork_url).exists() {
+        return Ok(())
+    }
+
+    if config.rpc_endpoints.is_empty() {
+        eyre::bail!(
+            "`{fork_url}` is not a URL, and no RPC endpoints are configured in `[rpc_endpoints]`"
+        );
+    }
+    let aliases = config.rpc_endpoints.keys().map(|alias| format!("`{alias}`")).collect::<Vec<_>>();
+    eyre::bail!(
+        "`{fork_url}` is not a URL, nor one of the configured RPC endpoints: {}",
+        aliases.join(", ")
+    )
+}
+
 /// Read contract constructor arguments from the given file.
 pub fn read_constructor_args_file(constructor_args_path: PathBuf) -> Result<Vec<String>> {
     if !constructor_args_path.exists() {
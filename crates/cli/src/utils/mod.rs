@@ -1,5 +1,8 @@
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::U256;
+use alloy_primitives::{
+    utils::{ParseUnits, Unit},
+    U256,
+};
 use alloy_provider::{network::AnyNetwork, Provider};
 use alloy_transport::Transport;
 use eyre::{ContextCompat, Result};
@@ -133,6 +136,14 @@ pub fn parse_ether_value(value: &str) -> Result<U256> {
     })
 }
 
+/// Parses an amount of gwei, e.g. "1.5", into wei.
+pub fn parse_gwei_value(value: &str) -> Result<U256> {
+    match ParseUnits::parse_units(value, Unit::GWEI)? {
+        ParseUnits::U256(wei) => Ok(wei),
+        ParseUnits::I256(_) => eyre::bail!("gwei amount must not be negative: {value}"),
+    }
+}
+
 /// Parses a `Duration` from a &str
 pub fn parse_delay(delay: &str) -> Result<Duration> {
     let delay = if delay.ends_with("ms") {
@@ -593,6 +604,14 @@ mod tests {
     use std::{env, fs::File, io::Write};
     use tempfile::tempdir;
 
+    #[test]
+    fn can_parse_gwei_value() {
+        assert_eq!(parse_gwei_value("1").unwrap(), U256::from(1_000_000_000u64));
+        assert_eq!(parse_gwei_value("1.5").unwrap(), U256::from(1_500_000_000u64));
+        assert_eq!(parse_gwei_value("0.000000001").unwrap(), U256::from(1));
+        assert!(parse_gwei_value("-1").is_err());
+    }
+
     #[test]
     fn foundry_path_ext_works() {
         let p = Path::new("contracts/MyTest.t.sol");
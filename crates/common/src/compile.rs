@@ -125,6 +125,8 @@ impl ProjectCompiler {
     }
 
     /// Compiles the project.
+    ///
+    /// Relative extra files are resolved against the current working directory.
     pub fn compile<C: Compiler>(mut self, project: &Project<C>) -> Result<ProjectCompileOutput<C>> {
         // TODO: Avoid process::exit
         if !project.paths.has_input_files() && self.files.is_empty() {
@@ -133,8 +135,29 @@ impl ProjectCompiler {
             std::process::exit(0);
         }
 
+        if self.files.iter().any(|file| file.is_relative()) {
+            let cwd = std::env::current_dir()?;
+            self.files = self.files.into_iter().map(|file| cwd.join(file)).collect();
+        }
+        self.compile_with_paths(project)
+    }
+
+    /// Compiles the given, already resolved project.
+    ///
+    /// Unlike [`compile`](Self::compile), this only relies on the paths of the project and never
+    /// exits the process if there is nothing to compile. Relative extra files are resolved against
+    /// the project root. This makes it independent of the current working directory, so that
+    /// several projects can be compiled concurrently.
+    pub fn compile_with_paths<C: Compiler>(
+        mut self,
+        project: &Project<C>,
+    ) -> Result<ProjectCompileOutput<C>> {
         // Taking is fine since we don't need these in `compile_with`.
-        let files = std::mem::take(&mut self.files);
+        let root = &project.paths.root;
+        let files = std::mem::take(&mut self.files)
+            .into_iter()
+            .map(|file| root.join(file))
+            .collect::<Vec<_>>();
         self.compile_with(|| {
             let sources = if !files.is_empty() {
                 Source::read_all(files)?
@@ -223,33 +246,7 @@ impl ProjectCompiler {
                 println!();
             }
 
-            let mut size_report = SizeReport { contracts: BTreeMap::new() };
-
-            let artifacts: BTreeMap<_, _> = output
-                .artifact_ids()
-                .filter(|(id, _)| {
-                    // filter out forge-std specific contracts
-                    !id.source.to_string_lossy().contains("/forge-std/src/")
-                })
-                .map(|(id, artifact)| (id.name, artifact))
-                .collect();
-
-            for (name, artifact) in artifacts {
-                let size = deployed_contract_size(artifact).unwrap_or_default();
-
-                let is_dev_contract = artifact
-                    .abi
-                    .as_ref()
-                    .map(|abi| {
-                        abi.functions().any(|f| {
-                            f.test_function_kind().is_known() ||
-                                matches!(f.name.as_str(), "IS_TEST" | "IS_SCRIPT")
-                        })
-                    })
-                    .unwrap_or(false);
-                size_report.contracts.insert(name, ContractInfo { size, is_dev_contract });
-            }
-
+            let size_report = SizeReport::new(output);
             println!("{size_report}");
 
             // TODO: avoid process::exit
@@ -388,6 +385,9 @@ impl ContractSources {
 // https://eips.ethereum.org/EIPS/eip-170
 const CONTRACT_SIZE_LIMIT: usize = 24576;
 
+// https://eips.ethereum.org/EIPS/eip-3860
+const CONTRACT_INITCODE_SIZE_LIMIT: usize = 49152;
+
 /// Contracts with info about their size
 pub struct SizeReport {
     /// `contract name -> info`
@@ -395,6 +395,61 @@ pub struct SizeReport {
 }
 
 impl SizeReport {
+    /// Collects the sizes of all the compiled contracts, excluding the forge-std ones.
+    pub fn new<C: Compiler>(output: &ProjectCompileOutput<C>) -> Self {
+        let mut size_report = Self { contracts: BTreeMap::new() };
+
+        let artifacts: BTreeMap<_, _> = output
+            .artifact_ids()
+            .filter(|(id, _)| {
+                // filter out forge-std specific contracts
+                !id.source.to_string_lossy().contains("/forge-std/src/")
+            })
+            .map(|(id, artifact)| (id.name, artifact))
+            .collect();
+
+        for (name, artifact) in artifacts {
+            let size = deployed_contract_size(artifact).unwrap_or_default();
+            let init_size = initcode_size(artifact).unwrap_or_default();
+
+            let is_dev_contract = artifact
+                .abi
+                .as_ref()
+                .map(|abi| {
+                    abi.functions().any(|f| {
+                        f.test_function_kind().is_known() ||
+                            matches!(f.name.as_str(), "IS_TEST" | "IS_SCRIPT")
+                    })
+                })
+                .unwrap_or(false);
+            size_report.contracts.insert(name, ContractInfo { size, init_size, is_dev_contract });
+        }
+
+        size_report
+    }
+
+    /// Returns the runtime and init code sizes of the contracts, along with their margins to the
+    /// EIP-170 and EIP-3860 limits, as JSON.
+    ///
+    /// Like the table, this excludes test contracts and contracts without code.
+    pub fn to_json(&self) -> serde_json::Value {
+        let contracts = self
+            .contracts
+            .iter()
+            .filter(|(_, c)| !c.is_dev_contract && c.size > 0)
+            .map(|(name, contract)| {
+                let value = serde_json::json!({
+                    "runtime_size": contract.size,
+                    "init_size": contract.init_size,
+                    "runtime_margin": CONTRACT_SIZE_LIMIT as isize - contract.size as isize,
+                    "init_margin": CONTRACT_INITCODE_SIZE_LIMIT as isize - contract.init_size as isize,
+                });
+                (name.clone(), value)
+            })
+            .collect::<serde_json::Map<_, _>>();
+        contracts.into()
+    }
+
     /// Returns the size of the largest contract, excluding test contracts.
     pub fn max_size(&self) -> usize {
         let mut max_size = 0;
@@ -468,11 +523,26 @@ pub fn deployed_contract_size<T: Artifact>(artifact: &T) -> Option<usize> {
     Some(size)
 }
 
+/// Returns the size of the init code of the contract
+pub fn initcode_size<T: Artifact>(artifact: &T) -> Option<usize> {
+    let bytecode = artifact.get_bytecode_object()?;
+    let size = match bytecode.as_ref() {
+        BytecodeObject::Bytecode(bytes) => bytes.len(),
+        BytecodeObject::Unlinked(unlinked) => {
+            // see `deployed_contract_size`, placeholders have the same length as addresses
+            unlinked.strip_prefix("0x").unwrap_or(unlinked).len() / 2
+        }
+    };
+    Some(size)
+}
+
 /// How big the contract is and whether it is a dev contract where size limits can be neglected
 #[derive(Clone, Copy, Debug)]
 pub struct ContractInfo {
     /// size of the contract in bytes
     pub size: usize,
+    /// size of the init code of the contract in bytes
+    pub init_size: usize,
     /// A development contract is either a Script or a Test contract.
     pub is_dev_contract: bool,
 }
@@ -13,6 +13,7 @@ use foundry_config::{
 };
 use rustc_hash::FxHashMap;
 use serde::Serialize;
+use std::str::FromStr;
 
 /// Map keyed by breakpoints char to their location (contract address, pc)
 pub type Breakpoints = FxHashMap<char, (Address, usize)>;
@@ -50,10 +51,13 @@ pub struct EvmArgs {
 
     /// Fetch state from a specific block number over a remote endpoint.
     ///
+    /// Either a block number or `latest`. With `latest`, `forge test` pins the latest block when
+    /// it starts, so that all the tests fork the same block.
+    ///
     /// See --fork-url.
     #[arg(long, requires = "fork_url", value_name = "BLOCK")]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub fork_block_number: Option<u64>,
+    #[serde(skip_serializing_if = "is_none_or_latest", serialize_with = "block_number")]
+    pub fork_block_number: Option<ForkBlockNumber>,
 
     /// Number of retries.
     ///
@@ -271,6 +275,38 @@ pub struct EnvArgs {
     pub disable_block_gas_limit: bool,
 }
 
+/// The block to fork from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForkBlockNumber {
+    /// The block with the given number.
+    Number(u64),
+    /// The latest block.
+    Latest,
+}
+
+impl ForkBlockNumber {
+    /// Returns the block number, if it is not the latest block.
+    pub fn number(self) -> Option<u64> {
+        match self {
+            Self::Number(number) => Some(number),
+            Self::Latest => None,
+        }
+    }
+}
+
+impl FromStr for ForkBlockNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "latest" {
+            return Ok(Self::Latest)
+        }
+        s.parse()
+            .map(Self::Number)
+            .map_err(|_| format!("invalid block number `{s}`, expected a number or `latest`"))
+    }
+}
+
 impl EvmArgs {
     /// Ensures that fork url exists and returns its reference.
     pub fn ensure_fork_url(&self) -> eyre::Result<&String> {
@@ -278,6 +314,23 @@ impl EvmArgs {
     }
 }
 
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_none_or_latest(block: &Option<ForkBlockNumber>) -> bool {
+    block.and_then(ForkBlockNumber::number).is_none()
+}
+
+/// The latest block is serialized as `None`, so that it is resolved like an unset block.
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn block_number<S: serde::Serializer>(
+    block: &Option<ForkBlockNumber>,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    match block.and_then(ForkBlockNumber::number) {
+        Some(number) => s.serialize_u64(number),
+        None => s.serialize_none(),
+    }
+}
+
 /// We have to serialize chain IDs and not names because when extracting an EVM `Env`, it expects
 /// `chain_id` to be `u64`.
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -308,6 +361,36 @@ mod tests {
         assert_eq!(env.chain, Some(NamedChain::Goerli.into()));
     }
 
+    #[test]
+    fn can_parse_fork_block_number() {
+        let args = EvmArgs::parse_from(["foundry-common", "-f", "http://localhost:8545"]);
+        assert_eq!(args.fork_block_number, None);
+
+        let args = EvmArgs::parse_from([
+            "foundry-common",
+            "-f",
+            "http://localhost:8545",
+            "--fork-block-number",
+            "100",
+        ]);
+        assert_eq!(args.fork_block_number, Some(ForkBlockNumber::Number(100)));
+        let config = Config::from_provider(Config::figment().merge(args));
+        assert_eq!(config.fork_block_number, Some(100));
+
+        let args = EvmArgs::parse_from([
+            "foundry-common",
+            "-f",
+            "http://localhost:8545",
+            "--fork-block-number",
+            "latest",
+        ]);
+        assert_eq!(args.fork_block_number, Some(ForkBlockNumber::Latest));
+        let config = Config::from_provider(Config::figment().merge(args));
+        assert_eq!(config.fork_block_number, None);
+
+        assert!("head".parse::<ForkBlockNumber>().is_err());
+    }
+
     #[test]
     fn test_memory_limit() {
         let args = EvmArgs {
@@ -15,6 +15,12 @@ pub trait TestFilter: Send + Sync {
 
     /// Returns a contract with the given path should be included.
     fn matches_path(&self, path: &Path) -> bool;
+
+    /// Returns whether tests of the given kind should be included.
+    fn matches_test_kind(&self, kind: TestFunctionKind) -> bool {
+        let _ = kind;
+        true
+    }
 }
 
 /// Extension trait for `Function`.
@@ -0,0 +1,14 @@
+//! Configuration of the build of a project.
+
+use serde::{Deserialize, Serialize};
+
+/// Contains the config of the `[build]` section.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildConfig {
+    /// A shell command run in the project root before the project is compiled by `forge build`
+    /// and `forge test`, e.g. to generate sources.
+    ///
+    /// The build fails if the command fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prebuild: Option<String>,
+}
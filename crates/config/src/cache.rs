@@ -176,12 +176,17 @@ pub struct Cache {
     pub chains: Vec<ChainCache>,
 }
 
+impl Cache {
+    /// Returns the total size of the cache in bytes.
+    pub fn size(&self) -> u64 {
+        self.chains.iter().map(ChainCache::size).sum()
+    }
+}
+
 impl fmt::Display for Cache {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         for chain in &self.chains {
-            match NumberPrefix::decimal(
-                chain.block_explorer as f32 + chain.blocks.iter().map(|x| x.1).sum::<u64>() as f32,
-            ) {
+            match NumberPrefix::decimal(chain.size() as f32) {
                 NumberPrefix::Standalone(size) => {
                     writeln!(f, "- {} ({size:.1} B)", chain.name)?;
                 }
@@ -198,12 +203,12 @@ impl fmt::Display for Cache {
                 }
             }
             for block in &chain.blocks {
-                match NumberPrefix::decimal(block.1 as f32) {
+                match NumberPrefix::decimal(block.size as f32) {
                     NumberPrefix::Standalone(size) => {
-                        writeln!(f, "\t- Block {} ({size:.1} B)", block.0)?;
+                        writeln!(f, "\t- Block {} ({size:.1} B)", block.number)?;
                     }
                     NumberPrefix::Prefixed(prefix, size) => {
-                        writeln!(f, "\t- Block {} ({size:.1} {prefix}B)", block.0)?;
+                        writeln!(f, "\t- Block {} ({size:.1} {prefix}B)", block.number)?;
                     }
                 }
             }
@@ -218,13 +223,40 @@ pub struct ChainCache {
     /// The name of the chain
     pub name: String,
 
-    /// A tuple containing block number and the block directory size in bytes
-    pub blocks: Vec<(String, u64)>,
+    /// The cached blocks
+    pub blocks: Vec<CachedBlock>,
 
     /// The size of the block explorer directory in bytes
     pub block_explorer: u64,
 }
 
+impl ChainCache {
+    /// Returns the total size of the cached data of the chain in bytes.
+    pub fn size(&self) -> u64 {
+        self.block_explorer + self.blocks.iter().map(|block| block.size).sum::<u64>()
+    }
+}
+
+/// A block cached for a given chain in the foundry cache
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CachedBlock {
+    /// The block number
+    pub number: String,
+
+    /// The size of the cached block data in bytes
+    pub size: u64,
+
+    /// The last modification time of the cached block data, in seconds since the unix epoch
+    pub modified: Option<u64>,
+}
+
+impl CachedBlock {
+    /// Creates a cached block without a known modification time.
+    pub fn new(number: impl Into<String>, size: u64) -> Self {
+        Self { number: number.into(), size, modified: None }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,22 +299,22 @@ mod tests {
             chains: vec![
                 ChainCache {
                     name: "mainnet".to_string(),
-                    blocks: vec![("1".to_string(), 1), ("2".to_string(), 2)],
+                    blocks: vec![CachedBlock::new("1", 1), CachedBlock::new("2", 2)],
                     block_explorer: 500,
                 },
                 ChainCache {
                     name: "ropsten".to_string(),
-                    blocks: vec![("1".to_string(), 1), ("2".to_string(), 2)],
+                    blocks: vec![CachedBlock::new("1", 1), CachedBlock::new("2", 2)],
                     block_explorer: 4567,
                 },
                 ChainCache {
                     name: "rinkeby".to_string(),
-                    blocks: vec![("1".to_string(), 1032), ("2".to_string(), 2000000)],
+                    blocks: vec![CachedBlock::new("1", 1032), CachedBlock::new("2", 2000000)],
                     block_explorer: 4230000,
                 },
                 ChainCache {
                     name: "mumbai".to_string(),
-                    blocks: vec![("1".to_string(), 1), ("2".to_string(), 2)],
+                    blocks: vec![CachedBlock::new("1", 1), CachedBlock::new("2", 2)],
                     block_explorer: 0,
                 },
             ],
@@ -22,6 +22,11 @@ pub struct DocConfig {
     pub repository: Option<String>,
     /// Globs to ignore
     pub ignore: Vec<String>,
+    /// The NatSpec tags the public API must be documented with, checked by
+    /// `forge doc --check-missing`.
+    ///
+    /// Any of `notice`, `param` and `return`.
+    pub required_tags: Vec<String>,
 }
 
 impl Default for DocConfig {
@@ -33,6 +38,7 @@ impl Default for DocConfig {
             title: String::default(),
             repository: None,
             ignore: Vec::default(),
+            required_tags: vec!["notice".into(), "param".into(), "return".into()],
         }
     }
 }
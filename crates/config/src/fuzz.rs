@@ -29,6 +29,11 @@ pub struct FuzzConfig {
     pub failure_persist_dir: Option<PathBuf>,
     /// Name of the file to record fuzz failures, defaults to `failures`.
     pub failure_persist_file: Option<String>,
+    /// Whether to seed the dictionary with storage values read from the forked chain.
+    ///
+    /// This reads additional storage slots of every forked contract, each one costing an RPC
+    /// request if not cached yet.
+    pub fork_dictionary: bool,
 }
 
 impl Default for FuzzConfig {
@@ -41,6 +46,7 @@ impl Default for FuzzConfig {
             gas_report_samples: 256,
             failure_persist_dir: None,
             failure_persist_file: None,
+            fork_dictionary: false,
         }
     }
 }
@@ -56,6 +62,7 @@ impl FuzzConfig {
             gas_report_samples: 256,
             failure_persist_dir: Some(cache_dir),
             failure_persist_file: Some("failures".to_string()),
+            fork_dictionary: false,
         }
     }
 }
@@ -14,6 +14,12 @@ pub use natspec::*;
 pub const INLINE_CONFIG_FUZZ_KEY: &str = "fuzz";
 pub const INLINE_CONFIG_INVARIANT_KEY: &str = "invariant";
 const INLINE_CONFIG_PREFIX: &str = "forge-config";
+/// NatSpec tag declaring the maximum amount of gas a test is allowed to use.
+pub const INLINE_CONFIG_GAS_LIMIT_TAG: &str = "@custom:gas-limit";
+/// NatSpec tag declaring the section a test is grouped under in gas snapshots.
+pub const INLINE_CONFIG_SNAPSHOT_GROUP_TAG: &str = "@custom:snapshot-group";
+/// NatSpec tag declaring the number of runs of a fuzz test.
+pub const INLINE_CONFIG_FUZZ_RUNS_TAG: &str = "@custom:fuzz-runs";
 
 static INLINE_CONFIG_PREFIX_SELECTED_PROFILE: Lazy<String> = Lazy::new(|| {
     let selected_profile = Config::selected_profile().to_string();
@@ -50,6 +56,14 @@ impl<T> InlineConfig<T> {
     }
 }
 
+/// Returns whether the given comment contains any inline configuration.
+pub(crate) fn has_inline_config(s: &str) -> bool {
+    s.contains(INLINE_CONFIG_PREFIX) ||
+        s.contains(INLINE_CONFIG_GAS_LIMIT_TAG) ||
+        s.contains(INLINE_CONFIG_SNAPSHOT_GROUP_TAG) ||
+        s.contains(INLINE_CONFIG_FUZZ_RUNS_TAG)
+}
+
 pub(crate) fn remove_whitespaces(s: &str) -> String {
     s.chars().filter(|c| !c.is_whitespace()).collect()
 }
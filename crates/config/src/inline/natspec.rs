@@ -1,4 +1,8 @@
-use super::{remove_whitespaces, INLINE_CONFIG_PREFIX, INLINE_CONFIG_PREFIX_SELECTED_PROFILE};
+use super::{
+    has_inline_config, parse_config_u32, parse_config_u64, remove_whitespaces,
+    InlineConfigParserError, INLINE_CONFIG_FUZZ_RUNS_TAG, INLINE_CONFIG_GAS_LIMIT_TAG,
+    INLINE_CONFIG_PREFIX, INLINE_CONFIG_PREFIX_SELECTED_PROFILE, INLINE_CONFIG_SNAPSHOT_GROUP_TAG,
+};
 use foundry_compilers::{
     artifacts::{ast::NodeType, Node},
     ProjectCompileOutput,
@@ -80,6 +84,33 @@ impl NatSpec {
     pub fn config_lines(&self) -> impl Iterator<Item = String> + '_ {
         self.docs.lines().filter(|line| line.contains(INLINE_CONFIG_PREFIX)).map(remove_whitespaces)
     }
+
+    /// Returns the gas limit declared with `@custom:gas-limit <gas>`, if any.
+    pub fn gas_limit(&self) -> Option<Result<u64, InlineConfigParserError>> {
+        self.docs.lines().find_map(|line| {
+            let (_, value) = line.split_once(INLINE_CONFIG_GAS_LIMIT_TAG)?;
+            let value = remove_whitespaces(value).replace('_', "");
+            Some(parse_config_u64(INLINE_CONFIG_GAS_LIMIT_TAG.to_string(), value))
+        })
+    }
+
+    /// Returns the number of fuzz runs declared with `@custom:fuzz-runs <runs>`, if any.
+    pub fn fuzz_runs(&self) -> Option<Result<u32, InlineConfigParserError>> {
+        self.docs.lines().find_map(|line| {
+            let (_, value) = line.split_once(INLINE_CONFIG_FUZZ_RUNS_TAG)?;
+            let value = remove_whitespaces(value).replace('_', "");
+            Some(parse_config_u32(INLINE_CONFIG_FUZZ_RUNS_TAG.to_string(), value))
+        })
+    }
+
+    /// Returns the snapshot group declared with `@custom:snapshot-group <name>`, if any.
+    pub fn snapshot_group(&self) -> Option<String> {
+        self.docs.lines().find_map(|line| {
+            let (_, value) = line.split_once(INLINE_CONFIG_SNAPSHOT_GROUP_TAG)?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    }
 }
 
 struct SolcParser {
@@ -152,7 +183,7 @@ impl SolcParser {
     fn get_fn_docs(&self, fn_data: &BTreeMap<String, Value>) -> Option<(String, String)> {
         if let Value::Object(fn_docs) = fn_data.get("documentation")? {
             if let Value::String(comment) = fn_docs.get("text")? {
-                if comment.contains(INLINE_CONFIG_PREFIX) {
+                if has_inline_config(comment) {
                     let mut src_line = fn_docs
                         .get("src")
                         .map(|src| src.to_string())
@@ -184,7 +215,7 @@ impl SolangParser {
         contract_name: &str,
     ) {
         // Fast path to avoid parsing the file.
-        if !src.contains(INLINE_CONFIG_PREFIX) {
+        if !has_inline_config(src) {
             return;
         }
 
@@ -201,16 +232,21 @@ impl SolangParser {
                 let start = f.loc.start();
                 // Parse doc comments in between the previous function and the current one.
                 let docs = solang_parser::doccomment::parse_doccomments(&comments, prev_end, start);
-                let docs = docs
-                    .into_iter()
-                    .flat_map(|doc| doc.into_comments())
-                    .filter(|doc| doc.value.contains(INLINE_CONFIG_PREFIX));
-                for doc in docs {
+                let docs = docs.into_iter().flat_map(|doc| doc.into_comments()).filter_map(|doc| {
+                    // Tags are split from their value, restore them to match the solc output.
+                    let value = if doc.tag.starts_with("custom:") {
+                        format!("@{} {}", doc.tag, doc.value)
+                    } else {
+                        doc.value
+                    };
+                    has_inline_config(&value).then_some(value)
+                });
+                for docs in docs {
                     natspecs.push(NatSpec {
                         contract: contract_id.to_string(),
                         function: f.name.as_ref().map(|id| id.to_string()).unwrap_or_default(),
                         line: "0:0:0".to_string(),
-                        docs: doc.value,
+                        docs,
                     });
                 }
                 prev_end = f.loc.end();
@@ -361,6 +397,76 @@ contract FuzzInlineConf is DSTest {
         )
     }
 
+    #[test]
+    fn gas_limit() {
+        let mut natspec = natspec();
+        assert_eq!(natspec.gas_limit(), None);
+
+        natspec.docs = "@custom:gas-limit 50_000".to_string();
+        assert_eq!(natspec.gas_limit(), Some(Ok(50000)));
+
+        natspec.docs = "@custom:gas-limit lots".to_string();
+        assert_eq!(
+            natspec.gas_limit(),
+            Some(Err(InlineConfigParserError::ParseInt(
+                "@custom:gas-limit".to_string(),
+                "lots".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn fuzz_runs() {
+        let mut natspec = natspec();
+        assert_eq!(natspec.fuzz_runs(), None);
+
+        natspec.docs = "@custom:fuzz-runs 10_000".to_string();
+        assert_eq!(natspec.fuzz_runs(), Some(Ok(10000)));
+
+        natspec.docs = "@custom:fuzz-runs many".to_string();
+        assert_eq!(
+            natspec.fuzz_runs(),
+            Some(Err(InlineConfigParserError::ParseInt(
+                "@custom:fuzz-runs".to_string(),
+                "many".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn snapshot_group() {
+        let mut natspec = natspec();
+        assert_eq!(natspec.snapshot_group(), None);
+
+        natspec.docs = "@custom:snapshot-group deposits".to_string();
+        assert_eq!(natspec.snapshot_group(), Some("deposits".to_string()));
+
+        natspec.docs = "@custom:snapshot-group ".to_string();
+        assert_eq!(natspec.snapshot_group(), None);
+    }
+
+    #[test]
+    fn parse_solang_gas_limit() {
+        let src = "
+contract C {
+    /// @custom:gas-limit 50000
+    function f1() {}
+}
+";
+        let mut natspecs = vec![];
+        SolangParser::new().parse(&mut natspecs, src, "path.sol:C", "C");
+        assert_eq!(
+            natspecs,
+            [NatSpec {
+                contract: "path.sol:C".to_string(),
+                function: "f1".to_string(),
+                line: "0:0:0".to_string(),
+                docs: "@custom:gas-limit 50000".to_string(),
+            }]
+        );
+        assert_eq!(natspecs[0].gas_limit(), Some(Ok(50000)));
+    }
+
     #[test]
     fn can_handle_unavailable_src_line_with_fallback() {
         let mut fn_data: BTreeMap<String, Value> = BTreeMap::new();
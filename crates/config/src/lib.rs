@@ -41,7 +41,7 @@ use semver::Version;
 use serde::{Deserialize, Serialize, Serializer};
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
     str::FromStr,
@@ -64,7 +64,7 @@ mod resolve;
 pub use resolve::UnresolvedEnvVarError;
 
 pub mod cache;
-use cache::{Cache, ChainCache};
+use cache::{Cache, CachedBlock, ChainCache};
 
 pub mod fmt;
 pub use fmt::FormatterConfig;
@@ -110,6 +110,9 @@ use soldeer::SoldeerConfig;
 mod vyper;
 use vyper::VyperConfig;
 
+mod build;
+pub use build::BuildConfig;
+
 /// Foundry configuration
 ///
 /// # Defaults
@@ -385,6 +388,11 @@ pub struct Config {
     pub build_info: bool,
     /// The path to the `build-info` directory that contains the build info json files.
     pub build_info_path: Option<PathBuf>,
+    /// Key/value defines, substituted for `${KEY}` in the remappings.
+    ///
+    /// This allows building several configurations from the same sources, e.g. by remapping an
+    /// import path to a different directory per network.
+    pub defines: BTreeMap<String, String>,
     /// Configuration for `forge fmt`
     pub fmt: FormatterConfig,
     /// Configuration for `forge doc`
@@ -420,6 +428,9 @@ pub struct Config {
     /// Configuration for Vyper compiler
     pub vyper: VyperConfig,
 
+    /// Configuration of the build, e.g. the pre-build hook
+    pub build: BuildConfig,
+
     /// Soldeer dependencies
     pub dependencies: Option<SoldeerConfig>,
 
@@ -484,6 +495,7 @@ impl Config {
         "labels",
         "dependencies",
         "vyper",
+        "build",
     ];
 
     /// File name of config toml file
@@ -1010,8 +1022,27 @@ impl Config {
     /// contracts/tokens/token.sol
     /// contracts/math/math.sol
     /// ```
+    ///
+    /// Any `${KEY}` in the remappings is substituted with the value of the `KEY` define, see
+    /// [`Config::defines`].
     pub fn get_all_remappings(&self) -> impl Iterator<Item = Remapping> + '_ {
-        self.remappings.iter().map(|m| m.clone().into())
+        self.remappings.iter().map(|m| {
+            let mut remapping: Remapping = m.clone().into();
+            if !self.defines.is_empty() {
+                remapping.name = self.substitute_defines(&remapping.name);
+                remapping.path = self.substitute_defines(&remapping.path);
+            }
+            remapping
+        })
+    }
+
+    /// Substitutes every `${KEY}` in the given string with the value of the `KEY` define.
+    ///
+    /// Unknown keys are left as is.
+    pub fn substitute_defines(&self, s: &str) -> String {
+        self.defines
+            .iter()
+            .fold(s.to_string(), |s, (key, value)| s.replace(&format!("${{{key}}}"), value))
     }
 
     /// Returns the configured rpc jwt secret
@@ -1650,6 +1681,18 @@ impl Config {
         Ok(())
     }
 
+    /// Clears the foundry rpc cache.
+    pub fn clean_foundry_rpc_cache() -> eyre::Result<()> {
+        if let Some(cache_dir) = Self::foundry_rpc_cache_dir() {
+            let path = cache_dir.as_path();
+            let _ = fs::remove_dir_all(path);
+        } else {
+            eyre::bail!("failed to get foundry_rpc_cache_dir");
+        }
+
+        Ok(())
+    }
+
     /// Clears the foundry cache for `chain`.
     pub fn clean_foundry_chain_cache(chain: Chain) -> eyre::Result<()> {
         if let Some(cache_dir) = Self::foundry_chain_cache_dir(chain) {
@@ -1744,7 +1787,7 @@ impl Config {
     }
 
     /// The path provided to this function should point to a cached chain folder.
-    fn get_cached_blocks(chain_path: &Path) -> eyre::Result<Vec<(String, u64)>> {
+    fn get_cached_blocks(chain_path: &Path) -> eyre::Result<Vec<CachedBlock>> {
         let mut blocks = vec![];
         if !chain_path.exists() {
             return Ok(blocks)
@@ -1761,7 +1804,17 @@ impl Config {
             } else {
                 continue
             };
-            blocks.push((file_name.to_string_lossy().into_owned(), fs::metadata(filepath)?.len()));
+            let metadata = fs::metadata(filepath)?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs());
+            blocks.push(CachedBlock {
+                number: file_name.to_string_lossy().into_owned(),
+                size: metadata.len(),
+                modified,
+            });
         }
         Ok(blocks)
     }
@@ -2069,6 +2122,7 @@ impl Default for Config {
             gas_reports_ignore: vec![],
             solc: None,
             vyper: Default::default(),
+            build: Default::default(),
             auto_detect_solc: true,
             offline: false,
             optimizer: true,
@@ -2140,6 +2194,7 @@ impl Default for Config {
             sparse_mode: false,
             build_info: false,
             build_info_path: None,
+            defines: Default::default(),
             fmt: Default::default(),
             doc: Default::default(),
             labels: Default::default(),
@@ -4806,16 +4861,14 @@ mod tests {
         let result = Config::get_cached_blocks(chain_dir.path())?;
 
         assert_eq!(result.len(), 3);
-        let block1 = &result.iter().find(|x| x.0 == "1").unwrap();
-        let block2 = &result.iter().find(|x| x.0 == "2").unwrap();
-        let block3 = &result.iter().find(|x| x.0 == "3").unwrap();
+        let block1 = &result.iter().find(|x| x.number == "1").unwrap();
+        let block2 = &result.iter().find(|x| x.number == "2").unwrap();
+        let block3 = &result.iter().find(|x| x.number == "3").unwrap();
 
-        assert_eq!(block1.0, "1");
-        assert_eq!(block1.1, 100);
-        assert_eq!(block2.0, "2");
-        assert_eq!(block2.1, 500);
-        assert_eq!(block3.0, "3");
-        assert_eq!(block3.1, 900);
+        assert_eq!(block1.size, 100);
+        assert_eq!(block2.size, 500);
+        assert_eq!(block3.size, 900);
+        assert!(block1.modified.is_some());
 
         chain_dir.close()?;
         Ok(())
@@ -4914,6 +4967,28 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_substitute_defines_in_remappings() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                remappings = ["@config/=configs/${NETWORK}/", "ds-test/=lib/ds-test/src/"]
+                defines = { NETWORK = "mainnet" }
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(config.defines, BTreeMap::from([("NETWORK".into(), "mainnet".into())]));
+            let remapping = config.get_all_remappings().find(|r| r.name == "@config/").unwrap();
+            assert!(remapping.path.ends_with("configs/mainnet/"), "{remapping}");
+            assert_eq!(config.substitute_defines("${NETWORK}-${OTHER}"), "mainnet-${OTHER}");
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_parse_labels() {
         figment::Jail::expect_with(|jail| {
@@ -4969,4 +5044,22 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn test_parse_prebuild() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [build]
+                prebuild = "make generate"
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(config.build, BuildConfig { prebuild: Some("make generate".into()) });
+
+            Ok(())
+        });
+    }
 }
@@ -214,6 +214,38 @@ pub fn get_available_profiles(toml_path: impl AsRef<Path>) -> eyre::Result<Vec<S
     Ok(result)
 }
 
+/// Returns the keys set by every `profile` of a given `.toml` file, in the order of the file
+///
+/// i.e. The toml below would return `[("default", ["src"]), ("ci", ["fuzz", "verbosity"])]`
+/// ```toml
+/// [profile.default]
+/// src = "src"
+/// [profile.ci]
+/// fuzz = { runs = 10000 }
+/// verbosity = 3
+/// ```
+pub fn get_profile_keys(toml_path: impl AsRef<Path>) -> eyre::Result<Vec<(String, Vec<String>)>> {
+    let mut result = Vec::new();
+
+    if !toml_path.as_ref().exists() {
+        return Ok(result)
+    }
+
+    let doc = read_toml(toml_path)?;
+
+    if let Some(Item::Table(profiles)) = doc.as_table().get(Config::PROFILE_SECTION) {
+        for (profile, item) in profiles {
+            let keys = item
+                .as_table_like()
+                .map(|table| table.iter().map(|(key, _)| key.to_string()).collect())
+                .unwrap_or_default();
+            result.push((profile.to_string(), keys));
+        }
+    }
+
+    Ok(result)
+}
+
 /// Returns a [`toml_edit::Document`] loaded from the provided `path`.
 /// Can raise an error in case of I/O or parsing errors.
 fn read_toml(path: impl AsRef<Path>) -> eyre::Result<DocumentMut> {
@@ -315,7 +347,7 @@ pub fn evm_spec_id(evm_version: &EvmVersion) -> SpecId {
 
 #[cfg(test)]
 mod tests {
-    use crate::get_available_profiles;
+    use crate::{get_available_profiles, get_profile_keys};
     use std::path::Path;
 
     #[test]
@@ -349,4 +381,36 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn get_profile_keys_from_toml() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r"
+                [profile.default]
+                src = 'src'
+                libs = ['node_modules', 'lib']
+
+                [profile.ci]
+                verbosity = 3
+
+                [profile.ci.fuzz]
+                runs = 10000
+            ",
+            )?;
+
+            let keys = get_profile_keys(Path::new("./foundry.toml")).unwrap();
+            assert_eq!(
+                keys,
+                vec![
+                    ("default".to_string(), vec!["src".to_string(), "libs".to_string()]),
+                    ("ci".to_string(), vec!["verbosity".to_string(), "fuzz".to_string()]),
+                ]
+            );
+            assert!(get_profile_keys(Path::new("./missing.toml")).unwrap().is_empty());
+
+            Ok(())
+        });
+    }
 }
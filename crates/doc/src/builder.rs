@@ -1,6 +1,6 @@
 use crate::{
-    document::DocumentContent, helpers::merge_toml_table, AsDoc, BufWriter, Document, ParseItem,
-    ParseSource, Parser, Preprocessor,
+    document::DocumentContent, find_missing_natspec, helpers::merge_toml_table, AsDoc, BufWriter,
+    CommentTag, Document, MissingNatSpec, ParseItem, ParseSource, Parser, Preprocessor,
 };
 use forge_fmt::{FormatterConfig, Visitable};
 use foundry_compilers::{compilers::solc::SOLC_EXTENSIONS, utils::source_files_iter};
@@ -37,6 +37,12 @@ pub struct DocBuilder {
     pub fmt: FormatterConfig,
     /// Whether to include libraries to the output.
     pub include_libraries: bool,
+    /// Whether to include the internal and private members of contracts to the output.
+    pub include_private: bool,
+    /// Flag whether to only write the summary and the markdown pages, without the book scaffold.
+    pub summary_only: bool,
+    /// The base path prepended to the summary links.
+    pub summary_base: Option<PathBuf>,
 }
 
 // TODO: consider using `tfio`
@@ -58,10 +64,13 @@ impl DocBuilder {
             sources,
             libraries,
             include_libraries,
+            include_private: false,
             should_build: false,
             config: DocConfig::default(),
             preprocessors: Default::default(),
             fmt: Default::default(),
+            summary_only: false,
+            summary_base: None,
         }
     }
 
@@ -71,6 +80,24 @@ impl DocBuilder {
         self
     }
 
+    /// Set `include_private` flag on the builder
+    pub fn with_include_private(mut self, include_private: bool) -> Self {
+        self.include_private = include_private;
+        self
+    }
+
+    /// Set `summary_only` flag on the builder
+    pub fn with_summary_only(mut self, summary_only: bool) -> Self {
+        self.summary_only = summary_only;
+        self
+    }
+
+    /// Set the base path of the summary links on the builder.
+    pub fn with_summary_base(mut self, summary_base: Option<PathBuf>) -> Self {
+        self.summary_base = summary_base;
+        self
+    }
+
     /// Set config on the builder.
     pub fn with_config(mut self, config: DocConfig) -> Self {
         self.config = config;
@@ -94,15 +121,52 @@ impl DocBuilder {
         self.root.join(&self.config.out)
     }
 
-    /// Parse the sources and build the documentation.
-    pub fn build(self) -> eyre::Result<()> {
+    /// Returns the source files to document, without the ignored ones.
+    fn source_files(&self) -> eyre::Result<Vec<PathBuf>> {
         // Expand ignore globs
         let ignored = expand_globs(&self.root, self.config.ignore.iter())?;
+        Ok(source_files_iter(&self.sources, SOLC_EXTENSIONS)
+            .filter(|file| !ignored.contains(file))
+            .collect())
+    }
+
+    /// Parse the sources and return the items of their public API lacking any of the required
+    /// NatSpec tags, without building the documentation.
+    ///
+    /// Library sources are not checked.
+    pub fn missing_natspec(&self, required: &[CommentTag]) -> eyre::Result<Vec<MissingNatSpec>> {
+        let sources = self.source_files()?;
+        let missing = sources
+            .par_iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let source = fs::read_to_string(path)?;
+                let (mut source_unit, comments) =
+                    solang_parser::parse(&source, i).map_err(|err| {
+                        eyre::eyre!(
+                            "Failed to parse Solidity code for {}\nDebug info: {:?}",
+                            path.display(),
+                            err
+                        )
+                    })?;
 
+                let mut doc = Parser::new(comments, source).with_fmt(self.fmt.clone());
+                source_unit
+                    .visit(&mut doc)
+                    .map_err(|err| eyre::eyre!("Failed to parse source: {err}"))?;
+
+                let relative_path = path.strip_prefix(&self.root).unwrap_or(path).to_path_buf();
+                Ok(find_missing_natspec(relative_path, &doc.items(), required))
+            })
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        Ok(missing.into_iter().flatten().collect())
+    }
+
+    /// Parse the sources and build the documentation.
+    pub fn build(self) -> eyre::Result<()> {
         // Collect and parse source files
-        let sources = source_files_iter(&self.sources, SOLC_EXTENSIONS)
-            .filter(|file| !ignored.contains(file))
-            .collect::<Vec<_>>();
+        let sources = self.source_files()?;
 
         if sources.is_empty() {
             println!("No sources detected at {}", self.sources.display());
@@ -148,7 +212,9 @@ impl DocBuilder {
                 };
 
                 // Visit the parse tree
-                let mut doc = Parser::new(comments, source).with_fmt(self.fmt.clone());
+                let mut doc = Parser::new(comments, source)
+                    .with_fmt(self.fmt.clone())
+                    .with_public_only(!self.include_private);
                 source_unit
                     .visit(&mut doc)
                     .map_err(|err| eyre::eyre!("Failed to parse source: {err}"))?;
@@ -189,7 +255,8 @@ impl DocBuilder {
                             from_library,
                             self.config.out.clone(),
                         )
-                        .with_content(DocumentContent::Single(item), ident))
+                        .with_content(DocumentContent::Single(item), ident)
+                        .with_include_private(self.include_private))
                     })
                     .collect::<eyre::Result<Vec<_>>>()?;
 
@@ -221,7 +288,8 @@ impl DocBuilder {
                             from_library,
                             self.config.out.clone(),
                         )
-                        .with_content(DocumentContent::Constants(consts), identity),
+                        .with_content(DocumentContent::Constants(consts), identity)
+                        .with_include_private(self.include_private),
                     )
                 }
 
@@ -238,7 +306,8 @@ impl DocBuilder {
                                 from_library,
                                 self.config.out.clone(),
                             )
-                            .with_content(DocumentContent::OverloadedFunctions(funcs), ident),
+                            .with_content(DocumentContent::OverloadedFunctions(funcs), ident)
+                            .with_include_private(self.include_private),
                         );
                     }
                 }
@@ -280,6 +349,19 @@ impl DocBuilder {
         let out_dir_src = out_dir.join(Self::SRC);
         fs::create_dir_all(&out_dir_src)?;
 
+        if self.summary_only {
+            // Write a summary fragment to be embedded into an existing book
+            let mut summary = BufWriter::default();
+            self.write_summary_section(
+                &mut summary,
+                &documents.iter().collect::<Vec<_>>(),
+                None,
+                0,
+            )?;
+            fs::write(out_dir_src.join(Self::SUMMARY), summary.finish())?;
+            return self.write_documents(documents)
+        }
+
         // Write readme content if any
         let homepage_content = {
             // Default to the homepage README if it's available.
@@ -327,7 +409,11 @@ impl DocBuilder {
         let gitignore = "book/";
         fs::write(self.out_dir().join(".gitignore"), gitignore)?;
 
-        // Write doc files
+        self.write_documents(documents)
+    }
+
+    /// Writes the documentation pages.
+    fn write_documents(&self, documents: Vec<Document>) -> eyre::Result<()> {
         for document in documents {
             fs::create_dir_all(
                 document
@@ -377,6 +463,14 @@ impl DocBuilder {
         Ok(toml::to_string_pretty(&book)?)
     }
 
+    /// Returns the summary link for the given path, relative to the summary base if any.
+    fn summary_link(&self, path: &Path) -> String {
+        match &self.summary_base {
+            Some(base) => base.join(path).display().to_string(),
+            None => path.display().to_string(),
+        }
+    }
+
     fn write_summary_section(
         &self,
         summary: &mut BufWriter,
@@ -396,7 +490,7 @@ impl DocBuilder {
                 let summary_path = path.join(Self::README);
                 summary.write_link_list_item(
                     &format!("❱ {title}"),
-                    &summary_path.display().to_string(),
+                    &self.summary_link(&summary_path),
                     depth - 1,
                 )?;
             }
@@ -431,11 +525,7 @@ impl DocBuilder {
                     let summary_path = file
                         .target_path
                         .strip_prefix(self.out_dir().strip_prefix(&self.root)?.join(Self::SRC))?;
-                    summary.write_link_list_item(
-                        ident,
-                        &summary_path.display().to_string(),
-                        depth,
-                    )?;
+                    summary.write_link_list_item(ident, &self.summary_link(summary_path), depth)?;
 
                     let readme_path = base_path
                         .map(|path| summary_path.strip_prefix(path))
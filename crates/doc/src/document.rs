@@ -26,6 +26,8 @@ pub struct Document {
     pub from_library: bool,
     /// The target directory for the doc output.
     pub out_target_dir: PathBuf,
+    /// Whether the internal and private members are documented, labeled by visibility.
+    pub include_private: bool,
 }
 
 impl Document {
@@ -45,6 +47,7 @@ impl Document {
             content: DocumentContent::Empty,
             out_target_dir,
             context: Mutex::new(HashMap::default()),
+            include_private: false,
         }
     }
 
@@ -56,6 +59,13 @@ impl Document {
         self
     }
 
+    /// Set whether the internal and private members are documented on the [Document].
+    #[must_use]
+    pub fn with_include_private(mut self, include_private: bool) -> Self {
+        self.include_private = include_private;
+        self
+    }
+
     /// Add a preprocessor result to inner document context.
     pub fn add_context(&self, id: PreprocessorId, output: PreprocessorOutput) {
         let mut context = self.context.lock().expect("failed to lock context");
@@ -1,3 +1,6 @@
+use solang_parser::pt::{
+    FunctionAttribute, FunctionDefinition, VariableAttribute, VariableDefinition, Visibility,
+};
 use toml::{value::Table, Value};
 
 /// Merge original toml table with the override.
@@ -26,3 +29,35 @@ pub(crate) fn merge_toml_table(table: &mut Table, override_table: Table) {
         };
     }
 }
+
+/// Returns the visibility of the function, `public` if none is declared.
+pub(crate) fn function_visibility(func: &FunctionDefinition) -> Visibility {
+    func.attributes
+        .iter()
+        .find_map(|attr| match attr {
+            FunctionAttribute::Visibility(visibility) => Some(visibility.clone()),
+            _ => None,
+        })
+        .unwrap_or(Visibility::Public(None))
+}
+
+/// Returns the visibility of the state variable, `internal` if none is declared.
+pub(crate) fn variable_visibility(var: &VariableDefinition) -> Visibility {
+    var.attrs
+        .iter()
+        .find_map(|attr| match attr {
+            VariableAttribute::Visibility(visibility) => Some(visibility.clone()),
+            _ => None,
+        })
+        .unwrap_or(Visibility::Internal(None))
+}
+
+/// Returns the label of the visibility if it is not part of the public API of the contract, i.e.
+/// `internal` or `private`.
+pub(crate) fn non_public_label(visibility: &Visibility) -> Option<&'static str> {
+    match visibility {
+        Visibility::Internal(_) => Some("internal"),
+        Visibility::Private(_) => Some("private"),
+        Visibility::External(_) | Visibility::Public(_) => None,
+    }
+}
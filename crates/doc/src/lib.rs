@@ -16,6 +16,9 @@ pub use document::Document;
 
 mod helpers;
 
+mod missing;
+pub use missing::{find_missing_natspec, parse_required_tags, MissingNatSpec};
+
 mod parser;
 pub use parser::{
     error, Comment, CommentTag, Comments, CommentsRef, ParseItem, ParseSource, Parser,
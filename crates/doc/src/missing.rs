@@ -0,0 +1,213 @@
+//! Detection of the public API lacking NatSpec documentation.
+
+use crate::{helpers::function_visibility, CommentTag, Comments, ParseItem, ParseSource};
+use solang_parser::pt::{FunctionTy, Identifier, Visibility};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// An item of the public API lacking some of the required NatSpec tags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingNatSpec {
+    /// The source file of the item, relative to the project root.
+    pub path: PathBuf,
+    /// The kind of the item: `function`, `event` or `error`.
+    pub kind: &'static str,
+    /// The name of the item, qualified with the name of its contract, e.g. `Counter.setNumber`.
+    pub name: String,
+    /// The missing tags, e.g. `@param newNumber`.
+    pub missing: Vec<String>,
+}
+
+impl fmt::Display for MissingNatSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} `{}` is missing {}",
+            self.path.display(),
+            self.kind,
+            self.name,
+            self.missing.join(", ")
+        )
+    }
+}
+
+/// Parses the NatSpec tags which can be required: `notice`, `param` and `return`.
+pub fn parse_required_tags(tags: &[String]) -> eyre::Result<Vec<CommentTag>> {
+    tags.iter()
+        .map(|tag| match tag.trim().trim_start_matches('@') {
+            "notice" => Ok(CommentTag::Notice),
+            "param" => Ok(CommentTag::Param),
+            "return" => Ok(CommentTag::Return),
+            tag => eyre::bail!(
+                "`{tag}` cannot be a required tag, expected one of `notice`, `param` and `return`"
+            ),
+        })
+        .collect()
+}
+
+/// Returns the public and external functions, the events and the errors of the parsed items which
+/// lack any of the required tags.
+///
+/// Items documented with `@inheritdoc` are documented by their base, and are never reported.
+pub fn find_missing_natspec(
+    path: PathBuf,
+    items: &[ParseItem],
+    required: &[CommentTag],
+) -> Vec<MissingNatSpec> {
+    let mut missing = Vec::new();
+    collect_missing(&path, None, items, required, &mut missing);
+    missing
+}
+
+fn collect_missing(
+    path: &Path,
+    contract: Option<&str>,
+    items: &[ParseItem],
+    required: &[CommentTag],
+    out: &mut Vec<MissingNatSpec>,
+) {
+    for item in items {
+        let (kind, params, returns) = match &item.source {
+            ParseSource::Contract(_) => {
+                let name = item.source.ident();
+                collect_missing(path, Some(&name), &item.children, required, out);
+                continue
+            }
+            ParseSource::Function(func) => {
+                let public = !matches!(
+                    function_visibility(func),
+                    Visibility::Internal(_) | Visibility::Private(_)
+                );
+                if contract.is_none() || func.ty != FunctionTy::Function || !public {
+                    continue
+                }
+                let names =
+                    func.params.iter().filter_map(|(_, param)| param.as_ref()?.name.as_ref());
+                ("function", names.collect(), func.returns.len())
+            }
+            ParseSource::Event(event) => {
+                ("event", event.fields.iter().filter_map(|field| field.name.as_ref()).collect(), 0)
+            }
+            ParseSource::Error(error) => {
+                ("error", error.fields.iter().filter_map(|field| field.name.as_ref()).collect(), 0)
+            }
+            _ => continue,
+        };
+
+        let tags = missing_tags(&item.comments, required, &params, returns);
+        if !tags.is_empty() {
+            let ident = item.source.ident();
+            out.push(MissingNatSpec {
+                path: path.to_path_buf(),
+                kind,
+                name: contract.map_or(ident.clone(), |contract| format!("{contract}.{ident}")),
+                missing: tags,
+            });
+        }
+    }
+}
+
+/// Returns the required tags missing from the comments of an item with the given named parameters
+/// and number of return values.
+fn missing_tags(
+    comments: &Comments,
+    required: &[CommentTag],
+    params: &[&Identifier],
+    returns: usize,
+) -> Vec<String> {
+    if comments.iter().any(|comment| comment.tag == CommentTag::Inheritdoc) {
+        return Vec::new()
+    }
+
+    let mut missing = Vec::new();
+    if required.contains(&CommentTag::Notice) &&
+        !comments.iter().any(|comment| comment.tag == CommentTag::Notice)
+    {
+        missing.push("@notice".to_string());
+    }
+    if required.contains(&CommentTag::Param) {
+        for param in params {
+            let documented = comments.iter().any(|comment| {
+                comment.tag == CommentTag::Param &&
+                    comment.value.split_whitespace().next() == Some(param.name.as_str())
+            });
+            if !documented {
+                missing.push(format!("@param {}", param.name));
+            }
+        }
+    }
+    if required.contains(&CommentTag::Return) {
+        let documented = comments.include_tag(CommentTag::Return).len();
+        if documented < returns {
+            missing.push("@return".to_string());
+        }
+    }
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use forge_fmt::Visitable;
+
+    fn missing(src: &str) -> Vec<(String, Vec<String>)> {
+        let (mut source, comments) = solang_parser::parse(src, 0).unwrap();
+        let mut parser = Parser::new(comments, src.to_owned());
+        source.visit(&mut parser).unwrap();
+        let required =
+            parse_required_tags(&["notice".into(), "param".into(), "return".into()]).unwrap();
+        find_missing_natspec("A.sol".into(), &parser.items(), &required)
+            .into_iter()
+            .map(|missing| (missing.name, missing.missing))
+            .collect()
+    }
+
+    #[test]
+    fn finds_missing_tags() {
+        let missing = missing(
+            r#"
+contract A {
+    /// @notice Sets the number.
+    /// @param newNumber The new number.
+    function setNumber(uint256 newNumber) public {}
+
+    /// Increments the number.
+    function increment(uint256 by) external returns (uint256) {}
+
+    function _internal() internal {}
+
+    /// @inheritdoc B
+    function inherited(uint256 x) external returns (uint256) {}
+
+    event Transfer(address indexed from, address to);
+
+    /// @notice The number is too large.
+    /// @param max The maximum.
+    error TooLarge(uint256 max);
+}
+"#,
+        );
+        assert_eq!(
+            missing,
+            [
+                ("A.increment".to_string(), vec!["@param by".to_string(), "@return".to_string()]),
+                (
+                    "A.Transfer".to_string(),
+                    vec!["@notice".to_string(), "@param from".to_string(), "@param to".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_required_tags() {
+        assert_eq!(
+            parse_required_tags(&["@notice".into(), "return".into()]).unwrap(),
+            [CommentTag::Notice, CommentTag::Return]
+        );
+        assert!(parse_required_tags(&["dev".into()]).is_err());
+    }
+}
@@ -1,13 +1,14 @@
 //! The parser module.
 
+use crate::helpers::{function_visibility, non_public_label, variable_visibility};
 use forge_fmt::{FormatterConfig, Visitable, Visitor};
 use itertools::Itertools;
 use solang_parser::{
     doccomment::{parse_doccomments, DocComment},
     pt::{
-        Comment as SolangComment, EnumDefinition, ErrorDefinition, EventDefinition,
+        Comment as SolangComment, ContractTy, EnumDefinition, ErrorDefinition, EventDefinition,
         FunctionDefinition, Identifier, Loc, SourceUnit, SourceUnitPart, StructDefinition,
-        TypeDefinition, VariableDefinition,
+        TypeDefinition, VariableDefinition, Visibility,
     },
 };
 
@@ -38,6 +39,8 @@ pub struct Parser {
     source: String,
     /// The formatter config.
     fmt: FormatterConfig,
+    /// Whether to leave the internal and private members of contracts out.
+    public_only: bool,
 }
 
 /// [Parser] context.
@@ -61,6 +64,12 @@ impl Parser {
         self
     }
 
+    /// Set whether to leave the internal and private members of contracts out on the [Parser]
+    pub fn with_public_only(mut self, public_only: bool) -> Self {
+        self.public_only = public_only;
+        self
+    }
+
     /// Return the parsed items. Consumes the parser.
     pub fn items(self) -> Vec<ParseItem> {
         self.items
@@ -97,6 +106,28 @@ impl Parser {
         Ok(())
     }
 
+    /// Whether a member with the given visibility should be left out of the docs.
+    ///
+    /// Internal and private members of contracts are left out if only the public API is
+    /// documented. Library members are always documented, since internal functions are the main
+    /// API of most libraries.
+    fn is_hidden_member(&self, visibility: &Visibility) -> bool {
+        if !self.public_only || non_public_label(visibility).is_none() {
+            return false;
+        }
+        match self.context.parent.as_ref().map(|parent| &parent.source) {
+            Some(ParseSource::Contract(contract)) => !matches!(contract.ty, ContractTy::Library(_)),
+            _ => false,
+        }
+    }
+
+    /// Moves the doc comment pointer past an element that is left out of the docs, so its
+    /// comments are not attached to the next element.
+    fn skip_element(&mut self, loc: Loc) -> ParserResult<()> {
+        self.context.doc_start_loc = loc.end();
+        Ok(())
+    }
+
     /// Create new [ParseItem] with comments and formatted code.
     fn new_item(&mut self, source: ParseSource, loc_start: usize) -> ParserResult<ParseItem> {
         let docs = self.parse_docs(loc_start)?;
@@ -176,10 +207,17 @@ impl Visitor for Parser {
     }
 
     fn visit_var_definition(&mut self, var: &mut VariableDefinition) -> ParserResult<()> {
+        if self.is_hidden_member(&variable_visibility(var)) {
+            return self.skip_element(var.loc);
+        }
         self.add_element_to_parent(ParseSource::Variable(var.clone()), var.loc)
     }
 
     fn visit_function(&mut self, func: &mut FunctionDefinition) -> ParserResult<()> {
+        if self.is_hidden_member(&function_visibility(func)) {
+            return self.skip_element(func.loc);
+        }
+
         // If the function parameter doesn't have a name, try to set it with
         // `@custom:name` tag if any was provided
         let mut start_loc = func.loc.start();
@@ -347,5 +385,40 @@ mod tests {
         assert!(matches!(fallback.source, ParseSource::Function(_)));
     }
 
+    #[test]
+    fn contract_with_private_members() {
+        let src = r"
+            contract Contract {
+                uint256 public publicVar;
+                uint256 internalVar;
+
+                function externalFn() external {}
+                /// @notice Some internal function.
+                function internalFn() internal {}
+                function privateFn() private {}
+            }
+
+            library Library {
+                function internalFn() internal {}
+            }
+        ";
+
+        let items = parse_source(src);
+        let contract = items.first().unwrap();
+        let members = contract.children.iter().map(|ch| ch.source.ident()).collect::<Vec<_>>();
+        assert_eq!(members, ["publicVar", "internalVar", "externalFn", "internalFn", "privateFn"]);
+        assert_eq!(contract.children[3].comments.len(), 1);
+
+        let (mut source, comments) = parse(src, 0).unwrap();
+        let mut doc = Parser::new(comments, src.to_owned()).with_public_only(true);
+        source.visit(&mut doc).unwrap();
+        let items = doc.items();
+        let contract = items.first().unwrap();
+        let members = contract.children.iter().map(|ch| ch.source.ident()).collect::<Vec<_>>();
+        assert_eq!(members, ["publicVar", "externalFn"]);
+        let library = items.get(1).unwrap();
+        assert_eq!(library.children.len(), 1);
+    }
+
     // TODO: test regular doc comments & natspec
 }
@@ -0,0 +1,291 @@
+use super::{Preprocessor, PreprocessorId};
+use crate::{
+    document::DocumentContent, helpers::function_visibility, Comments, Document, ParseItem,
+    ParseSource, PreprocessorOutput,
+};
+use forge_fmt::solang_ext::SafeUnwrap;
+use foundry_compilers::utils::canonicalize;
+use itertools::Itertools;
+use solang_parser::pt::{
+    ContractDefinition, EventDefinition, FunctionDefinition, FunctionTy, Visibility,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+/// [InheritedMembers] preprocessor id.
+pub const INHERITED_MEMBERS_ID: PreprocessorId = PreprocessorId("inherited_members");
+
+/// The inherited members preprocessor.
+/// It matches the documents with inner [`ParseSource::Contract`](crate::ParseSource) elements,
+/// resolves the bases they name through the imports of their file, linearizes them like solc
+/// does, and collects the functions and events defined by the bases which are not overridden by a
+/// more derived contract.
+///
+/// This preprocessor writes to [Document]'s context.
+#[derive(Debug, Default)]
+pub struct InheritedMembers {
+    /// Whether to capture members inherited from libraries.
+    pub include_libraries: bool,
+    /// Whether the inherited members are documented inline, instead of being linked to.
+    pub inline: bool,
+    /// The files transitively imported by each source file, keyed by their canonical path.
+    ///
+    /// A base is resolved among the contracts of the file naming it, then of the files it
+    /// imports. Bases of files missing from the map only resolve to a contract with a unique
+    /// name.
+    pub imports: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+/// The members inherited by a contract.
+#[derive(Clone, Debug)]
+pub struct InheritedMembersOutput {
+    /// Whether the members are documented inline, instead of being linked to.
+    pub inline: bool,
+    /// The bases defining the members, from the most derived one.
+    pub bases: Vec<InheritedBase>,
+}
+
+/// The members inherited from a single base contract.
+#[derive(Clone, Debug)]
+pub struct InheritedBase {
+    /// The name of the base contract.
+    pub name: String,
+    /// The path of the document of the base contract.
+    pub target_path: PathBuf,
+    /// The inherited functions.
+    pub functions: Vec<InheritedItem<FunctionDefinition>>,
+    /// The inherited events.
+    pub events: Vec<InheritedItem<EventDefinition>>,
+}
+
+/// An inherited member with its comments and formatted code.
+#[derive(Clone, Debug)]
+pub struct InheritedItem<T> {
+    pub definition: T,
+    pub comments: Comments,
+    pub code: String,
+}
+
+impl Preprocessor for InheritedMembers {
+    fn id(&self) -> PreprocessorId {
+        INHERITED_MEMBERS_ID
+    }
+
+    fn preprocess(&self, documents: Vec<Document>) -> Result<Vec<Document>, eyre::Error> {
+        let resolver = BaseResolver::new(&documents, &self.imports);
+        for (idx, document) in documents.iter().enumerate() {
+            if let DocumentContent::Single(ref item) = document.content {
+                if let ParseSource::Contract(_) = item.source {
+                    let bases = self.inherited_members(idx, item, &resolver);
+                    if !bases.is_empty() {
+                        let output = InheritedMembersOutput { inline: self.inline, bases };
+                        document
+                            .add_context(self.id(), PreprocessorOutput::InheritedMembers(output));
+                    }
+                }
+            }
+        }
+
+        Ok(documents)
+    }
+}
+
+impl InheritedMembers {
+    /// Collects the members of the bases of the contract which are not overridden.
+    fn inherited_members(
+        &self,
+        idx: usize,
+        item: &ParseItem,
+        resolver: &BaseResolver<'_>,
+    ) -> Vec<InheritedBase> {
+        let Some(linearized) = resolver.linearize(idx, &mut Vec::new()) else { return Vec::new() };
+
+        let mut seen = HashSet::new();
+        for (func, _, _) in item.functions().unwrap_or_default() {
+            seen.insert(function_signature(func));
+        }
+        for (event, _, _) in item.events().unwrap_or_default() {
+            seen.insert(event_signature(event));
+        }
+
+        let mut bases = Vec::new();
+        for base in linearized.iter().skip(1) {
+            let Some(idx) = base.document else { continue };
+            let (document, base_item) = resolver.contract(idx);
+
+            let mut functions = Vec::new();
+            for (func, comments, code) in base_item.functions().unwrap_or_default() {
+                let inherited = func.ty == FunctionTy::Function &&
+                    !matches!(function_visibility(func), Visibility::Private(_));
+                if inherited && seen.insert(function_signature(func)) {
+                    functions.push(InheritedItem {
+                        definition: func.clone(),
+                        comments: comments.clone(),
+                        code: code.clone(),
+                    });
+                }
+            }
+            let mut events = Vec::new();
+            for (event, comments, code) in base_item.events().unwrap_or_default() {
+                if seen.insert(event_signature(event)) {
+                    events.push(InheritedItem {
+                        definition: event.clone(),
+                        comments: comments.clone(),
+                        code: code.clone(),
+                    });
+                }
+            }
+
+            // the members of bases without a page are considered overridden, but not listed
+            if document.from_library && !self.include_libraries {
+                continue
+            }
+            if !functions.is_empty() || !events.is_empty() {
+                bases.push(InheritedBase {
+                    name: base.name.clone(),
+                    target_path: document.target_path.clone(),
+                    functions,
+                    events,
+                });
+            }
+        }
+        bases
+    }
+}
+
+/// A base contract of a linearization.
+#[derive(Clone, Debug, PartialEq)]
+struct Base {
+    /// The name of the base contract.
+    name: String,
+    /// The index of the document of the base contract, if it has one.
+    document: Option<usize>,
+}
+
+/// Resolves the bases named by the contracts to their documents.
+struct BaseResolver<'a> {
+    documents: &'a [Document],
+    /// The canonical source file path of each document.
+    paths: Vec<PathBuf>,
+    imports: &'a HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl<'a> BaseResolver<'a> {
+    fn new(documents: &'a [Document], imports: &'a HashMap<PathBuf, HashSet<PathBuf>>) -> Self {
+        let paths = documents
+            .iter()
+            .map(|document| {
+                canonicalize(&document.item_path).unwrap_or_else(|_| document.item_path.clone())
+            })
+            .collect();
+        Self { documents, paths, imports }
+    }
+
+    /// Returns the document and the parsed contract of the document at the given index.
+    fn contract(&self, idx: usize) -> (&'a Document, &'a ParseItem) {
+        let document = &self.documents[idx];
+        let DocumentContent::Single(item) = &document.content else { unreachable!() };
+        (document, item)
+    }
+
+    /// Returns the contract definition of the document at the given index, if it is one.
+    fn contract_definition(&self, idx: usize) -> Option<&'a ContractDefinition> {
+        match &self.documents[idx].content {
+            DocumentContent::Single(item) => match &item.source {
+                ParseSource::Contract(contract) => Some(contract),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Finds the document of the contract with the given name, as seen from the file of the
+    /// document at index `from`.
+    fn find(&self, name: &str, from: usize) -> Option<usize> {
+        let candidates = (0..self.documents.len())
+            .filter(|&idx| {
+                self.contract_definition(idx)
+                    .is_some_and(|contract| contract.name.safe_unwrap().name == name)
+            })
+            .collect::<Vec<_>>();
+
+        let path = &self.paths[from];
+        if let Some(&idx) = candidates.iter().find(|&&idx| self.paths[idx] == *path) {
+            return Some(idx)
+        }
+        match self.imports.get(path) {
+            Some(imports) => candidates.into_iter().find(|&idx| imports.contains(&self.paths[idx])),
+            None => candidates.into_iter().exactly_one().ok(),
+        }
+    }
+
+    /// Returns the C3 linearization of the contract of the document at the given index, from
+    /// the contract itself to its most base-like base, as computed by solc.
+    ///
+    /// Bases without a document are kept, without bases of their own. Returns `None` if the
+    /// inheritance graph has a cycle or cannot be linearized.
+    fn linearize(&self, idx: usize, visiting: &mut Vec<usize>) -> Option<Vec<Base>> {
+        if visiting.contains(&idx) {
+            return None
+        }
+        let contract = self.contract_definition(idx)?;
+
+        // solc considers the last listed base the most derived one
+        let bases = contract
+            .base
+            .iter()
+            .rev()
+            .map(|base| {
+                let name = base.name.identifiers.last().unwrap().name.clone();
+                let document = self.find(&name, idx);
+                Base { name, document }
+            })
+            .collect::<Vec<_>>();
+
+        visiting.push(idx);
+        let mut sequences = bases
+            .iter()
+            .map(|base| match base.document {
+                Some(idx) => self.linearize(idx, visiting),
+                None => Some(vec![base.clone()]),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        visiting.pop();
+        sequences.push(bases);
+
+        let name = contract.name.safe_unwrap().name.clone();
+        let mut linearized = vec![Base { name, document: Some(idx) }];
+        loop {
+            sequences.retain(|sequence| !sequence.is_empty());
+            if sequences.is_empty() {
+                return Some(linearized)
+            }
+            // the first head which is not in the tail of any sequence
+            let head = sequences
+                .iter()
+                .map(|sequence| &sequence[0])
+                .find(|head| sequences.iter().all(|sequence| !sequence[1..].contains(head)))?
+                .clone();
+            for sequence in &mut sequences {
+                if sequence[0] == head {
+                    sequence.remove(0);
+                }
+            }
+            linearized.push(head);
+        }
+    }
+}
+
+fn function_signature(func: &FunctionDefinition) -> String {
+    let name = func.name.as_ref().map_or(func.ty.to_string(), |n| n.name.to_owned());
+    let params =
+        func.params.iter().map(|p| p.1.as_ref().map(|p| p.ty.to_string()).unwrap_or_default());
+    format!("function {name}({})", params.format(","))
+}
+
+fn event_signature(event: &EventDefinition) -> String {
+    let params = event.fields.iter().map(|field| field.ty.to_string());
+    format!("event {}({})", event.name.safe_unwrap().name, params.format(","))
+}
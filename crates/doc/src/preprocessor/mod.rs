@@ -9,6 +9,11 @@ pub use contract_inheritance::{ContractInheritance, CONTRACT_INHERITANCE_ID};
 mod inheritdoc;
 pub use inheritdoc::{Inheritdoc, INHERITDOC_ID};
 
+mod inherited_members;
+pub use inherited_members::{
+    InheritedBase, InheritedItem, InheritedMembers, InheritedMembersOutput, INHERITED_MEMBERS_ID,
+};
+
 mod infer_hyperlinks;
 pub use infer_hyperlinks::{InferInlineHyperlinks, INFER_INLINE_HYPERLINKS_ID};
 
@@ -33,6 +38,9 @@ pub enum PreprocessorOutput {
     /// The inheritdoc output.
     /// The map of inherited item keys to their comments.
     Inheritdoc(HashMap<String, Comments>),
+    /// The inherited members output.
+    /// The functions and events inherited from the bases of the contract.
+    InheritedMembers(InheritedMembersOutput),
     /// The git source output.
     /// The git url of the item path.
     GitSource(String),
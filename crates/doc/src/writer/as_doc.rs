@@ -1,9 +1,11 @@
 use crate::{
     document::{read_context, DocumentContent},
+    helpers::{function_visibility, non_public_label, variable_visibility},
     parser::ParseSource,
     writer::BufWriter,
-    CommentTag, Comments, CommentsRef, Document, Markdown, PreprocessorOutput,
-    CONTRACT_INHERITANCE_ID, DEPLOYMENTS_ID, GIT_SOURCE_ID, INHERITDOC_ID,
+    CommentTag, Comments, CommentsRef, Document, InheritedMembersOutput, Markdown,
+    PreprocessorOutput, CONTRACT_INHERITANCE_ID, DEPLOYMENTS_ID, GIT_SOURCE_ID, INHERITDOC_ID,
+    INHERITED_MEMBERS_ID,
 };
 use forge_fmt::solang_ext::SafeUnwrap;
 use itertools::Itertools;
@@ -171,6 +173,13 @@ impl AsDoc for Document {
                                 );
 
                                 writer.write_heading(&item.name.safe_unwrap().name)?;
+                                if self.include_private {
+                                    if let Some(label) =
+                                        non_public_label(&variable_visibility(item))
+                                    {
+                                        writer.write_visibility(label)?;
+                                    }
+                                }
                                 writer.write_section(&comments, code)?;
                                 writer.writeln()
                             })?;
@@ -218,6 +227,12 @@ impl AsDoc for Document {
                                 writer.write_section(comments, code)
                             })?;
                         }
+
+                        if let Some(inherited) =
+                            read_context!(self, INHERITED_MEMBERS_ID, InheritedMembers)
+                        {
+                            self.write_inherited_members(&mut writer, &inherited)?;
+                        }
                     }
 
                     ParseSource::Function(func) => {
@@ -277,6 +292,52 @@ impl Document {
         self.out_target_dir.join("src")
     }
 
+    /// Writes the members inherited from the bases of a contract to the buffer, either with their
+    /// documentation or as links to the pages of the bases.
+    fn write_inherited_members(
+        &self,
+        writer: &mut BufWriter,
+        inherited: &InheritedMembersOutput,
+    ) -> Result<(), std::fmt::Error> {
+        writer.write_subtitle("Inherited Members")?;
+
+        let src_target_dir = self.target_src_dir();
+        for base in &inherited.bases {
+            let path = Path::new("/")
+                .join(base.target_path.strip_prefix(&src_target_dir).unwrap_or(&base.target_path));
+            let path = path.display().to_string();
+            writer.write_heading(&format!("From {}", Markdown::Link(&base.name, &path)))?;
+
+            if inherited.inline {
+                for func in &base.functions {
+                    self.write_function(writer, &func.definition, &func.comments, &func.code)?;
+                }
+                for event in &base.events {
+                    writer.write_heading(&event.definition.name.safe_unwrap().name)?;
+                    writer.write_section(&event.comments, &event.code)?;
+                    writer.try_write_events_table(&event.definition.fields, &event.comments)?;
+                }
+                continue
+            }
+
+            // mdbook anchors headings by their lowercased text
+            for func in &base.functions {
+                let name = func.definition.name.safe_unwrap().name.as_str();
+                let link =
+                    Markdown::Link(name, &format!("{path}#{}", name.to_lowercase())).as_doc()?;
+                writer.write_list_item(&format!("function {link}"), 0)?;
+            }
+            for event in &base.events {
+                let name = event.definition.name.safe_unwrap().name.as_str();
+                let link =
+                    Markdown::Link(name, &format!("{path}#{}", name.to_lowercase())).as_doc()?;
+                writer.write_list_item(&format!("event {link}"), 0)?;
+            }
+            writer.writeln()?;
+        }
+        Ok(())
+    }
+
     /// Writes a function to the buffer.
     fn write_function(
         &self,
@@ -294,6 +355,13 @@ impl Document {
 
         writer.writeln()?;
 
+        // Label the members that are not part of the public API
+        if self.include_private {
+            if let Some(label) = non_public_label(&function_visibility(func)) {
+                writer.write_visibility(label)?;
+            }
+        }
+
         // Write function docs
         writer.writeln_doc(comments.exclude_tags(&[CommentTag::Param, CommentTag::Return]))?;
 
@@ -85,6 +85,13 @@ impl BufWriter {
         writeln!(self.buf, "{}", Markdown::Bold(text))
     }
 
+    /// Writes the visibility of a member to the buffer.
+    pub fn write_visibility(&mut self, visibility: &str) -> fmt::Result {
+        self.write_bold("Visibility:")?;
+        self.writeln_raw(visibility)?;
+        self.writeln()
+    }
+
     /// Writes link to the buffer formatted as [Markdown::Link].
     pub fn write_link(&mut self, name: &str, path: &str) -> fmt::Result {
         writeln!(self.buf, "{}", Markdown::Link(name, path))
@@ -4,6 +4,7 @@
 //! concurrently active pairs at once.
 
 use super::CreateFork;
+use crate::opts::EvmOpts;
 use foundry_common::provider::{
     runtime_transport::RuntimeTransport, tower::RetryBackoffService, ProviderBuilder, RetryProvider,
 };
@@ -19,6 +20,7 @@ use revm::primitives::Env;
 use std::{
     collections::HashMap,
     fmt::{self, Write},
+    path::PathBuf,
     pin::Pin,
     sync::{
         atomic::AtomicUsize,
@@ -480,6 +482,35 @@ impl Drop for ShutDownMultiFork {
     }
 }
 
+/// Returns the path of the storage cache of the fork at the given block, if it should be cached.
+///
+/// Forks of a pinned block are cached indefinitely. Forks of the latest block are not cached if
+/// the `rpc_cache_ttl` is zero, and their cache is removed once it is older than the TTL so the
+/// state is fetched again.
+fn fork_cache_path(opts: &EvmOpts, chain_id: u64, number: u64) -> Option<PathBuf> {
+    expire_fork_cache(opts, Config::foundry_block_cache_dir(chain_id, number)?)
+}
+
+/// Applies the `rpc_cache_ttl` of the options to the storage cache at `path`, see
+/// [fork_cache_path].
+fn expire_fork_cache(opts: &EvmOpts, path: PathBuf) -> Option<PathBuf> {
+    let Some(ttl) = opts.rpc_cache_ttl.filter(|_| opts.fork_block_number.is_none()) else {
+        return Some(path)
+    };
+    if ttl.is_zero() {
+        return None
+    }
+
+    let expired = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().map_or(false, |age| age > ttl));
+    if expired && path.is_file() {
+        trace!(target: "fork::multi", ?path, "removing expired fork cache");
+        let _ = std::fs::remove_file(&path);
+    }
+    Some(path)
+}
+
 /// Creates a new fork
 ///
 /// This will establish a new `Provider` to the endpoint and return the Fork Backend
@@ -503,7 +534,7 @@ async fn create_fork(mut fork: CreateFork) -> eyre::Result<(ForkId, CreatedFork,
 
     // determine the cache path if caching is enabled
     let cache_path = if fork.enable_caching {
-        Config::foundry_block_cache_dir(meta.cfg_env.chain_id, number)
+        fork_cache_path(&fork.evm_opts, meta.cfg_env.chain_id, number)
     } else {
         None
     };
@@ -515,3 +546,52 @@ async fn create_fork(mut fork: CreateFork) -> eyre::Result<(ForkId, CreatedFork,
 
     Ok((fork_id, fork, handler))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        fs::File,
+        time::{Duration, SystemTime},
+    };
+
+    fn stale_cache(dir: &tempfile::TempDir) -> PathBuf {
+        let path = dir.path().join("storage.json");
+        let file = File::create(&path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(3600)).unwrap();
+        path
+    }
+
+    #[test]
+    fn discards_expired_latest_fork_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = stale_cache(&dir);
+        let opts = EvmOpts { rpc_cache_ttl: Some(Duration::from_secs(60)), ..Default::default() };
+
+        assert_eq!(expire_fork_cache(&opts, path.clone()), Some(path.clone()));
+        assert!(!path.exists());
+
+        // fresh caches are kept
+        File::create(&path).unwrap();
+        assert_eq!(expire_fork_cache(&opts, path.clone()), Some(path.clone()));
+        assert!(path.exists());
+
+        // a zero TTL disables the cache
+        let opts = EvmOpts { rpc_cache_ttl: Some(Duration::ZERO), ..Default::default() };
+        assert_eq!(expire_fork_cache(&opts, path), None);
+    }
+
+    #[test]
+    fn keeps_pinned_fork_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = stale_cache(&dir);
+        let opts = EvmOpts {
+            fork_block_number: Some(1),
+            rpc_cache_ttl: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        assert_eq!(expire_fork_cache(&opts, path.clone()), Some(path.clone()));
+        assert!(path.exists());
+    }
+}
@@ -8,6 +8,7 @@ use foundry_common::{provider::ProviderBuilder, ALCHEMY_FREE_TIER_CUPS};
 use foundry_config::{Chain, Config};
 use revm::primitives::{BlockEnv, CfgEnv, TxEnv};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct EvmOpts {
@@ -39,6 +40,12 @@ pub struct EvmOpts {
     /// Disables storage caching entirely.
     pub no_storage_caching: bool,
 
+    /// How long the cached state of forks of the latest block is reused.
+    ///
+    /// Forks of a pinned block are always cached, a TTL of zero disables caching of the others.
+    #[serde(skip)]
+    pub rpc_cache_ttl: Option<Duration>,
+
     /// The initial balance of each deployed test contract.
     pub initial_balance: U256,
 
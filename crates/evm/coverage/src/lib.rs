@@ -150,6 +150,25 @@ impl CoverageReport {
         Ok(())
     }
 
+    /// Returns the IDs of the coverage items hit at least once in the [`HitMap`] of the contract.
+    ///
+    /// Like [`add_hit_map`](Self::add_hit_map), this should only be called after all the anchors
+    /// were added.
+    pub fn hit_items(
+        &self,
+        contract_id: &ContractId,
+        hit_map: &HitMap,
+        is_deployed_code: bool,
+    ) -> Vec<usize> {
+        let Some(anchors) = self.anchors.get(contract_id) else { return Vec::new() };
+        let anchors = if is_deployed_code { &anchors.1 } else { &anchors.0 };
+        anchors
+            .iter()
+            .filter(|anchor| hit_map.hits.get(&anchor.instruction).is_some_and(|&hits| hits > 0))
+            .map(|anchor| anchor.item_id)
+            .collect()
+    }
+
     /// Removes all the coverage items that should be ignored by the filter.
     ///
     /// This function should only be called after all the sources were used, otherwise, the output
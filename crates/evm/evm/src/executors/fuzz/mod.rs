@@ -13,6 +13,7 @@ use foundry_evm_coverage::HitMaps;
 use foundry_evm_fuzz::{
     strategies::{fuzz_calldata, fuzz_calldata_from_state, EvmFuzzState},
     BaseCounterExample, CounterExample, FuzzCase, FuzzError, FuzzFixtures, FuzzTestResult,
+    TooManyRejects,
 };
 use foundry_evm_traces::CallTraceArena;
 use indicatif::ProgressBar;
@@ -170,9 +171,12 @@ impl FuzzedExecutor {
             // `vm.assume` cheatcode, thus we surface this info to the user when the fuzz test
             // aborts due to too many global rejects, making the error message more actionable.
             Err(TestError::Abort(reason)) if reason.message() == "Too many global rejects" => {
-                result.reason = Some(
-                    FuzzError::TooManyRejects(self.runner.config().max_global_rejects).to_string(),
-                );
+                let err = FuzzError::TooManyRejects(TooManyRejects {
+                    test: func.signature(),
+                    rejects: self.runner.config().max_global_rejects,
+                    accepted: result.gas_by_case.len(),
+                });
+                result.reason = Some(err.to_string());
             }
             Err(TestError::Abort(reason)) => {
                 result.reason = Some(reason.to_string());
@@ -242,7 +246,11 @@ impl FuzzedExecutor {
     /// Stores fuzz state for use with [fuzz_calldata_from_state]
     pub fn build_fuzz_state(&self) -> EvmFuzzState {
         if let Some(fork_db) = self.executor.backend().active_fork_db() {
-            EvmFuzzState::new(fork_db, self.config.dictionary)
+            if self.config.fork_dictionary {
+                EvmFuzzState::with_fork_values(fork_db, self.config.dictionary)
+            } else {
+                EvmFuzzState::new(fork_db, self.config.dictionary)
+            }
         } else {
             EvmFuzzState::new(self.executor.backend().mem_db(), self.config.dictionary)
         }
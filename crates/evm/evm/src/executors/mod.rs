@@ -427,6 +427,10 @@ impl Executor {
             // Clear broadcastable transactions
             cheats.broadcastable_transactions.clear();
 
+            // Matched expected reverts are only reported for the call they happened in, e.g. not
+            // for the tests run after `setUp`.
+            cheats.matched_expected_reverts.clear();
+
             // corrected_nonce value is needed outside of this context (setUp), so we don't
             // reset it.
         }
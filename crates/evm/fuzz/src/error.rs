@@ -1,5 +1,6 @@
 //! errors related to fuzz tests
 use proptest::test_runner::Reason;
+use std::fmt;
 
 /// Possible errors when running fuzz tests
 #[derive(Debug, thiserror::Error)]
@@ -10,8 +11,54 @@ pub enum FuzzError {
     FailedContractCall,
     #[error("`vm.assume` reject")]
     AssumeReject,
-    #[error("The `vm.assume` cheatcode rejected too many inputs ({0} allowed)")]
-    TooManyRejects(u32),
+    #[error("{0}")]
+    TooManyRejects(TooManyRejects),
+}
+
+/// The fuzz test was aborted because `vm.assume` rejected too many inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TooManyRejects {
+    /// The signature of the fuzz test.
+    pub test: String,
+    /// The maximum number of rejected inputs.
+    pub rejects: u32,
+    /// The number of inputs accepted before the limit was hit.
+    pub accepted: usize,
+}
+
+impl TooManyRejects {
+    /// The acceptance rate below which rejecting inputs is considered inefficient.
+    const LOW_ACCEPTANCE_RATE: f64 = 0.1;
+
+    /// Returns the ratio of accepted inputs over all the generated ones.
+    pub fn acceptance_rate(&self) -> f64 {
+        let total = self.accepted as f64 + self.rejects as f64;
+        if total == 0.0 {
+            return 0.0
+        }
+        self.accepted as f64 / total
+    }
+}
+
+impl fmt::Display for TooManyRejects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = self.acceptance_rate();
+        write!(
+            f,
+            "The `vm.assume` cheatcode rejected too many inputs of `{}` ({} allowed, {} accepted, \
+             {:.2}% acceptance rate)",
+            self.test,
+            self.rejects,
+            self.accepted,
+            rate * 100.0
+        )?;
+        if rate < Self::LOW_ACCEPTANCE_RATE {
+            f.write_str(
+                "; consider narrowing the input types or bounding the inputs instead of assuming",
+            )?;
+        }
+        Ok(())
+    }
 }
 
 impl From<FuzzError> for Reason {
@@ -19,3 +66,28 @@ impl From<FuzzError> for Reason {
         error.to_string().into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_many_rejects_reason() {
+        let err = FuzzError::TooManyRejects(TooManyRejects {
+            test: "testA(uint256)".into(),
+            rejects: 90,
+            accepted: 10,
+        });
+        assert_eq!(
+            err.to_string(),
+            "The `vm.assume` cheatcode rejected too many inputs of `testA(uint256)` (90 allowed, 10 accepted, 10.00% acceptance rate)"
+        );
+
+        let err = FuzzError::TooManyRejects(TooManyRejects {
+            test: "testA(uint256)".into(),
+            rejects: 99,
+            accepted: 1,
+        });
+        assert!(err.to_string().ends_with("instead of assuming"), "{err}");
+    }
+}
@@ -20,7 +20,7 @@ use std::{collections::HashMap, fmt, sync::Arc};
 pub use proptest::test_runner::{Config as FuzzConfig, Reason};
 
 mod error;
-pub use error::FuzzError;
+pub use error::{FuzzError, TooManyRejects};
 
 pub mod invariant;
 pub mod strategies;
@@ -201,6 +201,11 @@ impl FuzzTestResult {
         calc::mean(&values)
     }
 
+    /// Returns the highest gas use of all test cases
+    pub fn max_gas(&self, with_stipend: bool) -> u64 {
+        self.gas_values(with_stipend).into_iter().max().unwrap_or_default()
+    }
+
     fn gas_values(&self, with_stipend: bool) -> Vec<u64> {
         self.gas_by_case
             .iter()
@@ -9,7 +9,7 @@ use parking_lot::{lock_api::RwLockReadGuard, RawRwLock, RwLock};
 use revm::{
     db::{CacheDB, DatabaseRef, DbAccount},
     interpreter::opcode,
-    primitives::AccountInfo,
+    primitives::{AccountInfo, KECCAK_EMPTY},
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -25,6 +25,12 @@ type AIndexSet<T> = IndexSet<T, std::hash::BuildHasherDefault<ahash::AHasher>>;
 /// bytecode (as is the case with Solmate).
 const PUSH_BYTE_ANALYSIS_LIMIT: usize = 24 * 1024;
 
+/// The number of leading storage slots read from every forked contract when seeding the dictionary
+/// from the forked chain.
+///
+/// These usually hold the owners, admins, token addresses and totals of a contract.
+const FORK_DICTIONARY_SLOTS: u64 = 16;
+
 /// A set of arbitrary 32 byte data from the VM used to generate values for the strategy.
 ///
 /// Wrapped in a shareable container.
@@ -45,6 +51,23 @@ impl EvmFuzzState {
         Self { inner: Arc::new(RwLock::new(dictionary)) }
     }
 
+    /// Creates the fuzz state from a forked database, like [`new`](Self::new), additionally
+    /// seeding the dictionary with the leading storage slots of every forked contract.
+    ///
+    /// Slots that weren't touched yet are fetched from the fork.
+    pub fn with_fork_values<DB: DatabaseRef>(
+        db: &CacheDB<DB>,
+        config: FuzzDictionaryConfig,
+    ) -> Self {
+        let mut accs = db.accounts.iter().collect::<Vec<_>>();
+        accs.sort_by_key(|(address, _)| *address);
+
+        let mut dictionary = FuzzDictionary::new(config);
+        dictionary.insert_fork_storage_values(db, &accs);
+        dictionary.insert_db_values(accs);
+        Self { inner: Arc::new(RwLock::new(dictionary)) }
+    }
+
     pub fn collect_values(&self, values: impl IntoIterator<Item = B256>) {
         let mut dict = self.inner.write();
         for value in values {
@@ -166,6 +189,30 @@ impl FuzzDictionary {
         self.db_addresses = self.addresses.len();
     }
 
+    /// Insert the leading storage slots of the contracts in the db state into fuzz dictionary,
+    /// reading them through the db if not loaded yet.
+    /// These values are persisted across fuzz runs.
+    fn insert_fork_storage_values<DB: DatabaseRef>(
+        &mut self,
+        db: &CacheDB<DB>,
+        db_state: &[(&Address, &DbAccount)],
+    ) {
+        for (address, account) in db_state {
+            if account.info.code_hash == KECCAK_EMPTY {
+                continue;
+            }
+            for slot in 0..FORK_DICTIONARY_SLOTS {
+                let slot = U256::from(slot);
+                // Failing to fetch a slot only makes the dictionary less useful.
+                if let Ok(value) = db.storage_ref(**address, slot) {
+                    if !value.is_zero() {
+                        self.insert_storage_value(&slot, &value);
+                    }
+                }
+            }
+        }
+    }
+
     /// Insert values collected from call result into fuzz dictionary.
     fn insert_result_values(
         &mut self,
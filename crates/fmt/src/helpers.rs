@@ -65,22 +65,14 @@ pub fn format(src: &str) -> Result<String, FormatterError> {
     Ok(output)
 }
 
-/// Converts the start offset of a `Loc` to `(line, col)`
+/// Converts the start offset of a `Loc` to `(line, col)`, both starting at `1`
 pub fn offset_to_line_column(content: &str, start: usize) -> (usize, usize) {
     debug_assert!(content.len() > start);
 
-    // first line is `1`
-    let mut line_counter = 1;
-    for (offset, c) in content.chars().enumerate() {
-        if c == '\n' {
-            line_counter += 1;
-        }
-        if offset > start {
-            return (line_counter, offset - start)
-        }
-    }
-
-    unreachable!("content.len() > start")
+    let before = &content[..start];
+    let line = before.matches('\n').count() + 1;
+    let col = start - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+    (line, col)
 }
 
 /// Formats parser diagnostics
@@ -132,4 +124,13 @@ mod tests {
         let s = "interface I {\n    function increment() external;\n    function number() external view returns (uint256);\n    function setNumber(uint256 newNumber) external;\n}";
         let _formatted = format(s).unwrap();
     }
+
+    #[test]
+    fn test_offset_to_line_column() {
+        let s = "contract A {\n    uint x;\n}\n";
+        assert_eq!(offset_to_line_column(s, 0), (1, 1));
+        assert_eq!(offset_to_line_column(s, 9), (1, 10));
+        assert_eq!(offset_to_line_column(s, 17), (2, 5));
+        assert_eq!(offset_to_line_column(s, 25), (3, 1));
+    }
 }
@@ -1,8 +1,14 @@
 use super::{install, watch::WatchArgs};
 use clap::Parser;
 use eyre::Result;
-use foundry_cli::{opts::CoreBuildArgs, utils::LoadConfig};
-use foundry_common::compile::ProjectCompiler;
+use foundry_cli::{
+    opts::CoreBuildArgs,
+    utils::{run_prebuild_hook, LoadConfig},
+};
+use foundry_common::{
+    compile::{ProjectCompiler, SizeReport},
+    fs,
+};
 use foundry_compilers::{
     compilers::{multi::MultiCompilerLanguage, Language},
     utils::source_files_iter,
@@ -17,9 +23,13 @@ use foundry_config::{
     },
     Config,
 };
-use serde::Serialize;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Component, PathBuf},
+};
 use watchexec::config::{InitConfig, RuntimeConfig};
+use yansi::Paint;
 
 foundry_config::merge_impl_figment_convert!(BuildArgs, args);
 
@@ -52,15 +62,21 @@ pub struct BuildArgs {
     pub paths: Option<Vec<PathBuf>>,
 
     /// Print compiled contract names.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "silent")]
     #[serde(skip)]
     pub names: bool,
 
     /// Print compiled contract sizes.
-    #[arg(long)]
+    #[arg(long, conflicts_with = "silent")]
     #[serde(skip)]
     pub sizes: bool,
 
+    /// Print the contract sizes as JSON, with the runtime and init code sizes and their margins
+    /// to the EIP-170 and EIP-3860 limits.
+    #[arg(long, requires = "sizes", conflicts_with_all = ["names", "format_json", "format_json_errors"])]
+    #[serde(skip)]
+    pub json: bool,
+
     #[command(flatten)]
     #[serde(flatten)]
     pub args: CoreBuildArgs,
@@ -74,6 +90,54 @@ pub struct BuildArgs {
     #[arg(long, conflicts_with = "silent")]
     #[serde(skip)]
     pub format_json: bool,
+
+    /// Output only the compilation errors, in solc's standard JSON diagnostic format.
+    ///
+    /// Each error carries the `sourceLocation`, `severity` and `formattedMessage` fields as
+    /// emitted by `solc --standard-json`, so that tools already consuming solc output can parse
+    /// it.
+    #[arg(long, conflicts_with_all = ["silent", "format_json"])]
+    #[serde(skip)]
+    pub format_json_errors: bool,
+
+    /// Compile every source with the solc version resolved from its pragma, ignoring the `solc`
+    /// version pinned in the config.
+    ///
+    /// Sources are compiled in one group per resolved version and a summary of the contracts
+    /// compiled with every version is printed.
+    #[arg(long, conflicts_with_all = ["use_solc", "no_auto_detect"])]
+    #[serde(skip)]
+    pub multi_version: bool,
+
+    /// Write the AST of every compiled source file to `<out>/asts`, for external analyzers.
+    ///
+    /// The ASTs are written to `<out>/asts/<source path>.json`, and `<out>/asts/manifest.json`
+    /// maps every source path to its AST file, source id and compiler version.
+    #[arg(long)]
+    #[serde(skip)]
+    pub ast_json_out: bool,
+
+    /// Do not compile the files under the configured test directory.
+    ///
+    /// Test files imported by the other compiled files are still compiled.
+    #[arg(long)]
+    #[serde(skip)]
+    pub skip_tests: bool,
+
+    /// Do not compile the files under the configured script directory.
+    ///
+    /// Script files imported by the other compiled files are still compiled.
+    #[arg(long)]
+    #[serde(skip)]
+    pub skip_scripts: bool,
+
+    /// Fail if two contracts of the project share a name across different source files, instead
+    /// of warning about it.
+    ///
+    /// The contracts of the libraries are ignored.
+    #[arg(long)]
+    #[serde(skip)]
+    pub deny_duplicate_names: bool,
 }
 
 impl BuildArgs {
@@ -87,6 +151,18 @@ impl BuildArgs {
             config = self.load_config();
         }
 
+        if self.multi_version {
+            config.solc = None;
+            config.auto_detect_solc = true;
+        }
+
+        if self.ast_json_out {
+            config.ast = true;
+        }
+
+        let format_json = self.format_json || self.format_json_errors;
+        run_prebuild_hook(&config, format_json || self.json || self.args.silent)?;
+
         let project = config.project()?;
 
         // Collect sources to compile if build subdirectories specified.
@@ -99,17 +175,71 @@ impl BuildArgs {
             }
         }
 
+        // Only compile the remaining files and their imports if tests or scripts are skipped.
+        if self.skip_tests || self.skip_scripts {
+            let paths = &project.paths;
+            if self.paths.is_none() {
+                for dir in [&paths.sources, &paths.tests, &paths.scripts] {
+                    files.extend(source_files_iter(dir, MultiCompilerLanguage::FILE_EXTENSIONS));
+                }
+            }
+            files.retain(|file| {
+                let file = project.root().join(file);
+                !(self.skip_tests && file.starts_with(&paths.tests)) &&
+                    !(self.skip_scripts && file.starts_with(&paths.scripts))
+            });
+            if files.is_empty() {
+                eyre::bail!("No files left to compile after skipping the tests and scripts");
+            }
+        }
+
         let compiler = ProjectCompiler::new()
             .files(files)
             .print_names(self.names)
-            .print_sizes(self.sizes)
-            .quiet(self.format_json)
-            .bail(!self.format_json);
+            .print_sizes(self.sizes && !self.json)
+            .quiet(format_json || self.json || self.args.silent)
+            .bail(!format_json);
 
         let output = compiler.compile(&project)?;
 
+        let duplicates = duplicate_contract_names(&project, &output);
+        if !duplicates.is_empty() {
+            let mut message = String::new();
+            for (name, sources) in &duplicates {
+                let sources = sources.iter().map(|s| s.display().to_string()).collect::<Vec<_>>();
+                message.push_str(&format!("\n  {name}: {}", sources.join(", ")));
+            }
+            if self.deny_duplicate_names {
+                eyre::bail!("Contracts with duplicate names found:{message}");
+            } else if !(format_json || self.json || self.args.silent) {
+                eprintln!("{} Contracts with duplicate names found:{message}", "Warning:".yellow());
+            }
+        }
+
+        if !config.defines.is_empty() {
+            record_defines(&project, &output, &config.defines)?;
+        }
+
+        if self.ast_json_out {
+            write_asts(&project, &output)?;
+        }
+
+        if self.json {
+            let size_report = SizeReport::new(&output);
+            println!("{}", serde_json::to_string_pretty(&size_report.to_json())?);
+            // exit with error if any contract exceeds the size limit, like the table output
+            if size_report.exceeds_size_limit() {
+                std::process::exit(1);
+            }
+        }
+
         if self.format_json {
             println!("{}", serde_json::to_string_pretty(&output.output())?);
+        } else if self.format_json_errors {
+            let errors = serde_json::json!({ "errors": output.output().errors });
+            println!("{}", serde_json::to_string_pretty(&errors)?);
+        } else if self.multi_version && !self.args.silent {
+            print_version_summary(&output);
         }
 
         Ok(output)
@@ -140,6 +270,105 @@ impl BuildArgs {
     }
 }
 
+/// Prints the number of contracts and source files compiled with every compiler version.
+fn print_version_summary(output: &ProjectCompileOutput) {
+    let mut versions = BTreeMap::<_, (usize, BTreeSet<_>)>::new();
+    for (id, _) in output.artifact_ids() {
+        let (contracts, sources) = versions.entry(id.version).or_default();
+        *contracts += 1;
+        sources.insert(id.source);
+    }
+    for (version, (contracts, sources)) in versions {
+        println!(
+            "Compiler {version}: {contracts} contract(s) from {} source file(s)",
+            sources.len()
+        );
+    }
+}
+
+/// Returns the source files of the contract names of the project that are defined in more than
+/// one source file, ignoring the contracts of the libraries.
+fn duplicate_contract_names(
+    project: &Project,
+    output: &ProjectCompileOutput,
+) -> BTreeMap<String, BTreeSet<PathBuf>> {
+    let mut sources = BTreeMap::<_, BTreeSet<_>>::new();
+    for (id, _) in output.artifact_ids() {
+        if project.paths.libraries.iter().any(|lib| id.source.starts_with(lib)) {
+            continue;
+        }
+        let source = id.source.strip_prefix(project.root()).unwrap_or(&id.source).to_path_buf();
+        sources.entry(id.name).or_default().insert(source);
+    }
+    sources.retain(|_, sources| sources.len() > 1);
+    sources
+}
+
+/// Records the defines the project was built with in the freshly written build info files.
+fn record_defines(
+    project: &Project,
+    output: &ProjectCompileOutput,
+    defines: &BTreeMap<String, String>,
+) -> Result<()> {
+    for build_info in &output.output().build_infos {
+        let path = project.paths.build_infos.join(format!("{}.json", build_info.id));
+        if !path.exists() {
+            continue;
+        }
+        let mut content: serde_json::Value = fs::read_json_file(&path)?;
+        if let Some(content) = content.as_object_mut() {
+            content.insert("defines".to_string(), serde_json::to_value(defines)?);
+        }
+        fs::write_json_file(&path, &content)?;
+    }
+    Ok(())
+}
+
+/// An entry of the manifest of the AST files written with `--ast-json-out`.
+#[derive(Debug, Serialize, Deserialize)]
+struct AstManifestEntry {
+    /// The path of the AST file, relative to the manifest.
+    ast: PathBuf,
+    /// The source id assigned by the compiler.
+    id: u32,
+    /// The version of the compiler.
+    version: String,
+}
+
+/// Writes the AST of every freshly compiled source file to `<out>/asts` and updates the manifest.
+///
+/// Sources served from the cache keep their previously written AST, entries of sources that no
+/// longer exist are removed from the manifest.
+fn write_asts(project: &Project, output: &ProjectCompileOutput) -> Result<()> {
+    let dir = project.artifacts_path().join("asts");
+    let manifest_path = dir.join("manifest.json");
+    let mut manifest: BTreeMap<PathBuf, AstManifestEntry> =
+        if manifest_path.exists() { fs::read_json_file(&manifest_path)? } else { BTreeMap::new() };
+    manifest.retain(|source, _| project.root().join(source).exists());
+
+    for (path, source_file, version) in output.output().sources.sources_with_version() {
+        let Some(ast) = &source_file.ast else { continue };
+        let source: PathBuf = path
+            .strip_prefix(project.root())
+            .unwrap_or(path)
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .collect();
+        let ast_file = PathBuf::from(format!("{}.json", source.display()));
+        let ast_path = dir.join(&ast_file);
+        fs::create_dir_all(ast_path.parent().unwrap())?;
+        fs::write_json_file(&ast_path, ast)?;
+        manifest.insert(
+            source,
+            AstManifestEntry { ast: ast_file, id: source_file.id, version: version.to_string() },
+        );
+    }
+
+    fs::create_dir_all(&dir)?;
+    fs::write_json_file(&manifest_path, &manifest)?;
+    Ok(())
+}
+
 // Make this args a `figment::Provider` so that it can be merged into the `Config`
 impl Provider for BuildArgs {
     fn metadata(&self) -> Metadata {
@@ -184,11 +413,30 @@ mod tests {
         assert_eq!(args.args.skip, Some(vec![SkipBuildFilter::Tests, SkipBuildFilter::Scripts]));
     }
 
+    #[test]
+    fn can_parse_skip_tests() {
+        let args: BuildArgs = BuildArgs::parse_from(["foundry-cli", "--skip-tests"]);
+        assert!(args.skip_tests);
+        assert!(!args.skip_scripts);
+
+        let args: BuildArgs =
+            BuildArgs::parse_from(["foundry-cli", "--skip-tests", "--skip-scripts"]);
+        assert!(args.skip_tests && args.skip_scripts);
+    }
+
     #[test]
     fn check_conflicts() {
         let args: std::result::Result<BuildArgs, clap::Error> =
             BuildArgs::try_parse_from(["foundry-cli", "--format-json", "--silent"]);
         assert!(args.is_err());
         assert!(args.unwrap_err().kind() == clap::error::ErrorKind::ArgumentConflict);
+
+        let args: std::result::Result<BuildArgs, clap::Error> =
+            BuildArgs::try_parse_from(["foundry-cli", "--format-json-errors", "--format-json"]);
+        assert!(args.unwrap_err().kind() == clap::error::ErrorKind::ArgumentConflict);
+
+        let args: std::result::Result<BuildArgs, clap::Error> =
+            BuildArgs::try_parse_from(["foundry-cli", "--sizes", "--silent"]);
+        assert!(args.unwrap_err().kind() == clap::error::ErrorKind::ArgumentConflict);
     }
 }
@@ -4,6 +4,7 @@ use clap::{
     Arg, Command, Parser, Subcommand,
 };
 use eyre::Result;
+use foundry_cli::utils::load_config;
 use foundry_config::{cache, Chain, Config, NamedChain};
 use std::{ffi::OsStr, str::FromStr};
 use strum::VariantNames;
@@ -51,20 +52,37 @@ pub struct CleanArgs {
     /// Whether to clean the Etherscan cache.
     #[arg(long, group = "etherscan-blocks")]
     etherscan: bool,
+
+    /// Only clean the cached RPC data of the forks, keeping the Etherscan cache.
+    #[arg(long, conflicts_with = "etherscan")]
+    rpc_only: bool,
+
+    /// Only clean the compiler cache and the artifacts of the current project, keeping the
+    /// cached fork data.
+    #[arg(long, conflicts_with_all = ["chains", "blocks", "etherscan", "rpc_only"])]
+    compiler_only: bool,
 }
 
 impl CleanArgs {
     pub fn run(self) -> Result<()> {
-        let Self { chains, blocks, etherscan } = self;
+        let Self { chains, blocks, etherscan, rpc_only, compiler_only } = self;
+
+        if compiler_only {
+            let project = load_config().project()?;
+            project.cleanup()?;
+            return Ok(())
+        }
 
         for chain_or_all in chains {
             match chain_or_all {
                 ChainOrAll::NamedChain(chain) => {
-                    clean_chain_cache(chain, blocks.to_vec(), etherscan)?
+                    clean_chain_cache(chain, blocks.to_vec(), etherscan, rpc_only)?
                 }
                 ChainOrAll::All => {
                     if etherscan {
                         Config::clean_foundry_etherscan_cache()?;
+                    } else if rpc_only {
+                        Config::clean_foundry_rpc_cache()?;
                     } else {
                         Config::clean_foundry_cache()?
                     }
@@ -87,11 +105,15 @@ pub struct LsArgs {
         value_parser = ChainOrAllValueParser::default(),
     )]
     chains: Vec<ChainOrAll>,
+
+    /// Print the cached data as JSON, with the size and modification time of every block.
+    #[arg(long)]
+    json: bool,
 }
 
 impl LsArgs {
     pub fn run(self) -> Result<()> {
-        let Self { chains } = self;
+        let Self { chains, json } = self;
         let mut cache = Cache::default();
         for chain_or_all in chains {
             match chain_or_all {
@@ -101,7 +123,24 @@ impl LsArgs {
                 ChainOrAll::All => cache = Config::list_foundry_cache()?,
             }
         }
-        print!("{cache}");
+        if json {
+            let chains = cache
+                .chains
+                .iter()
+                .map(|chain| {
+                    serde_json::json!({
+                        "name": chain.name,
+                        "blocks": chain.blocks,
+                        "block_explorer": chain.block_explorer,
+                        "size": chain.size(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            let out = serde_json::json!({ "chains": chains, "size": cache.size() });
+            println!("{}", serde_json::to_string_pretty(&out)?);
+        } else {
+            print!("{cache}");
+        }
         Ok(())
     }
 }
@@ -126,10 +165,17 @@ impl FromStr for ChainOrAll {
     }
 }
 
-fn clean_chain_cache(chain: impl Into<Chain>, blocks: Vec<u64>, etherscan: bool) -> Result<()> {
+fn clean_chain_cache(
+    chain: impl Into<Chain>,
+    blocks: Vec<u64>,
+    etherscan: bool,
+    rpc_only: bool,
+) -> Result<()> {
     let chain = chain.into();
     if blocks.is_empty() {
-        Config::clean_foundry_etherscan_chain_cache(chain)?;
+        if !rpc_only {
+            Config::clean_foundry_etherscan_chain_cache(chain)?;
+        }
         if etherscan {
             return Ok(())
         }
@@ -184,5 +230,25 @@ mod tests {
     fn can_parse_cache_ls() {
         let args: CacheArgs = CacheArgs::parse_from(["cache", "ls"]);
         assert!(matches!(args.sub, CacheSubcommands::Ls(_)));
+
+        let args: CacheArgs = CacheArgs::parse_from(["cache", "ls", "mainnet", "--json"]);
+        assert!(matches!(args.sub, CacheSubcommands::Ls(LsArgs { json: true, .. })));
+    }
+
+    #[test]
+    fn can_parse_cache_clean_scopes() {
+        let args: CacheArgs = CacheArgs::parse_from(["cache", "clean", "mainnet", "--rpc-only"]);
+        assert!(matches!(args.sub, CacheSubcommands::Clean(CleanArgs { rpc_only: true, .. })));
+
+        let args: CacheArgs = CacheArgs::parse_from(["cache", "clean", "--compiler-only"]);
+        assert!(matches!(args.sub, CacheSubcommands::Clean(CleanArgs { compiler_only: true, .. })));
+
+        assert!(CacheArgs::try_parse_from(["cache", "clean", "--rpc-only", "--etherscan"]).is_err());
+        assert!(
+            CacheArgs::try_parse_from(["cache", "clean", "--compiler-only", "--rpc-only"]).is_err()
+        );
+        assert!(
+            CacheArgs::try_parse_from(["cache", "clean", "mainnet", "--compiler-only"]).is_err()
+        );
     }
 }
@@ -0,0 +1,80 @@
+use eyre::{Context, Result};
+use foundry_common::fs;
+use foundry_compilers::Project;
+use serde_json::Value;
+use std::path::Path;
+
+/// Removes only the artifacts matching the given targets, along with their cache entries.
+///
+/// A target matches either a contract name (e.g. `Counter`) or a source file path relative to the
+/// project root (e.g. `src/Counter.sol`). The cache entry of every matching source file is dropped
+/// so that the file is recompiled on the next build, leaving every other artifact untouched.
+pub fn clean_targets(project: &Project, targets: &[String]) -> Result<()> {
+    let cache_path = project.cache_path();
+    if !cache_path.exists() {
+        eyre::bail!("No cache found at {}, nothing to clean", cache_path.display());
+    }
+
+    let mut cache: Value = fs::read_json_file(cache_path)?;
+    let Some(files) = cache.get_mut("files").and_then(Value::as_object_mut) else {
+        eyre::bail!("Malformed cache file at {}", cache_path.display());
+    };
+
+    let root = project.root();
+    let artifacts_dir = project.artifacts_path();
+    let mut removed_entries = Vec::new();
+    let mut removed_artifacts = 0usize;
+    for (source, entry) in files.iter() {
+        let artifacts = entry.get("artifacts").and_then(Value::as_object);
+        let source_matches = targets.iter().any(|target| is_same_source(root, source, target));
+        let contract_matches = artifacts.map_or(false, |artifacts| {
+            artifacts.keys().any(|name| targets.iter().any(|target| target == name))
+        });
+        if !source_matches && !contract_matches {
+            continue;
+        }
+
+        for path in
+            artifacts.into_iter().flat_map(|artifacts| artifacts.values()).flat_map(|versions| {
+                versions.as_object().into_iter().flat_map(|versions| versions.values())
+            })
+        {
+            // Artifacts are either stored as a plain path or as an object with a `path` field.
+            let Some(path) = path.as_str().or_else(|| path.get("path").and_then(Value::as_str))
+            else {
+                continue;
+            };
+            let path = artifacts_dir.join(path);
+            if path.exists() {
+                std::fs::remove_file(&path)
+                    .wrap_err_with(|| format!("Failed to remove artifact {}", path.display()))?;
+                removed_artifacts += 1;
+            }
+        }
+        removed_entries.push(source.clone());
+    }
+
+    for source in &removed_entries {
+        files.remove(source);
+    }
+    fs::write_json_file(cache_path, &cache)?;
+
+    if removed_entries.is_empty() {
+        println!("No artifacts matched {}", targets.join(", "));
+    } else {
+        println!(
+            "Removed {removed_artifacts} artifact(s) from {} source file(s)",
+            removed_entries.len()
+        );
+    }
+    Ok(())
+}
+
+/// Returns whether the cached `source` path refers to the `target` path.
+fn is_same_source(root: &Path, source: &str, target: &str) -> bool {
+    let source = Path::new(source);
+    let target = Path::new(target);
+    let source = if source.is_absolute() { source.to_path_buf() } else { root.join(source) };
+    let target = if target.is_absolute() { target.to_path_buf() } else { root.join(target) };
+    source == target || dunce::canonicalize(&source).ok() == dunce::canonicalize(&target).ok()
+}
@@ -19,6 +19,7 @@ use foundry_compilers::{
     ProjectCompileOutput, ProjectPathsConfig,
 };
 use foundry_config::{Chain, Config};
+use semver::Version;
 use std::{
     fs::read_dir,
     path::{Path, PathBuf},
@@ -47,6 +48,8 @@ pub struct CloneMetadata {
     pub constructor_arguments: Bytes,
     /// The storage layout of the contract on chain.
     pub storage_layout: StorageLayout,
+    /// The version of solc the contract was compiled with.
+    pub compiler_version: Version,
 }
 
 /// CLI arguments for `forge clone`.
@@ -205,6 +208,7 @@ impl CloneArgs {
             deployer: creation_tx.contract_creator,
             constructor_arguments: meta.constructor_arguments.clone(),
             storage_layout,
+            compiler_version: compiler_version(meta)?,
         };
         let metadata_content = serde_json::to_string(&clone_meta)?;
         let metadata_file = root.join(".clone.meta");
@@ -337,9 +341,8 @@ fn update_config_by_metadata(
 
     // disable auto detect solc and set the solc version
     doc[Config::PROFILE_SECTION][profile]["auto_detect_solc"] = toml_edit::value(false);
-    let version = meta.compiler_version()?;
     doc[Config::PROFILE_SECTION][profile]["solc_version"] =
-        toml_edit::value(format!("{}.{}.{}", version.major, version.minor, version.patch));
+        toml_edit::value(compiler_version(meta)?.to_string());
 
     // get optimizer settings
     // we ignore `model_checker`, `debug`, and `output_selection` for now,
@@ -410,6 +413,12 @@ fn update_config_by_metadata(
     Ok(())
 }
 
+/// Returns the solc version of the metadata, without its commit.
+fn compiler_version(meta: &Metadata) -> Result<Version> {
+    let version = meta.compiler_version()?;
+    Ok(Version::new(version.major, version.minor, version.patch))
+}
+
 /// Dump the contract sources to the root directory.
 /// The sources are dumped to the `src` directory.
 /// IO errors may be returned.
@@ -1,9 +1,10 @@
 use super::build::BuildArgs;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::Result;
 use foundry_cli::utils::LoadConfig;
 use foundry_common::{evm::EvmArgs, term::cli_warn};
-use foundry_config::fix::fix_tomls;
+use foundry_config::{fix::fix_tomls, get_profile_keys, Config};
+use serde_json::{json, Value};
 
 foundry_config::impl_figment_convert!(ConfigArgs, opts, evm_opts);
 
@@ -22,12 +23,34 @@ pub struct ConfigArgs {
     #[arg(long)]
     fix: bool,
 
+    /// List the profiles defined in the project and global config files, with the keys they set.
+    ///
+    /// The selected profile, set with `FOUNDRY_PROFILE`, is marked with `*`.
+    #[arg(long, conflicts_with_all = ["basic", "fix"])]
+    list_profiles: bool,
+
     // support nested build arguments
     #[command(flatten)]
     opts: BuildArgs,
 
     #[command(flatten)]
     evm_opts: EvmArgs,
+
+    #[command(subcommand)]
+    sub: Option<ConfigSubcommand>,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+enum ConfigSubcommand {
+    /// Print a single resolved config value, exiting with code 1 if the key does not exist.
+    ///
+    /// Strings are printed as is, other values as JSON.
+    Get {
+        /// The key of the value, nested keys and array indices are separated by dots, e.g.
+        /// `optimizer_runs` or `rpc_endpoints.mainnet`.
+        #[arg(value_name = "KEY")]
+        key: String,
+    },
 }
 
 impl ConfigArgs {
@@ -44,6 +67,22 @@ impl ConfigArgs {
             // we explicitly normalize the version, so mimic the behavior when invoking solc
             .normalized_evm_version();
 
+        if self.list_profiles {
+            return list_profiles(&config, self.json)
+        }
+
+        if let Some(ConfigSubcommand::Get { key }) = &self.sub {
+            let config = serde_json::to_value(&config)?;
+            let Some(value) = get_value(&config, key) else {
+                eyre::bail!("config key `{key}` does not exist");
+            };
+            match value {
+                Value::String(value) => println!("{value}"),
+                value => println!("{}", serde_json::to_string_pretty(value)?),
+            }
+            return Ok(())
+        }
+
         let s = if self.basic {
             let config = config.into_basic();
             if self.json {
@@ -61,3 +100,85 @@ impl ConfigArgs {
         Ok(())
     }
 }
+
+/// Returns the value of the config at the dotted `key`.
+fn get_value<'a>(config: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(config, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(values) => values.get(key.parse::<usize>().ok()?),
+        _ => None,
+    })
+}
+
+/// Prints the profiles of the project and global config files, the keys set by a profile in both
+/// files are merged.
+fn list_profiles(config: &Config, json: bool) -> Result<()> {
+    let mut profiles: Vec<(String, Vec<String>)> =
+        vec![(Config::DEFAULT_PROFILE.to_string(), vec![])];
+    let global = Config::foundry_dir_toml();
+    for toml in [Some(config.get_config_path()), global].into_iter().flatten() {
+        for (name, keys) in get_profile_keys(toml)? {
+            let entry = match profiles.iter().position(|(profile, _)| *profile == name) {
+                Some(idx) => &mut profiles[idx].1,
+                None => {
+                    profiles.push((name, vec![]));
+                    &mut profiles.last_mut().unwrap().1
+                }
+            };
+            for key in keys {
+                if !entry.contains(&key) {
+                    entry.push(key);
+                }
+            }
+        }
+    }
+
+    let selected = config.profile.as_str().as_str();
+    if json {
+        let profiles = profiles
+            .iter()
+            .map(|(name, keys)| json!({ "name": name, "selected": name == selected, "keys": keys }))
+            .collect::<Vec<_>>();
+        println!("{}", serde_json::to_string_pretty(&profiles)?);
+        return Ok(())
+    }
+
+    for (name, keys) in profiles {
+        let marker = if name == selected { "*" } else { " " };
+        if keys.is_empty() {
+            println!("{marker} {name}");
+        } else {
+            println!("{marker} {name}: {}", keys.join(", "));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_config_get() {
+        let args = ConfigArgs::parse_from(["foundry-cli", "get", "optimizer_runs"]);
+        assert!(matches!(args.sub, Some(ConfigSubcommand::Get { key }) if key == "optimizer_runs"));
+    }
+
+    #[test]
+    fn can_get_nested_values() {
+        let config = json!({
+            "optimizer_runs": 200,
+            "rpc_endpoints": { "mainnet": "https://eth.example" },
+            "libs": ["lib", "node_modules"],
+        });
+        assert_eq!(get_value(&config, "optimizer_runs"), Some(&json!(200)));
+        assert_eq!(
+            get_value(&config, "rpc_endpoints.mainnet"),
+            Some(&json!("https://eth.example"))
+        );
+        assert_eq!(get_value(&config, "libs.1"), Some(&json!("node_modules")));
+        assert_eq!(get_value(&config, "libs.2"), None);
+        assert_eq!(get_value(&config, "optimizer_runs.value"), None);
+        assert_eq!(get_value(&config, "missing"), None);
+    }
+}
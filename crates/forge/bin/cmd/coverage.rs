@@ -1,13 +1,16 @@
-use super::{install, test::TestArgs};
+use super::{
+    install,
+    test::{ProjectPathsAwareFilter, TestArgs},
+};
 use alloy_primitives::{Address, Bytes, U256};
-use clap::{Parser, ValueEnum, ValueHint};
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
 use eyre::{Context, Result};
 use forge::{
     coverage::{
         analysis::{SourceAnalysis, SourceAnalyzer, SourceFile, SourceFiles},
         anchors::find_anchors,
-        BytecodeReporter, ContractId, CoverageReport, CoverageReporter, DebugReporter, ItemAnchor,
-        LcovReporter, SummaryReporter,
+        BytecodeReporter, ContractId, CoverageItemKind, CoverageReport, CoverageReporter,
+        CoverageSummary, DebugReporter, HitMap, ItemAnchor, LcovReporter, SummaryReporter,
     },
     opts::EvmOpts,
     utils::IcPcMap,
@@ -17,7 +20,7 @@ use foundry_cli::{
     p_println,
     utils::{LoadConfig, STATIC_FUZZ_SEED},
 };
-use foundry_common::{compile::ProjectCompiler, fs};
+use foundry_common::{compile::ProjectCompiler, fs, ContractsByArtifact};
 use foundry_compilers::{
     artifacts::{sourcemap::SourceMap, CompactBytecode, CompactDeployedBytecode},
     Artifact, ArtifactId, Project, ProjectCompileOutput,
@@ -26,8 +29,9 @@ use foundry_config::{Config, SolcReq};
 use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -39,10 +43,13 @@ foundry_config::impl_figment_convert!(CoverageArgs, test);
 /// CLI arguments for `forge coverage`.
 #[derive(Clone, Debug, Parser)]
 pub struct CoverageArgs {
+    #[command(subcommand)]
+    cmd: Option<CoverageSubcommand>,
+
     /// The report type to use for coverage.
     ///
     /// This flag can be used multiple times.
-    #[arg(long, value_enum, default_value = "summary")]
+    #[arg(long, value_enum, default_value = "summary", global = true)]
     report: Vec<CoverageReportKind>,
 
     /// Enable viaIR with minimum optimization
@@ -59,7 +66,8 @@ pub struct CoverageArgs {
         long,
         short,
         value_hint = ValueHint::FilePath,
-        value_name = "PATH"
+        value_name = "PATH",
+        global = true
     )]
     report_file: Option<PathBuf>,
 
@@ -67,11 +75,50 @@ pub struct CoverageArgs {
     #[arg(long)]
     include_libs: bool,
 
+    /// Write the tests executing every source line to the given file, as JSON.
+    ///
+    /// The file maps the source files to their lines, and the lines to the tests that hit them.
+    /// Defaults to `coverage-per-test.json` in the root of the project.
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        num_args(0..=1),
+        default_missing_value = "coverage-per-test.json"
+    )]
+    per_test: Option<PathBuf>,
+
     #[command(flatten)]
     test: TestArgs,
 }
 
+#[derive(Clone, Debug, Subcommand)]
+pub enum CoverageSubcommand {
+    /// Merge the raw coverage data of multiple runs and output the final report.
+    ///
+    /// The raw coverage data is written with `--report raw`, e.g. by every shard of a test suite.
+    Merge {
+        /// The raw coverage files to merge.
+        #[arg(required = true, value_hint = ValueHint::FilePath, value_name = "FILES")]
+        files: Vec<PathBuf>,
+    },
+}
+
 impl CoverageArgs {
+    /// Collects the coverage of the tests selected by the arguments of `forge test`, with the
+    /// summary report.
+    pub fn from_test_args(test: TestArgs) -> Self {
+        Self {
+            cmd: None,
+            report: vec![CoverageReportKind::Summary],
+            ir_minimum: false,
+            report_file: None,
+            include_libs: false,
+            per_test: None,
+            test,
+        }
+    }
+
     pub async fn run(self) -> Result<()> {
         let (mut config, evm_opts) = self.load_config_and_evm_opts_emit_warnings()?;
 
@@ -93,6 +140,18 @@ impl CoverageArgs {
         p_println!(!self.test.build_args().silent => "Analysing contracts...");
         let report = self.prepare(&project, &output)?;
 
+        if let Some(CoverageSubcommand::Merge { files }) = &self.cmd {
+            if self.per_test.is_some() {
+                eyre::bail!("`--per-test` is not supported when merging raw coverage data");
+            }
+            let mut hits = CoverageHits::default();
+            for file in files {
+                let other: CoverageHits = fs::read_json_file(file)?;
+                hits.merge(other);
+            }
+            return self.report(project.paths.root, &config, report, hits)
+        }
+
         p_println!(!self.test.build_args().silent => "Running tests...");
         self.collect(project, &output, report, Arc::new(config), evm_opts).await
     }
@@ -142,6 +201,12 @@ impl CoverageArgs {
         Ok((project, output))
     }
 
+    /// Returns `true` if the source file matches one of the `--ignore` globs.
+    fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        let path = path.strip_prefix(root).unwrap_or(path);
+        self.test.ignore.iter().any(|glob| glob.is_match(path))
+    }
+
     /// Builds the coverage report.
     #[instrument(name = "prepare", skip_all)]
     fn prepare(&self, project: &Project, output: &ProjectCompileOutput) -> Result<CoverageReport> {
@@ -153,10 +218,13 @@ impl CoverageArgs {
         for (path, source_file, version) in output.output().sources.sources_with_version() {
             report.add_source(version.clone(), source_file.id as usize, path.clone());
 
-            // Filter out dependencies
+            // Filter out dependencies and ignored sources
             if !self.include_libs && project_paths.has_library_ancestor(path) {
                 continue;
             }
+            if self.is_ignored(&project_paths.root, path) {
+                continue;
+            }
 
             if let Some(ast) = &source_file.ast {
                 let file = project_paths.root.join(path);
@@ -256,47 +324,67 @@ impl CoverageArgs {
 
         outcome.ensure_ok()?;
 
-        // Add hit data to the coverage report
-        let data = outcome.results.iter().flat_map(|(_, suite)| {
-            let mut hits = Vec::new();
-            for result in suite.test_results.values() {
-                let Some(hit_maps) = result.coverage.as_ref() else { continue };
-                for map in hit_maps.0.values() {
-                    if let Some((id, _)) = known_contracts.find_by_deployed_code(&map.bytecode) {
-                        hits.push((id, map, true));
-                    } else if let Some((id, _)) =
-                        known_contracts.find_by_creation_code(&map.bytecode)
-                    {
-                        hits.push((id, map, false));
+        // Collect the hit data of every test
+        let mut hits = CoverageHits::default();
+        for result in outcome.results.values().flat_map(|suite| suite.test_results.values()) {
+            let Some(hit_maps) = result.coverage.as_ref() else { continue };
+            for map in hit_maps.0.values() {
+                if let Some((id, deployed)) = find_hit_artifact(&known_contracts, map) {
+                    hits.add(id, map, deployed);
+                }
+            }
+        }
+
+        if let Some(per_test) = &self.per_test {
+            let mut lines = LineTests::new();
+            for (suite_name, suite) in &outcome.results {
+                for (test_name, result) in &suite.test_results {
+                    let Some(hit_maps) = result.coverage.as_ref() else { continue };
+                    let test = format!("{suite_name}:{test_name}");
+                    for map in hit_maps.0.values() {
+                        if let Some((id, deployed)) = find_hit_artifact(&known_contracts, map) {
+                            add_line_tests(&mut lines, &report, id, map, deployed, &test);
+                        }
                     }
                 }
             }
-            hits
-        });
 
-        for (artifact_id, map, is_deployed_code) in data {
+            lines.retain(|path, _| is_reported(&filter, path) && !self.is_ignored(&root, path));
+            fs::write_json_file(&root.join(per_test), &lines)?;
+        }
+
+        self.report(root, &config, report, hits)
+    }
+
+    /// Adds the hit data to the coverage report and outputs it.
+    fn report(
+        self,
+        root: PathBuf,
+        config: &Config,
+        mut report: CoverageReport,
+        hits: CoverageHits,
+    ) -> Result<()> {
+        // Add hit data to the coverage report
+        for contract in &hits.contracts {
             if let Some(source_id) =
-                report.get_source_id(artifact_id.version.clone(), artifact_id.source.clone())
+                report.get_source_id(contract.version.clone(), contract.source.clone())
             {
                 report.add_hit_map(
                     &ContractId {
-                        version: artifact_id.version.clone(),
+                        version: contract.version.clone(),
                         source_id,
-                        contract_name: artifact_id.name.as_str().into(),
+                        contract_name: contract.name.as_str().into(),
                     },
-                    map,
-                    is_deployed_code,
+                    &HitMap { bytecode: contract.bytecode.clone(), hits: contract.hits.clone() },
+                    contract.deployed,
                 )?;
             }
         }
 
         // Filter out ignored sources from the report
-        let file_pattern = filter.args().coverage_pattern_inverse.as_ref();
-        let file_root = &filter.paths().root;
+        let filter = self.test.filter(config);
         report.filter_out_ignored_sources(|path: &Path| {
-            file_pattern.map_or(true, |re| {
-                !re.is_match(&path.strip_prefix(file_root).unwrap_or(path).to_string_lossy())
-            })
+            is_reported(&filter, path) && !self.is_ignored(&root, path)
         });
 
         // Output final report
@@ -304,13 +392,8 @@ impl CoverageArgs {
             match report_kind {
                 CoverageReportKind::Summary => SummaryReporter::default().report(&report),
                 CoverageReportKind::Lcov => {
-                    if let Some(report_file) = self.report_file {
-                        return LcovReporter::new(&mut fs::create_file(root.join(report_file))?)
-                            .report(&report)
-                    } else {
-                        return LcovReporter::new(&mut fs::create_file(root.join("lcov.info"))?)
-                            .report(&report)
-                    }
+                    let report_file = self.report_file.as_deref().unwrap_or(Path::new("lcov.info"));
+                    LcovReporter::new(&mut fs::create_file(root.join(report_file))?).report(&report)
                 }
                 CoverageReportKind::Bytecode => {
                     let destdir = root.join("bytecode-coverage");
@@ -319,12 +402,166 @@ impl CoverageArgs {
                     Ok(())
                 }
                 CoverageReportKind::Debug => DebugReporter.report(&report),
+                CoverageReportKind::Raw => {
+                    let report_file =
+                        self.report_file.as_deref().unwrap_or(Path::new("coverage.json"));
+                    fs::write_json_file(&root.join(report_file), &hits)?;
+                    Ok(())
+                }
             }?;
         }
+
+        if let Some(min_coverage) = self.test.min_coverage {
+            check_min_coverage(&report, min_coverage)?;
+        }
         Ok(())
     }
 }
 
+/// Fails if the line coverage of all the sources of the report is below the given percentage.
+fn check_min_coverage(report: &CoverageReport, min_coverage: f64) -> Result<()> {
+    let mut total = CoverageSummary::default();
+    for (_, summary) in report.summary_by_file() {
+        total += &summary;
+    }
+    let coverage = if total.line_count == 0 {
+        100.
+    } else {
+        total.line_hits as f64 / total.line_count as f64 * 100.
+    };
+    if coverage < min_coverage {
+        eyre::bail!(
+            "line coverage of {coverage:.2}% is below the minimum of {min_coverage:.2}% \
+             ({:.2}% short)",
+            min_coverage - coverage
+        );
+    }
+    Ok(())
+}
+
+/// The raw hit data of a coverage run.
+///
+/// This is written with `--report raw` and can be merged across runs with `forge coverage merge`,
+/// adding up the hit counts of every instruction.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CoverageHits {
+    pub contracts: Vec<ContractHits>,
+}
+
+/// The hit counts for the creation or deployed code of a contract.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContractHits {
+    /// The compiler version the contract was compiled with.
+    pub version: Version,
+    /// The path of the source file the contract is defined in.
+    pub source: PathBuf,
+    /// The name of the contract.
+    pub name: String,
+    /// Whether the hits are for the deployed code, or the creation code.
+    pub deployed: bool,
+    pub bytecode: Bytes,
+    /// The hit counts, keyed by program counter.
+    pub hits: BTreeMap<usize, u64>,
+}
+
+impl CoverageHits {
+    fn add(&mut self, id: &ArtifactId, map: &HitMap, deployed: bool) {
+        self.add_hits(ContractHits {
+            version: id.version.clone(),
+            source: id.source.clone(),
+            name: id.name.clone(),
+            deployed,
+            bytecode: map.bytecode.clone(),
+            hits: map.hits.clone(),
+        });
+    }
+
+    fn add_hits(&mut self, hits: ContractHits) {
+        let existing = self.contracts.iter_mut().find(|contract| {
+            contract.version == hits.version &&
+                contract.source == hits.source &&
+                contract.name == hits.name &&
+                contract.deployed == hits.deployed
+        });
+        match existing {
+            Some(contract) => {
+                for (pc, count) in hits.hits {
+                    *contract.hits.entry(pc).or_default() += count;
+                }
+            }
+            None => self.contracts.push(hits),
+        }
+    }
+
+    /// Merges the hits of another run into this one.
+    pub fn merge(&mut self, other: Self) {
+        for hits in other.contracts {
+            self.add_hits(hits);
+        }
+    }
+}
+
+/// The tests hitting every line of the source files, keyed by source file and line number.
+type LineTests = BTreeMap<PathBuf, BTreeMap<usize, BTreeSet<String>>>;
+
+/// Returns the artifact of the contract the hit map was collected for, and whether the hits are
+/// for its deployed code.
+fn find_hit_artifact<'a>(
+    known_contracts: &'a ContractsByArtifact,
+    map: &HitMap,
+) -> Option<(&'a ArtifactId, bool)> {
+    if let Some((id, _)) = known_contracts.find_by_deployed_code(&map.bytecode) {
+        Some((id, true))
+    } else {
+        known_contracts.find_by_creation_code(&map.bytecode).map(|(id, _)| (id, false))
+    }
+}
+
+/// Records `test` as hitting the lines of the contract hit in the hit map.
+fn add_line_tests(
+    lines: &mut LineTests,
+    report: &CoverageReport,
+    id: &ArtifactId,
+    map: &HitMap,
+    deployed: bool,
+    test: &str,
+) {
+    let Some(source_id) = report.get_source_id(id.version.clone(), id.source.clone()) else {
+        return
+    };
+    let contract_id = ContractId {
+        version: id.version.clone(),
+        source_id,
+        contract_name: id.name.as_str().into(),
+    };
+    let Some(items) = report.items.get(&id.version) else { return };
+
+    for item_id in report.hit_items(&contract_id, map, deployed) {
+        let item = &items[item_id];
+        if !matches!(item.kind, CoverageItemKind::Line) {
+            continue
+        }
+        let Some(path) = report.source_paths.get(&(id.version.clone(), item.loc.source_id)) else {
+            continue
+        };
+        lines
+            .entry(path.clone())
+            .or_default()
+            .entry(item.loc.line)
+            .or_default()
+            .insert(test.to_string());
+    }
+}
+
+/// Returns whether the source file at `path` is not excluded from the coverage report with
+/// `--no-match-coverage`.
+fn is_reported(filter: &ProjectPathsAwareFilter, path: &Path) -> bool {
+    let file_root = &filter.paths().root;
+    filter.args().coverage_pattern_inverse.as_ref().map_or(true, |re| {
+        !re.is_match(&path.strip_prefix(file_root).unwrap_or(path).to_string_lossy())
+    })
+}
+
 // TODO: HTML
 #[derive(Clone, Debug, ValueEnum)]
 pub enum CoverageReportKind {
@@ -332,6 +569,8 @@ pub enum CoverageReportKind {
     Lcov,
     Debug,
     Bytecode,
+    /// The raw hit data, which can be merged with `forge coverage merge`.
+    Raw,
 }
 
 /// Helper function that will link references in unlinked bytecode to the 0 address.
@@ -2,13 +2,14 @@ use alloy_chains::Chain;
 use alloy_dyn_abi::{DynSolValue, JsonAbiExt, Specifier};
 use alloy_json_abi::{Constructor, JsonAbi};
 use alloy_network::{AnyNetwork, EthereumWallet, TransactionBuilder};
-use alloy_primitives::{hex, Address, Bytes};
+use alloy_primitives::{hex, utils::format_units, Address, Bytes, B256, U256};
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types::{AnyTransactionReceipt, TransactionRequest};
 use alloy_serde::WithOtherFields;
 use alloy_signer::Signer;
 use alloy_transport::{Transport, TransportError};
-use clap::{Parser, ValueHint};
+use clap::{builder::RangedU64ValueParser, Parser, ValueHint};
+use dialoguer::Confirm;
 use eyre::{Context, Result};
 use forge_verify::RetryArgs;
 use foundry_cli::{
@@ -18,10 +19,24 @@ use foundry_cli::{
 use foundry_common::{
     compile::{self},
     fmt::parse_tokens,
+    fs,
 };
-use foundry_compilers::{artifacts::BytecodeObject, info::ContractInfo, utils::canonicalize};
+use foundry_compilers::{
+    artifacts::{BytecodeObject, CompactBytecode, Libraries},
+    info::ContractInfo,
+    utils::canonicalize,
+};
+use foundry_evm::constants::DEFAULT_CREATE2_DEPLOYER;
 use serde_json::json;
-use std::{borrow::Borrow, marker::PhantomData, path::PathBuf, sync::Arc};
+use std::{
+    borrow::Borrow,
+    collections::BTreeMap,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 /// CLI arguments for `forge create`.
 #[derive(Clone, Debug, Parser)]
@@ -46,18 +61,68 @@ pub struct CreateArgs {
     )]
     constructor_args_path: Option<PathBuf>,
 
+    /// The path to a JSON file mapping the source files of libraries to their names and
+    /// addresses, e.g. `{"src/Math.sol": {"Math": "0x..."}}`.
+    ///
+    /// The libraries are linked like the ones given with `--libraries <file>:<lib>:<address>`.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    libraries_file: Option<PathBuf>,
+
     /// Print the deployment information as JSON.
     #[arg(long, help_heading = "Display options")]
     json: bool,
 
     /// Verify contract after creation.
+    ///
+    /// Uses the same compiler settings and constructor arguments as the deployment.
     #[arg(long)]
     verify: bool,
 
+    /// Wait for the verification result if `--verify` is provided.
+    #[arg(long, requires = "verify")]
+    watch: bool,
+
     /// Send via `eth_sendTransaction` using the `--from` argument or `$ETH_FROM` as sender
     #[arg(long, requires = "from")]
     unlocked: bool,
 
+    /// Deploy the contract with CREATE2 through the CREATE2 deployer, using the given salt.
+    ///
+    /// The address of the contract only depends on the deployer, the salt and the init code, and
+    /// is printed before sending the transaction.
+    #[arg(long, value_name = "SALT")]
+    salt: Option<B256>,
+
+    /// The address of the CREATE2 deployer to deploy through.
+    ///
+    /// Defaults to the deterministic deployment proxy.
+    #[arg(long, requires = "salt", value_name = "ADDRESS")]
+    create2_deployer: Option<Address>,
+
+    /// Print the estimated gas and cost of the deployment, and ask for confirmation before
+    /// sending it.
+    ///
+    /// The estimate uses the gas limit and fees the deployment is sent with.
+    #[arg(long)]
+    confirm: bool,
+
+    /// Send the deployment without asking for confirmation, only printing the estimate.
+    #[arg(long, requires = "confirm")]
+    yes: bool,
+
+    /// The number of confirmations to wait for before reporting the deployment.
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_name = "CONFIRMATIONS",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    confirmations: usize,
+
+    /// The number of seconds to wait for the confirmations of the deployment before failing.
+    #[arg(long, default_value_t = 120, value_name = "SECONDS")]
+    timeout: u64,
+
     /// Prints the standard json compiler input if `--verify` is provided.
     ///
     /// The standard json compiler input can be used to manually submit contract verification in
@@ -84,6 +149,11 @@ pub struct CreateArgs {
 impl CreateArgs {
     /// Executes the command to create a contract
     pub async fn run(mut self) -> Result<()> {
+        if let Some(path) = &self.libraries_file {
+            let libraries = read_libraries_file(path)?;
+            self.opts.libraries.extend(libraries);
+        }
+
         // Find Project & Compile
         let project = self.opts.project()?;
 
@@ -96,8 +166,10 @@ impl CreateArgs {
         let mut output =
             compile::compile_target(&target_path, &project, self.json || self.opts.silent)?;
 
-        let (abi, bin, _) = remove_contract(&mut output, &target_path, &self.contract.name)?;
+        let (abi, mut bin, _) = remove_contract(&mut output, &target_path, &self.contract.name)?;
 
+        let libraries = Libraries::parse(&self.opts.libraries)?;
+        link_bytecode(&mut bin, &libraries, project.root())?;
         let bin = match bin.object {
             BytecodeObject::Bytecode(_) => bin.object,
             _ => {
@@ -109,7 +181,7 @@ impl CreateArgs {
                     })
                     .collect::<Vec<String>>()
                     .join("\n");
-                eyre::bail!("Dynamic linking not supported in `create` command - deploy the following library contracts first, then provide the address to link at compile time\n{}", link_refs)
+                eyre::bail!("Dynamic linking not supported in `create` command - deploy the following library contracts first, then provide their addresses with `--libraries <file>:<lib>:<address>`\n{}", link_refs)
             }
         };
 
@@ -197,8 +269,10 @@ impl CreateArgs {
         // Check config for Etherscan API Keys to avoid preflight check failing if no
         // ETHERSCAN_API_KEY value set.
         let config = verify.load_config_emit_warnings();
-        verify.etherscan.key =
-            config.get_etherscan_config_with_chain(Some(chain.into()))?.map(|c| c.key);
+        verify.etherscan.key = verify
+            .verifier
+            .api_key(config.get_etherscan_config_with_chain(Some(chain.into()))?.map(|c| c.key));
+        verify.verifier.check_verifier_url().await?;
 
         let context = verify.resolve_context().await?;
 
@@ -230,9 +304,35 @@ impl CreateArgs {
                 } else {
                     e
                 }
-            })?;
+            })?
+            .confirmations(self.confirmations)
+            .timeout(Some(Duration::from_secs(self.timeout)));
         let is_legacy = self.tx.legacy || Chain::from(chain).is_legacy();
 
+        // Route the deployment through the CREATE2 deployer, which expects the salt followed by
+        // the init code as calldata.
+        let create2 = if let Some(salt) = self.salt {
+            let create2_deployer = self.create2_deployer.unwrap_or(DEFAULT_CREATE2_DEPLOYER);
+            if provider.get_code_at(create2_deployer).await?.is_empty() {
+                eyre::bail!("CREATE2 deployer {create2_deployer} is not deployed on this chain");
+            }
+
+            let init_code = deployer.tx.input.input().cloned().unwrap_or_default();
+            let predicted = create2_deployer.create2_from_code(salt, &init_code);
+            if !provider.get_code_at(predicted).await?.is_empty() {
+                eyre::bail!("a contract is already deployed at the predicted address {predicted}");
+            }
+            if !self.json {
+                println!("Predicted address: {predicted}");
+            }
+
+            deployer.tx.set_to(create2_deployer);
+            deployer.tx.set_input(Bytes::from([salt.as_slice(), &init_code[..]].concat()));
+            Some(predicted)
+        } else {
+            None
+        };
+
         deployer.tx.set_from(deployer_address);
         deployer.tx.set_chain_id(chain);
         // `to` field must be set explicitly, cannot be None.
@@ -280,6 +380,24 @@ impl CreateArgs {
             deployer.tx.set_max_priority_fee_per_gas(priority_fee);
         }
 
+        if self.confirm {
+            let gas = deployer.tx.gas.unwrap_or_default();
+            let per_gas = deployer.tx.max_fee_per_gas.or(deployer.tx.gas_price).unwrap_or_default();
+            let estimate = deployment_estimate(gas, per_gas);
+            // Keep the JSON output parseable.
+            if self.json {
+                eprintln!("{estimate}");
+            } else {
+                println!("{estimate}");
+            }
+
+            if !self.yes &&
+                !Confirm::new().with_prompt("Do you wish to deploy?".to_string()).interact()?
+            {
+                eyre::bail!("User canceled the deployment.");
+            }
+        }
+
         // Before we actually deploy the contract we try check if the verify settings are valid
         let mut constructor_args = None;
         if self.verify {
@@ -295,7 +413,11 @@ impl CreateArgs {
         }
 
         // Deploy the actual contract
-        let (deployed_contract, receipt) = deployer.send_with_receipt().await?;
+        let (deployed_contract, receipt) = if let Some(predicted) = create2 {
+            deployer.send_create2_with_receipt(predicted).await?
+        } else {
+            deployer.send_with_receipt().await?
+        };
 
         let address = deployed_contract;
         if self.json {
@@ -331,7 +453,7 @@ impl CreateArgs {
             flatten: false,
             force: false,
             skip_is_verified_check: false,
-            watch: true,
+            watch: self.watch,
             retry: self.retry,
             libraries: self.opts.libraries.clone(),
             root: None,
@@ -342,7 +464,11 @@ impl CreateArgs {
             guess_constructor_args: false,
         };
         println!("Waiting for {} to detect contract deployment...", verify.verifier.verifier);
-        verify.run().await
+        // The deployment is already confirmed at this point, make sure its address isn't lost.
+        verify
+            .run()
+            .await
+            .wrap_err_with(|| format!("contract was deployed to {address}, but failed to verify"))
     }
 
     /// Parses the given constructor arguments into a vector of `DynSolValue`s, by matching them
@@ -367,6 +493,58 @@ impl CreateArgs {
     }
 }
 
+/// Reads a JSON file mapping the source files of libraries to their names and addresses, and
+/// returns the libraries in the `<file>:<lib>:<address>` form of `--libraries`.
+fn read_libraries_file(path: &Path) -> Result<Vec<String>> {
+    let libraries: BTreeMap<String, BTreeMap<String, Address>> = fs::read_json_file(path)
+        .wrap_err_with(|| format!("failed to read libraries file {}", path.display()))?;
+    Ok(libraries
+        .into_iter()
+        .flat_map(|(file, libs)| {
+            libs.into_iter().map(move |(name, address)| format!("{file}:{name}:{address}"))
+        })
+        .collect())
+}
+
+/// Replaces the placeholders of the given libraries in the bytecode with their addresses.
+///
+/// The files of the link references and of the libraries are compared relative to the project
+/// root, as solc may reference them by their absolute path. Placeholders of other libraries are
+/// left as is.
+fn link_bytecode(bytecode: &mut CompactBytecode, libraries: &Libraries, root: &Path) -> Result<()> {
+    let references = bytecode.link_references.keys().cloned().collect::<Vec<_>>();
+    for reference in references {
+        let reference_path = Path::new(&reference);
+        let reference_path = reference_path.strip_prefix(root).unwrap_or(reference_path);
+        for (file, libs) in &libraries.libs {
+            if file.strip_prefix(root).unwrap_or(file) != reference_path {
+                continue
+            }
+            for (name, address) in libs {
+                let address = Address::from_str(address).wrap_err_with(|| {
+                    format!("invalid address of library {name} at {}", file.display())
+                })?;
+                bytecode.link(&reference, name, address);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Formats the estimated gas and cost of a deployment with the given gas limit and price per gas.
+fn deployment_estimate(gas: u128, per_gas: u128) -> String {
+    let format = |amount: u128, decimals: u8| {
+        let amount = format_units(U256::from(amount), decimals)
+            .unwrap_or_else(|_| "[Could not calculate]".to_string());
+        amount.trim_end_matches('0').trim_end_matches('.').to_string()
+    };
+    format!(
+        "Estimated gas: {gas}\nEstimated gas price: {} gwei\nEstimated cost: {} ETH",
+        format(per_gas, 9),
+        format(gas.saturating_mul(per_gas), 18)
+    )
+}
+
 /// `ContractFactory` is a [`DeploymentTxFactory`] object with an
 /// [`Arc`] middleware. This type alias exists to preserve backwards
 /// compatibility with less-abstract Contracts.
@@ -414,6 +592,7 @@ pub struct Deployer<B, P, T> {
     abi: JsonAbi,
     client: B,
     confs: usize,
+    timeout: Option<Duration>,
     _p: PhantomData<P>,
     _t: PhantomData<T>,
 }
@@ -428,6 +607,7 @@ where
             abi: self.abi.clone(),
             client: self.client.clone(),
             confs: self.confs,
+            timeout: self.timeout,
             _p: PhantomData,
             _t: PhantomData,
         }
@@ -440,6 +620,18 @@ where
     P: Provider<T, AnyNetwork>,
     T: Transport + Clone,
 {
+    /// Sets the number of confirmations to wait for after the transaction is mined.
+    pub fn confirmations(mut self, confs: usize) -> Self {
+        self.confs = confs;
+        self
+    }
+
+    /// Sets the maximum time to wait for the confirmations, by default there is none.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
     /// Broadcasts the contract deployment transaction and after waiting for it to
     /// be sufficiently confirmed (default: 1), it returns a tuple with
     /// the [`Contract`](crate::Contract) struct at the deployed contract's address
@@ -453,6 +645,7 @@ where
             .send_transaction(self.tx)
             .await?
             .with_required_confirmations(self.confs as u64)
+            .with_timeout(self.timeout)
             .get_receipt()
             .await?;
 
@@ -461,6 +654,32 @@ where
 
         Ok((address, receipt))
     }
+
+    /// Broadcasts the deployment transaction through a CREATE2 deployer and waits for it to be
+    /// confirmed, like [`send_with_receipt`](Self::send_with_receipt).
+    ///
+    /// The receipt of a call to the deployer does not contain the contract's address, so this
+    /// checks that code was deployed at the `predicted` address instead.
+    pub async fn send_create2_with_receipt(
+        self,
+        predicted: Address,
+    ) -> Result<(Address, AnyTransactionReceipt), ContractDeploymentError> {
+        let client = self.client.clone();
+        let receipt = client
+            .borrow()
+            .send_transaction(self.tx)
+            .await?
+            .with_required_confirmations(self.confs as u64)
+            .with_timeout(self.timeout)
+            .get_receipt()
+            .await?;
+
+        if client.borrow().get_code_at(predicted).await?.is_empty() {
+            return Err(ContractDeploymentError::NotDeployedAtPredictedAddress(predicted))
+        }
+
+        Ok((predicted, receipt))
+    }
 }
 
 /// To deploy a contract to the Ethereum network, a `ContractFactory` can be
@@ -567,6 +786,7 @@ where
             abi: self.abi,
             tx,
             confs: 1,
+            timeout: None,
             _p: PhantomData,
             _t: PhantomData,
         })
@@ -582,6 +802,8 @@ pub enum ContractDeploymentError {
     DetokenizationError(#[from] alloy_dyn_abi::Error),
     #[error("contract was not deployed")]
     ContractNotDeployed,
+    #[error("contract was not deployed at the predicted address {0}")]
+    NotDeployedAtPredictedAddress(Address),
     #[error(transparent)]
     RpcError(#[from] TransportError),
 }
@@ -589,6 +811,7 @@ pub enum ContractDeploymentError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_primitives::keccak256;
 
     #[test]
     fn can_parse_create() {
@@ -603,6 +826,11 @@ mod tests {
         ]);
         assert_eq!(args.retry.retries, 10);
         assert_eq!(args.retry.delay, 30);
+        assert!(!args.watch);
+
+        let args =
+            CreateArgs::try_parse_from(["foundry-cli", "src/Domains.sol:Domains", "--watch"]);
+        assert!(args.is_err());
     }
     #[test]
     fn can_parse_chain_id() {
@@ -620,6 +848,105 @@ mod tests {
         assert_eq!(args.chain_id(), Some(9999));
     }
 
+    #[test]
+    fn can_parse_salt() {
+        let args: CreateArgs = CreateArgs::parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--salt",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ]);
+        assert_eq!(args.salt, Some(B256::with_last_byte(1)));
+        assert_eq!(args.create2_deployer, None);
+
+        let args = CreateArgs::try_parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--create2-deployer",
+            "0x4e59b44847b379578588920ca78fbf26c0b4956c",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn can_parse_confirmations() {
+        let args = CreateArgs::parse_from(["foundry-cli", "src/Domains.sol:Domains"]);
+        assert_eq!(args.confirmations, 1);
+        assert_eq!(args.timeout, 120);
+
+        let args = CreateArgs::parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--confirmations",
+            "12",
+            "--timeout",
+            "300",
+        ]);
+        assert_eq!(args.confirmations, 12);
+        assert_eq!(args.timeout, 300);
+
+        let args = CreateArgs::try_parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--confirmations",
+            "0",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn can_parse_confirm() {
+        let args: CreateArgs = CreateArgs::parse_from([
+            "foundry-cli",
+            "src/Domains.sol:Domains",
+            "--confirm",
+            "--yes",
+        ]);
+        assert!(args.confirm && args.yes);
+
+        let args = CreateArgs::try_parse_from(["foundry-cli", "src/Domains.sol:Domains", "--yes"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn can_format_deployment_estimate() {
+        assert_eq!(
+            deployment_estimate(100_000, 1_500_000_000),
+            "Estimated gas: 100000\nEstimated gas price: 1.5 gwei\nEstimated cost: 0.00015 ETH"
+        );
+        assert_eq!(
+            deployment_estimate(21_000, 0),
+            "Estimated gas: 21000\nEstimated gas price: 0 gwei\nEstimated cost: 0 ETH"
+        );
+    }
+
+    #[test]
+    fn can_link_bytecode() {
+        let root = Path::new("/project");
+        let hash = hex::encode(keccak256("/project/src/Math.sol:Math"));
+        let placeholder = format!("__${}$__", &hash[..34]);
+        let mut bytecode: CompactBytecode = serde_json::from_value(json!({
+            "object": format!("73{placeholder}"),
+            "linkReferences": {
+                "/project/src/Math.sol": {"Math": [{"start": 1, "length": 20}]}
+            }
+        }))
+        .unwrap();
+
+        let other = Libraries::parse(&[format!("src/Other.sol:Other:{}", Address::ZERO)]).unwrap();
+        link_bytecode(&mut bytecode, &other, root).unwrap();
+        assert!(bytecode.object.is_unlinked());
+
+        let address = Address::with_last_byte(1);
+        let libraries = Libraries::parse(&[format!("src/Math.sol:Math:{address}")]).unwrap();
+        link_bytecode(&mut bytecode, &libraries, root).unwrap();
+        assert!(bytecode.link_references.is_empty());
+        assert_eq!(
+            bytecode.object.into_bytes().unwrap().to_vec(),
+            [&[0x73], address.as_slice()].concat()
+        );
+    }
+
     #[test]
     fn test_parse_constructor_args() {
         let args: CreateArgs = CreateArgs::parse_from([
@@ -0,0 +1,141 @@
+use clap::{Parser, ValueHint};
+use eyre::Result;
+use foundry_cli::utils::{CommandUtils, Git, LoadConfig};
+use foundry_config::impl_figment_convert_basic;
+use serde::Serialize;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// CLI arguments for `forge deps`.
+#[derive(Clone, Debug, Parser)]
+pub struct DepsArgs {
+    /// List the installed dependencies.
+    #[arg(long, required = true)]
+    list: bool,
+
+    /// Print the dependencies as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// The project's root path.
+    ///
+    /// By default root of the Git repository, if in one,
+    /// or the current working directory.
+    #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    root: Option<PathBuf>,
+}
+impl_figment_convert_basic!(DepsArgs);
+
+impl DepsArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.try_load_config_emit_warnings()?;
+        let deps = dependency_manifest(Git::from_config(&config))?;
+        print_manifest(&deps, self.json)
+    }
+}
+
+/// An installed dependency, as recorded in `.gitmodules` and the submodule state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DependencyManifest {
+    /// The name of the submodule.
+    pub name: String,
+    /// The remote URL the dependency is fetched from.
+    pub url: Option<String>,
+    /// The commit the dependency is checked out at, if initialized.
+    pub commit: Option<String>,
+    /// The path of the dependency, relative to the root of the repository.
+    pub path: PathBuf,
+}
+
+/// Reads the manifest of all the dependencies installed as submodules in the repository
+/// containing `git.root`.
+///
+/// Returns an empty manifest if the repository has no `.gitmodules` file, or no submodules in it.
+pub fn dependency_manifest(git: Git<'_>) -> Result<Vec<DependencyManifest>> {
+    let root = Git::root_of(git.root)?;
+    if !root.join(".gitmodules").exists() {
+        return Ok(Vec::new());
+    }
+    let git = git.root(&root);
+
+    // `submodule.<name>.<key> <value>`
+    let output = git
+        .cmd()
+        .args(["config", "--file", ".gitmodules", "--get-regexp", r"^submodule\..*\.(path|url)$"])
+        .output()?;
+    if !output.status.success() {
+        // `git config` exits with 1 when no key matches
+        if output.status.code() == Some(1) && output.stdout.is_empty() {
+            return Ok(Vec::new());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eyre::bail!("failed to read .gitmodules: {}", stderr.trim());
+    }
+    let config = String::from_utf8_lossy(&output.stdout);
+    let mut paths = BTreeMap::new();
+    let mut urls = BTreeMap::new();
+    for line in config.lines() {
+        let Some((key, value)) = line.split_once(' ') else { continue };
+        let Some(key) = key.strip_prefix("submodule.") else { continue };
+        if let Some(name) = key.strip_suffix(".path") {
+            paths.insert(name.to_string(), value.to_string());
+        } else if let Some(name) = key.strip_suffix(".url") {
+            urls.insert(name.to_string(), value.to_string());
+        }
+    }
+
+    // `[-+U ]<commit> <path>[ (<describe>)]`, `-` marks uninitialized submodules
+    let status = git.cmd().args(["submodule", "status"]).get_stdout_lossy()?;
+    let mut commits = BTreeMap::new();
+    for line in status.lines() {
+        let line = line.trim_start();
+        let initialized = !line.starts_with('-');
+        let line = line.trim_start_matches(['-', '+', 'U']);
+        let mut parts = line.split_whitespace();
+        let (Some(commit), Some(path)) = (parts.next(), parts.next()) else { continue };
+        if initialized {
+            commits.insert(path.to_string(), commit.to_string());
+        }
+    }
+
+    Ok(paths
+        .into_iter()
+        .map(|(name, path)| DependencyManifest {
+            url: urls.remove(&name),
+            commit: commits.remove(&path),
+            path: path.into(),
+            name,
+        })
+        .collect())
+}
+
+/// Prints the dependency manifest, either as JSON or as one line per dependency.
+pub fn print_manifest(deps: &[DependencyManifest], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(deps)?);
+        return Ok(());
+    }
+
+    for dep in deps {
+        println!(
+            "{} {} {} {}",
+            dep.name,
+            dep.commit.as_deref().unwrap_or("(not installed)"),
+            dep.path.display(),
+            dep.url.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_list() {
+        assert!(DepsArgs::try_parse_from(["foundry-cli", "--json"]).is_err());
+        let args = DepsArgs::parse_from(["foundry-cli", "--list", "--json"]);
+        assert!(args.list);
+        assert!(args.json);
+    }
+}
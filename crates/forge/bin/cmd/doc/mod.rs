@@ -1,12 +1,18 @@
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use forge_doc::{
-    ContractInheritance, Deployments, DocBuilder, GitSource, InferInlineHyperlinks, Inheritdoc,
+    parse_required_tags, ContractInheritance, Deployments, DocBuilder, GitSource,
+    InferInlineHyperlinks, Inheritdoc, InheritedMembers,
 };
 use foundry_cli::opts::GH_REPO_PREFIX_REGEX;
 use foundry_common::compile::ProjectCompiler;
-use foundry_config::{find_project_root_path, load_config_with_root};
-use std::{path::PathBuf, process::Command};
+use foundry_compilers::{resolver::parse::SolData, Graph};
+use foundry_config::{find_project_root_path, load_config_with_root, Config};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    process::Command,
+};
 
 mod server;
 use server::Server;
@@ -59,6 +65,39 @@ pub struct DocArgs {
     /// Whether to create docs for external libraries.
     #[arg(long, short)]
     include_libraries: bool,
+
+    /// Whether to also document the internal and private functions and state variables of
+    /// contracts, labeled by their visibility.
+    ///
+    /// By default only the public and external members are documented.
+    #[arg(long)]
+    include_private: bool,
+
+    /// Whether to document the members inherited from base contracts inline on the page of the
+    /// derived contract.
+    ///
+    /// By default the inherited members are listed with links to the pages of their bases.
+    #[arg(long)]
+    inline_inherited: bool,
+
+    /// Only write the `SUMMARY.md` fragment and the markdown pages, without scaffolding a
+    /// standalone book.
+    ///
+    /// Useful to embed the documentation into an existing mdBook.
+    #[arg(long, conflicts_with_all = ["build", "serve"])]
+    summary_only: bool,
+
+    /// The base path prepended to the links of the summary.
+    #[arg(long, requires = "summary_only", value_name = "PATH")]
+    summary_base: Option<PathBuf>,
+
+    /// Check that the public API is documented instead of generating the documentation, and exit
+    /// with an error listing the public and external functions, events and errors lacking any of
+    /// the required NatSpec tags.
+    ///
+    /// The required tags are configured with `doc.required_tags`.
+    #[arg(long, conflicts_with_all = ["build", "serve", "summary_only"])]
+    check_missing: bool,
 }
 
 impl DocArgs {
@@ -100,10 +139,18 @@ impl DocArgs {
             self.include_libraries,
         )
         .with_should_build(self.build)
+        .with_include_private(self.include_private)
+        .with_summary_only(self.summary_only)
+        .with_summary_base(self.summary_base)
         .with_config(doc_config.clone())
         .with_fmt(config.fmt)
         .with_preprocessor(ContractInheritance { include_libraries: self.include_libraries })
         .with_preprocessor(Inheritdoc::default())
+        .with_preprocessor(InheritedMembers {
+            include_libraries: self.include_libraries,
+            inline: self.inline_inherited,
+            imports: source_imports(&config)?,
+        })
         .with_preprocessor(InferInlineHyperlinks::default())
         .with_preprocessor(GitSource {
             root: root.clone(),
@@ -111,6 +158,18 @@ impl DocArgs {
             repository: doc_config.repository.clone(),
         });
 
+        if self.check_missing {
+            let required = parse_required_tags(&doc_config.required_tags)?;
+            let missing = builder.missing_natspec(&required)?;
+            if missing.is_empty() {
+                return Ok(())
+            }
+            for item in &missing {
+                eprintln!("{item}");
+            }
+            eyre::bail!("{} items are missing NatSpec documentation", missing.len());
+        }
+
         // If deployment docgen is enabled, add the [Deployments] preprocessor
         if let Some(deployments) = self.deployments {
             builder = builder.with_preprocessor(Deployments { root, deployments });
@@ -129,3 +188,13 @@ impl DocArgs {
         Ok(())
     }
 }
+
+/// Returns the files transitively imported by each source file of the project.
+fn source_imports(config: &Config) -> Result<HashMap<PathBuf, HashSet<PathBuf>>> {
+    let graph = Graph::<SolData>::resolve(&config.project_paths())?;
+    Ok(graph
+        .files()
+        .keys()
+        .map(|file| (file.clone(), graph.imports(file).into_iter().cloned().collect()))
+        .collect())
+}
@@ -8,6 +8,7 @@ use foundry_config::{filter::expand_globs, impl_figment_convert_basic};
 use rayon::prelude::*;
 use similar::{ChangeTag, TextDiff};
 use std::{
+    collections::BTreeMap,
     fmt::{self, Write},
     io,
     io::{Read, Write as _},
@@ -39,13 +40,39 @@ pub struct FmtArgs {
     /// In 'check' and stdin modes, outputs raw formatted code instead of the diff.
     #[arg(long, short)]
     raw: bool,
+
+    /// Sort the import statements alphabetically, within groups separated by a blank line.
+    ///
+    /// Same as setting `sort_imports = true` in the `[fmt]` section of the config.
+    #[arg(long)]
+    sort_imports: bool,
+
+    /// Format the fenced `solidity` code blocks of Markdown files, leaving the prose untouched.
+    ///
+    /// Markdown files given as paths are always formatted this way. With this flag, the Markdown
+    /// files of the given directories are formatted as well, and stdin is read as Markdown.
+    #[arg(long)]
+    markdown: bool,
+
+    /// Print the resolved formatter configuration as TOML instead of formatting.
+    #[arg(long, conflicts_with_all = ["check", "raw"])]
+    print_config: bool,
 }
 
 impl_figment_convert_basic!(FmtArgs);
 
 impl FmtArgs {
     pub fn run(self) -> Result<()> {
-        let config = self.try_load_config_emit_warnings()?;
+        let mut config = self.try_load_config_emit_warnings()?;
+        if self.sort_imports {
+            config.fmt.sort_imports = true;
+        }
+
+        if self.print_config {
+            let fmt = BTreeMap::from([("fmt", &config.fmt)]);
+            print!("{}", toml::to_string_pretty(&fmt)?);
+            return Ok(())
+        }
 
         // Expand ignore globs and canonicalize from the get go
         let ignored = expand_globs(&config.root.0, config.fmt.ignore.iter())?
@@ -84,7 +111,13 @@ impl FmtArgs {
                             path,
                             SOLC_EXTENSIONS,
                         ));
-                    } else if path.is_sol() {
+                        if self.markdown {
+                            inputs.extend(foundry_compilers::utils::source_files_iter(
+                                path,
+                                MARKDOWN_EXTENSIONS,
+                            ));
+                        }
+                    } else if path.is_sol() || is_markdown(path) {
                         inputs.push(path.to_path_buf());
                     } else {
                         warn!("Cannot process path {}", path.display());
@@ -94,15 +127,8 @@ impl FmtArgs {
             }
         };
 
-        let format = |source: String, path: Option<&Path>| -> Result<_> {
-            let name = match path {
-                Some(path) => {
-                    path.strip_prefix(&config.root.0).unwrap_or(path).display().to_string()
-                }
-                None => "stdin".to_string(),
-            };
-
-            let parsed = parse(&source).wrap_err_with(|| {
+        let format_solidity = |source: &str, name: &str| -> Result<String> {
+            let parsed = parse(source).wrap_err_with(|| {
                 format!("Failed to parse Solidity code for {name}. Leaving source unchanged.")
             })?;
 
@@ -125,6 +151,30 @@ impl FmtArgs {
                 )
             })?;
 
+            Ok(output)
+        };
+
+        let format = |source: String, path: Option<&Path>| -> Result<_> {
+            let name = match path {
+                Some(path) => {
+                    path.strip_prefix(&config.root.0).unwrap_or(path).display().to_string()
+                }
+                None => "stdin".to_string(),
+            };
+
+            let output = if path.map_or(self.markdown, is_markdown) {
+                // Snippets which are not valid source units, e.g. lone statements, are kept as is.
+                format_markdown(&source, |code, line| {
+                    let name = format!("{name} (code block at line {line})");
+                    format_solidity(code, &name).unwrap_or_else(|err| {
+                        cli_warn!("{err}");
+                        code.to_string()
+                    })
+                })
+            } else {
+                format_solidity(&source, &name)?
+            };
+
             if self.check || path.is_none() {
                 if self.raw {
                     print!("{output}");
@@ -185,6 +235,72 @@ impl FmtArgs {
     }
 }
 
+/// The extensions of the Markdown files.
+const MARKDOWN_EXTENSIONS: &[&str] = &["md"];
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MARKDOWN_EXTENSIONS.contains(&ext))
+}
+
+/// Formats the fenced `solidity` code blocks of a Markdown document with `format`, which is given
+/// the code of a block and the line of its opening fence.
+///
+/// The blocks keep the indentation of their fence, everything else is left as is.
+fn format_markdown(source: &str, mut format: impl FnMut(&str, usize) -> String) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut lines = source.split_inclusive('\n').enumerate();
+    while let Some((i, line)) = lines.next() {
+        output.push_str(line);
+
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let fence = line[indent..].trim_end();
+        let fence_char = match fence.chars().next() {
+            Some(c @ ('`' | '~')) => c,
+            _ => continue,
+        };
+        let fence_len = fence.chars().take_while(|c| *c == fence_char).count();
+        if fence_len < 3 {
+            continue
+        }
+        let info = fence[fence_len..].split_whitespace().next().unwrap_or_default();
+        if !matches!(info, "solidity" | "sol") {
+            continue
+        }
+
+        // Collect the code until the closing fence, blocks which are not closed are skipped.
+        let mut raw = String::new();
+        let mut code = String::new();
+        let mut closing = None;
+        for (_, line) in lines.by_ref() {
+            let trimmed = line.trim();
+            if trimmed.len() >= fence_len && trimmed.chars().all(|c| c == fence_char) {
+                closing = Some(line);
+                break
+            }
+            raw.push_str(line);
+            let stripped = line.len() - line.trim_start_matches(' ').len();
+            code.push_str(&line[stripped.min(indent)..]);
+        }
+        let Some(closing) = closing else {
+            output.push_str(&raw);
+            break
+        };
+
+        for line in format(&code, i + 1).split_inclusive('\n') {
+            if line.trim().is_empty() {
+                output.push_str(line.trim_start_matches(' '));
+            } else {
+                output.extend(std::iter::repeat(' ').take(indent));
+                output.push_str(line);
+            }
+        }
+        output.push_str(closing);
+    }
+    output
+}
+
 struct Line(Option<usize>);
 
 #[derive(Debug)]
@@ -244,3 +360,44 @@ fn format_diff_summary<'a>(name: &str, diff: &'a TextDiff<'a, 'a, '_, str>) -> S
 
     diff_summary
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_solidity_code_blocks() {
+        let source = "# Title\n\
+                      ```solidity\n\
+                      contract  A {}\n\
+                      ```\n\
+                      Some  prose.\n\
+                      ```js\n\
+                      let  a;\n\
+                      ```\n\
+                      - item\n  ```sol\n  contract  B {}\n\n  contract C {}\n  ```\n";
+        let mut blocks = vec![];
+        let output = format_markdown(source, |code, line| {
+            blocks.push((code.to_string(), line));
+            code.replace("  ", " ")
+        });
+        assert_eq!(
+            blocks,
+            [
+                ("contract  A {}\n".to_string(), 2),
+                ("contract  B {}\n\ncontract C {}\n".to_string(), 10)
+            ]
+        );
+        assert_eq!(
+            output,
+            "# Title\n```solidity\ncontract A {}\n```\nSome  prose.\n```js\nlet  a;\n```\n\
+             - item\n  ```sol\n  contract B {}\n\n  contract C {}\n  ```\n"
+        );
+    }
+
+    #[test]
+    fn keeps_unclosed_code_blocks() {
+        let source = "```solidity\ncontract  A {}\n";
+        assert_eq!(format_markdown(source, |_, _| unreachable!()), source);
+    }
+}
@@ -2,6 +2,7 @@ use super::{error::ScanFileError, visitor::CheatcodeVisitor};
 use eyre::Result;
 use forge_fmt::{offset_to_line_column, parse2, FormatterError, Visitable};
 use foundry_common::fs;
+use serde::Serialize;
 use solang_parser::pt::Loc;
 use std::{
     fmt,
@@ -40,6 +41,41 @@ pub struct SolFileMetrics {
     pub cheatcodes: UnsafeCheatcodes,
 }
 
+impl SolFileMetrics {
+    /// Returns the unsafe cheatcode calls of the file, ordered by location.
+    pub fn findings(&self) -> Vec<Finding> {
+        let mut findings = self
+            .cheatcodes
+            .iter()
+            .flat_map(|(cheatcode, locs)| {
+                locs.iter().map(move |loc| {
+                    let (line, col) = offset_to_line_column(&self.contents, loc.start());
+                    Finding { cheatcode, line, col }
+                })
+            })
+            .collect::<Vec<_>>();
+        findings.sort_by_key(|finding| (finding.line, finding.col));
+        findings
+    }
+}
+
+/// The findings of a single file, as printed with `--json`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FileFindings {
+    /// The path of the file, relative to the project root.
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+/// An unsafe cheatcode call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    /// The name of the cheatcode, e.g. `ffi`.
+    pub cheatcode: &'static str,
+    pub line: usize,
+    pub col: usize,
+}
+
 /// Formats the metrics for a single file using [`fmt::Display`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SolFileMetricsPrinter<'a, 'b> {
@@ -113,6 +149,23 @@ impl UnsafeCheatcodes {
         self.len() == 0
     }
 
+    /// Returns the calls of every unsafe cheatcode, by cheatcode name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &[Loc])> {
+        [
+            ("ffi", &self.ffi),
+            ("readFile", &self.read_file),
+            ("readLine", &self.read_line),
+            ("writeFile", &self.write_file),
+            ("writeLine", &self.write_line),
+            ("removeFile", &self.remove_file),
+            ("closeFile", &self.close_file),
+            ("setEnv", &self.set_env),
+            ("deriveKey", &self.derive_key),
+        ]
+        .into_iter()
+        .map(|(name, locs)| (name, &locs[..]))
+    }
+
     /// The total number of unsafe calls.
     pub fn len(&self) -> usize {
         self.ffi.len() +
@@ -162,4 +215,27 @@ mod tests {
         assert_eq!(count.ffi.len(), 1);
         assert!(!count.is_empty());
     }
+
+    #[test]
+    fn can_locate_findings() {
+        let contents = r#"contract A is Test {
+    function do_unsafe() public {
+        vm.setEnv("KEY", "value");
+        string[] memory inputs = new string[](1);
+        vm.ffi(inputs); vm.ffi(inputs);
+    }
+}
+"#
+        .to_string();
+        let cheatcodes = find_cheatcodes_in_string(&contents, None).unwrap();
+        let metrics = SolFileMetrics { file: "A.sol".into(), contents, cheatcodes };
+        assert_eq!(
+            metrics.findings(),
+            [
+                Finding { cheatcode: "setEnv", line: 3, col: 9 },
+                Finding { cheatcode: "ffi", line: 5, col: 9 },
+                Finding { cheatcode: "ffi", line: 5, col: 25 },
+            ]
+        );
+    }
 }
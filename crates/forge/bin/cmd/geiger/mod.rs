@@ -11,7 +11,7 @@ use yansi::Paint;
 mod error;
 
 mod find;
-use find::{find_cheatcodes_in_file, SolFileMetricsPrinter};
+use find::{find_cheatcodes_in_file, FileFindings, SolFileMetricsPrinter};
 
 mod visitor;
 
@@ -52,6 +52,13 @@ pub struct GeigerArgs {
     /// Print a report of all files, even if no unsafe functions are found.
     #[arg(long)]
     full: bool,
+
+    /// Print the findings as JSON, as `{files: [{path, findings: [{cheatcode, line, col}]}],
+    /// total}`.
+    ///
+    /// Only the files with findings are listed, unless `--full` is provided.
+    #[arg(long)]
+    json: bool,
 }
 
 impl_figment_convert_basic!(GeigerArgs);
@@ -100,6 +107,30 @@ impl GeigerArgs {
 
         let root = config.root.0;
 
+        if self.json {
+            let mut files = sources
+                .par_iter()
+                .filter_map(|file| match find_cheatcodes_in_file(file) {
+                    Ok(metrics) => {
+                        let findings = metrics.findings();
+                        (self.full || !findings.is_empty()).then(|| FileFindings {
+                            path: file.strip_prefix(&root).unwrap_or(file).to_path_buf(),
+                            findings,
+                        })
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        None
+                    }
+                })
+                .collect::<Vec<_>>();
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            let total = files.iter().map(|file| file.findings.len()).sum::<usize>();
+            let json = serde_json::json!({ "files": files, "total": total });
+            println!("{}", serde_json::to_string_pretty(&json)?);
+            return Ok(total)
+        }
+
         let sum = sources
             .par_iter()
             .map(|file| match find_cheatcodes_in_file(file) {
@@ -2,7 +2,7 @@ use super::install::DependencyInstallOpts;
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use foundry_cli::{p_println, utils::Git};
-use foundry_common::fs;
+use foundry_common::{fs, term::cli_warn};
 use foundry_compilers::artifacts::remappings::Remapping;
 use foundry_config::Config;
 use std::path::{Path, PathBuf};
@@ -16,7 +16,7 @@ pub struct InitArgs {
     pub root: PathBuf,
 
     /// The template to start from.
-    #[arg(long, short)]
+    #[arg(long, short, conflicts_with = "no_git")]
     pub template: Option<String>,
 
     /// Branch argument that can only be used with template option.
@@ -138,7 +138,9 @@ impl InitArgs {
             let git = self.opts.git(&config);
 
             // set up the repo
-            if !no_git {
+            if no_git {
+                init_gitignore(&root)?;
+            } else {
                 init_git_repo(git, no_commit)?;
             }
 
@@ -149,7 +151,17 @@ impl InitArgs {
                     self.opts.install(&mut config, vec![])?;
                 } else {
                     let dep = "https://github.com/foundry-rs/forge-std".parse()?;
-                    self.opts.install(&mut config, vec![dep])?;
+                    let res = self.opts.install(&mut config, vec![dep]);
+                    // without git the project is still usable, dependencies can be installed later
+                    match res {
+                        Err(err) if no_git => {
+                            cli_warn!(
+                                "Failed to install forge-std, skipping dependencies: {err}\n\
+                                 Run `forge install --no-git foundry-rs/forge-std` to install it."
+                            );
+                        }
+                        res => res?,
+                    }
                 }
             }
 
@@ -175,11 +187,7 @@ fn init_git_repo(git: Git<'_>, no_commit: bool) -> Result<()> {
         git.init()?;
     }
 
-    // .gitignore
-    let gitignore = git.root.join(".gitignore");
-    if !gitignore.exists() {
-        fs::write(gitignore, include_str!("../../assets/.gitignoreTemplate"))?;
-    }
+    init_gitignore(git.root)?;
 
     // github workflow
     let workflow = git.root.join(".github/workflows/test.yml");
@@ -197,6 +205,15 @@ fn init_git_repo(git: Git<'_>, no_commit: bool) -> Result<()> {
     Ok(())
 }
 
+/// Creates `.gitignore` in `root`, if it doesn't exist already.
+fn init_gitignore(root: &Path) -> Result<()> {
+    let gitignore = root.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(gitignore, include_str!("../../assets/.gitignoreTemplate"))?;
+    }
+    Ok(())
+}
+
 /// initializes the `.vscode/settings.json` file
 fn init_vscode(root: &Path) -> Result<()> {
     let remappings_file = root.join("remappings.txt");
@@ -0,0 +1,101 @@
+//! Human-readable formatting of ABIs.
+
+use alloy_json_abi::{JsonAbi, Param, StateMutability};
+use clap::ValueEnum;
+
+/// The format of the `abi` field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AbiFormat {
+    /// The JSON ABI.
+    #[default]
+    Json,
+    /// One human-readable signature per line, e.g.
+    /// `function transfer(address,uint256) returns (bool)`.
+    Human,
+}
+
+/// Returns the human-readable signatures of the constructor, functions, events and errors of the
+/// ABI, in this order.
+pub fn human_readable_abi(abi: &JsonAbi) -> Vec<String> {
+    let mut signatures = Vec::new();
+
+    if let Some(constructor) = &abi.constructor {
+        let mutability = mutability(constructor.state_mutability);
+        signatures.push(format!("constructor({}){mutability}", types(&constructor.inputs)));
+    }
+    for function in abi.functions() {
+        let mutability = mutability(function.state_mutability);
+        let mut signature = format!("function {}{mutability}", function.signature());
+        if !function.outputs.is_empty() {
+            signature.push_str(&format!(" returns ({})", types(&function.outputs)));
+        }
+        signatures.push(signature);
+    }
+    if let Some(fallback) = &abi.fallback {
+        signatures.push(format!("fallback(){}", mutability(fallback.state_mutability)));
+    }
+    if abi.receive.is_some() {
+        signatures.push("receive() payable".to_string());
+    }
+    for event in abi.events() {
+        let params = event
+            .inputs
+            .iter()
+            .map(|param| {
+                let ty = param.selector_type();
+                if param.indexed {
+                    format!("{ty} indexed")
+                } else {
+                    ty.into_owned()
+                }
+            })
+            .collect::<Vec<_>>();
+        let anonymous = if event.anonymous { " anonymous" } else { "" };
+        signatures.push(format!("event {}({}){anonymous}", event.name, params.join(",")));
+    }
+    for error in abi.errors() {
+        signatures.push(format!("error {}", error.signature()));
+    }
+
+    signatures
+}
+
+fn types(params: &[Param]) -> String {
+    params.iter().map(|param| param.selector_type()).collect::<Vec<_>>().join(",")
+}
+
+fn mutability(mutability: StateMutability) -> &'static str {
+    match mutability {
+        StateMutability::Pure => " pure",
+        StateMutability::View => " view",
+        StateMutability::NonPayable => "",
+        StateMutability::Payable => " payable",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_format_human_readable_abi() {
+        let abi = JsonAbi::parse([
+            "constructor(address owner)",
+            "function transfer(address to, uint256 amount) returns (bool)",
+            "function balanceOf(address) view returns (uint256)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "error Unauthorized(address caller)",
+        ])
+        .unwrap();
+        assert_eq!(
+            human_readable_abi(&abi),
+            [
+                "constructor(address)",
+                "function balanceOf(address) view returns (uint256)",
+                "function transfer(address,uint256) returns (bool)",
+                "event Transfer(address indexed,address indexed,uint256)",
+                "error Unauthorized(address)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! ABI encoding of constructor arguments.
+
+use alloy_dyn_abi::{DynSolType, JsonAbiExt, Specifier};
+use alloy_json_abi::JsonAbi;
+use eyre::{Context, Result};
+
+/// ABI encodes the arguments of the constructor of the contract with the given ABI, as appended to
+/// its creation bytecode.
+///
+/// Every argument is checked against its parameter, so that an invalid value is reported with the
+/// position, name and type of the parameter it was given for.
+pub fn encode_constructor_args(abi: &JsonAbi, name: &str, args: &[String]) -> Result<Vec<u8>> {
+    let Some(constructor) = &abi.constructor else {
+        if !args.is_empty() {
+            eyre::bail!("`{name}` has no constructor, but {} arguments were given", args.len());
+        }
+        return Ok(Vec::new())
+    };
+
+    let inputs = &constructor.inputs;
+    if inputs.len() != args.len() {
+        let types = inputs.iter().map(|p| p.ty.as_str()).collect::<Vec<_>>();
+        eyre::bail!(
+            "the constructor of `{name}` takes {} arguments (`constructor({})`), but {} were given",
+            inputs.len(),
+            types.join(","),
+            args.len()
+        );
+    }
+
+    let mut values = Vec::with_capacity(args.len());
+    for (i, (param, arg)) in inputs.iter().zip(args).enumerate() {
+        let describe = || {
+            let name =
+                if param.name.is_empty() { String::new() } else { format!(" `{}`", param.name) };
+            format!("invalid constructor argument #{i}{name} of type `{}`: {arg:?}", param.ty)
+        };
+        let ty: DynSolType = param.resolve().wrap_err_with(describe)?;
+        values.push(ty.coerce_str(arg).wrap_err_with(describe)?);
+    }
+
+    Ok(constructor.abi_encode_input(&values)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn can_encode_constructor_args() {
+        let abi = JsonAbi::parse(["constructor(address owner, uint256 supply)"]).unwrap();
+        let args = ["0x00000000000000000000000000000000000000aa".to_string(), "1".to_string()];
+        let encoded = encode_constructor_args(&abi, "Token", &args).unwrap();
+        assert_eq!(
+            hex::encode(encoded),
+            "00000000000000000000000000000000000000000000000000000000000000aa\
+             0000000000000000000000000000000000000000000000000000000000000001"
+        );
+
+        let err = encode_constructor_args(&abi, "Token", &args[..1]).unwrap_err();
+        assert!(err.to_string().contains("takes 2 arguments"), "{err}");
+
+        let invalid = [args[0].clone(), "one".to_string()];
+        let err = encode_constructor_args(&abi, "Token", &invalid).unwrap_err();
+        assert!(err.to_string().contains("#1 `supply` of type `uint256`"), "{err}");
+    }
+
+    #[test]
+    fn contracts_without_constructor_take_no_args() {
+        let abi = JsonAbi::parse(["function get() external returns (uint256)"]).unwrap();
+        assert!(encode_constructor_args(&abi, "Store", &[]).unwrap().is_empty());
+        assert!(encode_constructor_args(&abi, "Store", &["1".to_string()]).is_err());
+    }
+}
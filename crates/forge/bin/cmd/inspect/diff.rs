@@ -0,0 +1,422 @@
+//! Diffing of the inspected fields against another git revision.
+
+use super::{compile_artifact, print_json, ContractArtifactField};
+use alloy_json_abi::{JsonAbi, StateMutability};
+use alloy_primitives::U256;
+use eyre::Result;
+use foundry_cli::{
+    opts::CoreBuildArgs,
+    utils::{CommandUtils, Git, LoadConfig},
+};
+use foundry_compilers::{
+    artifacts::{ConfigurableContractArtifact, StorageLayout},
+    info::ContractInfo,
+    utils::canonicalize,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Compiles the current tree and `git_ref`, then prints the diff of the given field between them.
+pub fn run(
+    contract: &ContractInfo,
+    field: ContractArtifactField,
+    build: CoreBuildArgs,
+    git_ref: &str,
+    pretty: bool,
+) -> Result<()> {
+    if !matches!(field, ContractArtifactField::Abi | ContractArtifactField::StorageLayout) {
+        eyre::bail!("`--diff` is only supported for the `abi` and `storage-layout` fields");
+    }
+
+    let config = build.try_load_config_emit_warnings()?;
+    let root = canonicalize(&config.root.0)?;
+    let repo_root = Git::root_of(&root)?;
+    let worktree = Worktree::add(Git::new(&repo_root), git_ref)?;
+    let old_root = worktree.path.join(root.strip_prefix(&repo_root)?);
+    link_libs(&root, &old_root, &config.libs)?;
+
+    let mut old_contract = contract.clone();
+    if let Some(path) = &mut old_contract.path {
+        let path_in_root = canonicalize(&*path)?;
+        *path = old_root.join(path_in_root.strip_prefix(&root)?).to_string_lossy().to_string();
+    }
+    let mut old_build = build.clone();
+    old_build.project_paths.root = Some(old_root);
+
+    let new = compile_artifact(contract, &[field], build)?;
+    let old = compile_artifact(&old_contract, &[field], old_build)
+        .map_err(|err| eyre::eyre!("failed to compile `{contract}` at {git_ref}: {err}"))?;
+
+    match field {
+        ContractArtifactField::Abi => {
+            let diff = AbiDiff::new(abi(&old)?, abi(&new)?);
+            if pretty {
+                diff.print();
+            } else {
+                print_json(&diff)?;
+            }
+        }
+        ContractArtifactField::StorageLayout => {
+            let diff = InspectStorageDiff::new(storage_layout(&old)?, storage_layout(&new)?);
+            if pretty {
+                diff.print();
+            } else {
+                print_json(&diff)?;
+            }
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+fn abi(artifact: &ConfigurableContractArtifact) -> Result<&JsonAbi> {
+    artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch lossless ABI"))
+}
+
+fn storage_layout(artifact: &ConfigurableContractArtifact) -> Result<&StorageLayout> {
+    artifact.storage_layout.as_ref().ok_or_else(|| eyre::eyre!("Could not get storage layout"))
+}
+
+/// A temporary git worktree checked out at a given revision, removed on drop.
+struct Worktree<'a> {
+    git: Git<'a>,
+    path: PathBuf,
+    _dir: tempfile::TempDir,
+}
+
+impl<'a> Worktree<'a> {
+    fn add(git: Git<'a>, git_ref: &str) -> Result<Self> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("worktree");
+        git.cmd().args(["worktree", "add", "--detach"]).arg(&path).arg(git_ref).exec()?;
+        Ok(Self { git, path, _dir: dir })
+    }
+}
+
+impl Drop for Worktree<'_> {
+    fn drop(&mut self) {
+        let _ = self.git.cmd().args(["worktree", "remove", "--force"]).arg(&self.path).exec();
+    }
+}
+
+/// Links the dependencies of the current checkout into the worktree, since submodules are not
+/// initialized in new worktrees.
+fn link_libs(root: &Path, worktree_root: &Path, libs: &[PathBuf]) -> Result<()> {
+    for lib in libs {
+        let Ok(lib) = root.join(lib).strip_prefix(root).map(Path::to_path_buf) else { continue };
+        let Ok(entries) = std::fs::read_dir(root.join(&lib)) else { continue };
+        std::fs::create_dir_all(worktree_root.join(&lib))?;
+        for entry in entries {
+            let entry = entry?;
+            let target = worktree_root.join(&lib).join(entry.file_name());
+            // Uninitialized submodules are checked out as empty directories.
+            if target.is_dir() && std::fs::read_dir(&target)?.next().is_none() {
+                std::fs::remove_dir(&target)?;
+            }
+            if !target.exists() {
+                symlink(&entry.path(), &target)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink(original: &Path, link: &Path) -> std::io::Result<()> {
+    if original.is_dir() {
+        std::os::windows::fs::symlink_dir(original, link)
+    } else {
+        std::os::windows::fs::symlink_file(original, link)
+    }
+}
+
+/// The differences between two ABIs.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AbiDiff {
+    /// Items only present in the new ABI.
+    pub added: Vec<String>,
+    /// Items only present in the old ABI.
+    pub removed: Vec<String>,
+    /// Items present in both ABIs, with a different definition, as `(old, new)`.
+    pub changed: Vec<(String, String)>,
+}
+
+impl AbiDiff {
+    pub fn new(old: &JsonAbi, new: &JsonAbi) -> Self {
+        let old = abi_items(old);
+        let mut new = abi_items(new);
+
+        let mut diff = Self::default();
+        for (key, old) in old {
+            match new.remove(&key) {
+                Some(new) if new != old => diff.changed.push((old, new)),
+                Some(_) => {}
+                None => diff.removed.push(old),
+            }
+        }
+        diff.added.extend(new.into_values());
+        diff
+    }
+
+    fn print(&self) {
+        if self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() {
+            println!("No differences found");
+            return;
+        }
+        for item in &self.removed {
+            println!("- {item}");
+        }
+        for item in &self.added {
+            println!("+ {item}");
+        }
+        for (old, new) in &self.changed {
+            println!("~ {old} => {new}");
+        }
+    }
+}
+
+/// Returns the ABI items keyed by their identity, i.e. their name and input types.
+fn abi_items(abi: &JsonAbi) -> BTreeMap<String, String> {
+    let mut items = BTreeMap::new();
+    if let Some(constructor) = &abi.constructor {
+        let inputs = constructor.inputs.iter().map(|p| p.selector_type()).collect::<Vec<_>>();
+        items.insert(
+            "constructor".to_string(),
+            format!(
+                "constructor({}) {}",
+                inputs.join(","),
+                mutability(constructor.state_mutability)
+            ),
+        );
+    }
+    if let Some(fallback) = &abi.fallback {
+        items.insert(
+            "fallback".to_string(),
+            format!("fallback() {}", mutability(fallback.state_mutability)),
+        );
+    }
+    if abi.receive.is_some() {
+        items.insert("receive".to_string(), "receive() payable".to_string());
+    }
+    for function in abi.functions() {
+        let outputs = function.outputs.iter().map(|p| p.selector_type()).collect::<Vec<_>>();
+        let mut item =
+            format!("function {} {}", function.signature(), mutability(function.state_mutability));
+        if !outputs.is_empty() {
+            item.push_str(&format!(" returns ({})", outputs.join(",")));
+        }
+        items.insert(format!("function {}", function.signature()), item);
+    }
+    for event in abi.events() {
+        let inputs = event
+            .inputs
+            .iter()
+            .map(|p| {
+                if p.indexed {
+                    format!("{} indexed", p.selector_type())
+                } else {
+                    p.selector_type().into_owned()
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut item = format!("event {}({})", event.name, inputs.join(","));
+        if event.anonymous {
+            item.push_str(" anonymous");
+        }
+        items.insert(format!("event {}", event.signature()), item);
+    }
+    for error in abi.errors() {
+        items
+            .insert(format!("error {}", error.signature()), format!("error {}", error.signature()));
+    }
+    items
+}
+
+fn mutability(state_mutability: StateMutability) -> &'static str {
+    match state_mutability {
+        StateMutability::Pure => "pure",
+        StateMutability::View => "view",
+        StateMutability::NonPayable => "nonpayable",
+        StateMutability::Payable => "payable",
+    }
+}
+
+/// The differences between two storage layouts.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct InspectStorageDiff {
+    pub changes: Vec<StorageChange>,
+}
+
+/// A change of a single storage variable, identified by its label.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct StorageChange {
+    pub label: String,
+    /// The old location and type of the variable, if it existed.
+    pub old: Option<String>,
+    /// The new location and type of the variable, if it still exists.
+    pub new: Option<String>,
+    /// Whether the change corrupts the storage of already deployed contracts.
+    pub breaking: bool,
+}
+
+impl InspectStorageDiff {
+    /// Diffs two storage layouts.
+    ///
+    /// Removing, moving or retyping a variable is breaking. Adding a variable is only
+    /// non-breaking if it is appended after all the existing variables.
+    pub fn new(old: &StorageLayout, new: &StorageLayout) -> Self {
+        let describe = |layout: &StorageLayout| {
+            layout
+                .storage
+                .iter()
+                .map(|slot| {
+                    let ty = layout.types.get(&slot.storage_type).map_or("?", |t| &t.label);
+                    let location = format!("slot {} offset {}: {ty}", slot.slot, slot.offset);
+                    (slot.label.clone(), location)
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        let last_slot = old.storage.iter().filter_map(|slot| slot.slot.parse::<U256>().ok()).max();
+
+        let old_slots = describe(old);
+        let mut new_slots = describe(new);
+        let mut changes = Vec::new();
+        for (label, old) in old_slots {
+            match new_slots.remove(&label) {
+                Some(new) if new == old => {}
+                new => changes.push(StorageChange { label, old: Some(old), new, breaking: true }),
+            }
+        }
+        for slot in &new.storage {
+            let Some(location) = new_slots.remove(&slot.label) else { continue };
+            let appended = match (last_slot, slot.slot.parse::<U256>()) {
+                (Some(last), Ok(slot)) => slot > last,
+                (None, _) => true,
+                _ => false,
+            };
+            changes.push(StorageChange {
+                label: slot.label.clone(),
+                old: None,
+                new: Some(location),
+                breaking: !appended,
+            });
+        }
+
+        Self { changes }
+    }
+
+    /// Returns `true` if any of the changes is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    fn print(&self) {
+        if self.changes.is_empty() {
+            println!("No differences found");
+            return;
+        }
+        for change in &self.changes {
+            let marker = if change.breaking { "breaking" } else { "non-breaking" };
+            match (&change.old, &change.new) {
+                (Some(old), Some(new)) => println!("~ [{marker}] {}: {old} => {new}", change.label),
+                (Some(old), None) => println!("- [{marker}] {}: {old}", change.label),
+                (None, Some(new)) => println!("+ [{marker}] {}: {new}", change.label),
+                (None, None) => unreachable!(),
+            }
+        }
+        if self.is_breaking() {
+            println!("The storage layout changes are breaking");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(slots: &[(&str, &str, &str)]) -> StorageLayout {
+        let storage = slots
+            .iter()
+            .map(|&(label, slot, ty)| {
+                serde_json::json!({
+                    "astId": 0,
+                    "contract": "C",
+                    "label": label,
+                    "offset": 0,
+                    "slot": slot,
+                    "type": ty,
+                })
+            })
+            .collect::<Vec<_>>();
+        let types = serde_json::json!({
+            "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+            "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+        });
+        serde_json::from_value(serde_json::json!({ "storage": storage, "types": types })).unwrap()
+    }
+
+    #[test]
+    fn abi_diff() {
+        let old = JsonAbi::parse([
+            "function keep(uint256) returns (uint256)",
+            "function remove()",
+            "function change(uint256) view returns (uint256)",
+        ])
+        .unwrap();
+        let new = JsonAbi::parse([
+            "function keep(uint256) returns (uint256)",
+            "function add(address)",
+            "function change(uint256) view returns (bool)",
+        ])
+        .unwrap();
+
+        let diff = AbiDiff::new(&old, &new);
+        assert_eq!(diff.added, ["function add(address) nonpayable"]);
+        assert_eq!(diff.removed, ["function remove() nonpayable"]);
+        assert_eq!(
+            diff.changed,
+            [(
+                "function change(uint256) view returns (uint256)".to_string(),
+                "function change(uint256) view returns (bool)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn storage_layout_appended_is_not_breaking() {
+        let old = layout(&[("a", "0", "t_uint256")]);
+        let new = layout(&[("a", "0", "t_uint256"), ("b", "1", "t_address")]);
+        let diff = InspectStorageDiff::new(&old, &new);
+        assert_eq!(diff.changes.len(), 1);
+        assert!(!diff.is_breaking());
+    }
+
+    #[test]
+    fn storage_layout_changes_are_breaking() {
+        let old = layout(&[("a", "0", "t_uint256"), ("b", "1", "t_address")]);
+
+        // retyped
+        let new = layout(&[("a", "0", "t_address"), ("b", "1", "t_address")]);
+        assert!(InspectStorageDiff::new(&old, &new).is_breaking());
+
+        // removed
+        let new = layout(&[("a", "0", "t_uint256")]);
+        assert!(InspectStorageDiff::new(&old, &new).is_breaking());
+
+        // inserted before existing variables
+        let new =
+            layout(&[("c", "0", "t_uint256"), ("a", "1", "t_uint256"), ("b", "2", "t_address")]);
+        let diff = InspectStorageDiff::new(&old, &new);
+        assert!(diff.changes.iter().all(|change| change.breaking));
+        assert_eq!(diff.changes.len(), 3);
+    }
+}
@@ -0,0 +1,35 @@
+//! Deployment order of the libraries linked by a contract.
+
+use eyre::Result;
+use foundry_cli::opts::CoreBuildArgs;
+use foundry_common::compile::ProjectCompiler;
+use foundry_compilers::{info::ContractInfo, utils::canonicalize};
+use foundry_linking::Linker;
+
+/// Compiles the project and returns the libraries `contract` links against, directly or through
+/// other libraries, as `<path>:<name>` identifiers in the order in which they must be deployed.
+pub fn deploy_order(contract: &ContractInfo, build: CoreBuildArgs) -> Result<Vec<String>> {
+    let project = build.project()?;
+    let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+
+    let path = contract.path.as_ref().map(canonicalize).transpose()?;
+    let linker = Linker::new(project.root(), output.artifact_ids().collect());
+    let target = linker
+        .contracts
+        .keys()
+        .find(|id| {
+            id.name == contract.name && path.as_ref().map_or(true, |path| *path == id.source)
+        })
+        .ok_or_else(|| {
+            eyre::eyre!("Could not find artifact `{contract}` in the compiled artifacts")
+        })?;
+
+    let order = linker.deploy_order(target)?;
+    Ok(order
+        .into_iter()
+        .map(|id| {
+            let source = id.source.strip_prefix(project.root()).unwrap_or(&id.source);
+            format!("{}:{}", source.display(), id.name)
+        })
+        .collect())
+}
@@ -0,0 +1,159 @@
+//! Generation of a mock contract from the ABI.
+
+use alloy_json_abi::{Function, JsonAbi, Param, StateMutability};
+use alloy_primitives::hex;
+use std::fmt::Write;
+
+/// Generates the source of a mock of the contract with the given ABI.
+///
+/// Calls to the functions of the ABI are handled by the fallback, which returns the data set with
+/// the `set<Function>Return` setters, or with `setReturnData` for any selector, and nothing
+/// otherwise. The calls to non-view functions are recorded in `callCount` and `lastCalldata`,
+/// keyed by selector. Calls to view functions cannot be recorded, as they may be static calls.
+pub fn mock_source(abi: &JsonAbi, name: &str) -> String {
+    let mut setters = String::new();
+    let mut views = Vec::new();
+    for (function_name, overloads) in &abi.functions {
+        for function in overloads {
+            let selector = hex::encode(function.selector());
+            if matches!(function.state_mutability, StateMutability::View | StateMutability::Pure) {
+                views.push(format!("selector == 0x{selector}"));
+            }
+
+            let mut setter = format!("set{}Return", capitalize(function_name));
+            if overloads.len() > 1 {
+                write!(setter, "_{selector}").unwrap();
+            }
+            write_setter(&mut setters, function, &setter, &selector);
+        }
+    }
+
+    let is_view = if views.is_empty() { "false".to_string() } else { views.join(" || ") };
+    format!(
+        r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.7.6;
+
+/// Mock of `{name}`, generated by `forge inspect {name} mock`.
+contract {name}Mock {{
+    mapping(bytes4 => bytes) internal _returnData;
+    mapping(bytes4 => uint256) public callCount;
+    mapping(bytes4 => bytes) public lastCalldata;
+
+    /// Sets the raw data returned by calls to the function with the given selector.
+    function setReturnData(bytes4 selector, bytes memory data) public {{
+        _returnData[selector] = data;
+    }}
+{setters}
+    fallback(bytes calldata data) external payable returns (bytes memory) {{
+        if (!_isView(msg.sig)) {{
+            callCount[msg.sig] += 1;
+            lastCalldata[msg.sig] = data;
+        }}
+        return _returnData[msg.sig];
+    }}
+
+    receive() external payable {{}}
+
+    function _isView(bytes4 selector) internal pure returns (bool) {{
+        return {is_view};
+    }}
+}}
+"#
+    )
+}
+
+/// Writes the typed setter of the return values of the function, if it returns any.
+///
+/// Tuples would require declaring their structs, their return data is set with `setReturnData`.
+fn write_setter(out: &mut String, function: &Function, setter: &str, selector: &str) {
+    if function.outputs.is_empty() || function.outputs.iter().any(|p| p.ty.starts_with("tuple")) {
+        return
+    }
+
+    let names = function
+        .outputs
+        .iter()
+        .enumerate()
+        .map(|(i, p)| if is_identifier(&p.name) { p.name.clone() } else { format!("ret{i}") })
+        .collect::<Vec<_>>();
+    let params = function
+        .outputs
+        .iter()
+        .zip(&names)
+        .map(|(p, name)| format!("{} {name}", param_type(p)))
+        .collect::<Vec<_>>();
+
+    let _ = write!(
+        out,
+        r#"
+    /// Sets the values returned by `{}`.
+    function {setter}({}) public {{
+        _returnData[0x{selector}] = abi.encode({});
+    }}
+"#,
+        function.signature(),
+        params.join(", "),
+        names.join(", "),
+    );
+}
+
+/// Returns the type of the parameter, with the `memory` location of reference types.
+fn param_type(param: &Param) -> String {
+    let ty = &param.ty;
+    if ty == "string" || ty == "bytes" || ty.ends_with(']') {
+        format!("{ty} memory")
+    } else {
+        ty.clone()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_') &&
+        name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_generate_mock() {
+        let abi = JsonAbi::parse([
+            "function balanceOf(address owner) external view returns (uint256)",
+            "function transfer(address to, uint256 amount) external returns (bool)",
+            "function name() external view returns (string)",
+            "function approve(address spender, uint256 amount) external",
+        ])
+        .unwrap();
+        let source = mock_source(&abi, "Token");
+
+        assert!(source.contains("contract TokenMock {"), "{source}");
+        assert!(source.contains("function setBalanceOfReturn(uint256 ret0) public {"), "{source}");
+        assert!(source.contains("_returnData[0x70a08231] = abi.encode(ret0);"), "{source}");
+        assert!(source.contains("function setNameReturn(string memory ret0) public {"), "{source}");
+        assert!(source.contains("function setTransferReturn(bool ret0) public {"), "{source}");
+        assert!(!source.contains("setApproveReturn"), "{source}");
+        assert!(source.contains("return selector == 0x70a08231 || selector == 0x06fdde03;"));
+    }
+
+    #[test]
+    fn can_disambiguate_overloads() {
+        let abi = JsonAbi::parse([
+            "function get() external returns (uint256)",
+            "function get(uint256 id) external returns (uint256)",
+        ])
+        .unwrap();
+        let source = mock_source(&abi, "Store");
+
+        for function in abi.functions() {
+            let setter = format!("setGetReturn_{}(", hex::encode(function.selector()));
+            assert!(source.contains(&setter), "{source}");
+        }
+        assert!(source.contains("return false;"), "{source}");
+    }
+}
@@ -0,0 +1,1048 @@
+use abi::{human_readable_abi, AbiFormat};
+use alloy_primitives::hex;
+use clap::{Parser, ValueHint};
+use comfy_table::{presets::ASCII_MARKDOWN, Table};
+use eyre::Result;
+use foundry_cli::opts::{CompilerArgs, CoreBuildArgs};
+use foundry_common::{compile::ProjectCompiler, fs};
+use foundry_compilers::{
+    artifacts::{
+        output_selection::{
+            BytecodeOutputSelection, ContractOutputSelection, DeployedBytecodeOutputSelection,
+            EvmOutputSelection, EwasmOutputSelection,
+        },
+        Ast, ConfigurableContractArtifact, GasEstimates, Offsets, StorageLayout,
+    },
+    info::ContractInfo,
+    utils::canonicalize,
+};
+use foundry_config::filter::GlobMatcher;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+mod abi;
+mod constructor;
+mod diff;
+mod libraries;
+mod mock;
+mod opcodes;
+
+/// CLI arguments for `forge inspect`.
+#[derive(Clone, Debug, Parser)]
+pub struct InspectArgs {
+    /// The identifier of the contract to inspect in the form `(<path>:)?<contractname>`.
+    ///
+    /// The path may be a glob (e.g. `src/tokens/*.sol`) to inspect all the contracts in the
+    /// matching files, in which case the contract name may be a glob as well. If more than one
+    /// contract matches, the fields are printed as JSON, keyed by fully qualified contract name.
+    pub contract: ContractInfo,
+
+    /// The contract artifact field to inspect.
+    #[arg(value_enum, required_unless_present = "fields", conflicts_with = "fields")]
+    pub field: Option<ContractArtifactField>,
+
+    /// Inspect several fields at once, e.g. `--field abi --field storage-layout`.
+    ///
+    /// The fields are printed as a single JSON object, keyed by field name.
+    #[arg(
+        long = "field",
+        value_enum,
+        value_name = "FIELD",
+        conflicts_with_all = ["diff", "disassemble", "args"]
+    )]
+    pub fields: Vec<ContractArtifactField>,
+
+    /// Pretty print the selected field, if supported.
+    #[arg(long)]
+    pub pretty: bool,
+
+    /// The format of the `abi` field.
+    ///
+    /// `human` prints one signature per line for the constructor, functions, events and errors.
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = AbiFormat::Json,
+        value_name = "FORMAT",
+        conflicts_with_all = ["pretty", "fields", "diff"]
+    )]
+    pub abi_format: AbiFormat,
+
+    /// Read the field from a prebuilt artifact JSON file instead of compiling the project.
+    ///
+    /// Takes precedence over resolving the contract from the project sources.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub artifact: Option<PathBuf>,
+
+    /// Diff the field against the contract compiled at the given git revision.
+    ///
+    /// Only supported for the `abi` and `storage-layout` fields.
+    #[arg(long, value_name = "GIT_REF", conflicts_with = "artifact")]
+    pub diff: Option<String>,
+
+    /// Print the full disassembly of the runtime bytecode instead of the opcode histogram.
+    ///
+    /// Only supported for the `opcodes` field.
+    #[arg(long, conflicts_with = "diff")]
+    pub disassemble: bool,
+
+    /// The constructor arguments to ABI encode.
+    ///
+    /// Only supported for the `constructor-args` field.
+    #[arg(long, num_args(1..), value_name = "ARGS", allow_hyphen_values = true)]
+    pub args: Vec<String>,
+
+    /// All build arguments are supported
+    #[command(flatten)]
+    build: CoreBuildArgs,
+}
+
+impl InspectArgs {
+    pub fn run(self) -> Result<()> {
+        let Self {
+            contract,
+            field,
+            fields,
+            build,
+            pretty,
+            abi_format,
+            artifact,
+            diff,
+            disassemble,
+            args,
+        } = self;
+
+        trace!(
+            target: "forge",
+            ?field, ?fields, ?contract, ?artifact, ?diff,
+            "running forge inspect"
+        );
+
+        let Some(field) = field else {
+            if fields.contains(&ContractArtifactField::ConstructorArgs) {
+                eyre::bail!("the `constructor-args` field cannot be combined with other fields");
+            }
+            if fields.contains(&ContractArtifactField::Libraries) {
+                eyre::bail!("the `libraries` field cannot be combined with other fields");
+            }
+            let artifact = if let Some(path) = &artifact {
+                fs::read_json_file::<ConfigurableContractArtifact>(path)?
+            } else if let Some((path_glob, name_glob)) = globs(&contract) {
+                return inspect_matching(&path_glob, &name_glob, &fields, true, build, pretty, false)
+            } else {
+                compile_artifact(&contract, &fields, build)?
+            };
+            return print_json(&fields_json(&artifact, &contract.name, &fields)?)
+        };
+
+        if disassemble && field != ContractArtifactField::Opcodes {
+            eyre::bail!("`--disassemble` is only supported for the `opcodes` field");
+        }
+        if !args.is_empty() && field != ContractArtifactField::ConstructorArgs {
+            eyre::bail!("`--args` is only supported for the `constructor-args` field");
+        }
+        if field == ContractArtifactField::ConstructorArgs && globs(&contract).is_some() {
+            eyre::bail!("the `constructor-args` field requires a single contract");
+        }
+        if field == ContractArtifactField::Libraries {
+            if globs(&contract).is_some() {
+                eyre::bail!("the `libraries` field requires a single contract");
+            }
+            if artifact.is_some() || diff.is_some() {
+                eyre::bail!("the `libraries` field requires compiling the project");
+            }
+        }
+        if abi_format == AbiFormat::Human {
+            if field != ContractArtifactField::Abi {
+                eyre::bail!("`--abi-format` is only supported for the `abi` field");
+            }
+            if globs(&contract).is_some() {
+                eyre::bail!("`--abi-format human` requires a single contract");
+            }
+        }
+
+        if let Some(git_ref) = &diff {
+            return diff::run(&contract, field, build, git_ref, pretty)
+        }
+
+        if field == ContractArtifactField::Libraries {
+            for library in libraries::deploy_order(&contract, build_args(&[field], build))? {
+                println!("{library}");
+            }
+            return Ok(())
+        }
+
+        let artifact = if let Some(path) = &artifact {
+            fs::read_json_file::<ConfigurableContractArtifact>(path)?
+        } else if let Some((path_glob, name_glob)) = globs(&contract) {
+            return inspect_matching(
+                &path_glob,
+                &name_glob,
+                &[field],
+                false,
+                build,
+                pretty,
+                disassemble,
+            )
+        } else {
+            compile_artifact(&contract, &[field], build)?
+        };
+
+        if field == ContractArtifactField::ConstructorArgs {
+            let abi = artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch ABI"))?;
+            let encoded = constructor::encode_constructor_args(abi, &contract.name, &args)?;
+            println!("{}", hex::encode_prefixed(encoded));
+            return Ok(())
+        }
+
+        if abi_format == AbiFormat::Human {
+            let abi = artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch ABI"))?;
+            for signature in human_readable_abi(abi) {
+                println!("{signature}");
+            }
+            return Ok(())
+        }
+
+        print_field(&artifact, &contract.name, field, pretty, disassemble)
+    }
+}
+
+/// Returns the path and contract name globs if the contract identifier contains glob patterns.
+///
+/// An identifier without a contract name matches all the contracts of the matching files.
+fn globs(contract: &ContractInfo) -> Option<(String, String)> {
+    let is_glob = |s: &str| s.contains(['*', '?', '[', '{']);
+    match &contract.path {
+        Some(path) if is_glob(path) || is_glob(&contract.name) => {
+            Some((path.clone(), contract.name.clone()))
+        }
+        None if is_glob(&contract.name) => Some((contract.name.clone(), "*".to_string())),
+        _ => None,
+    }
+}
+
+/// Compiles the project and prints `fields` for all the contracts matching the globs.
+///
+/// Unless `combined`, a single field is expected and printed as is.
+fn inspect_matching(
+    path_glob: &str,
+    name_glob: &str,
+    fields: &[ContractArtifactField],
+    combined: bool,
+    build: CoreBuildArgs,
+    pretty: bool,
+    disassemble: bool,
+) -> Result<()> {
+    let path_matcher: GlobMatcher = path_glob.parse()?;
+    let name_matcher: GlobMatcher = name_glob.parse()?;
+
+    let project = build_args(fields, build).project()?;
+    let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+
+    let mut matches = BTreeMap::new();
+    for (id, artifact) in output.artifact_ids() {
+        let source = id.source.strip_prefix(project.root()).unwrap_or(&id.source);
+        if path_matcher.is_match(source) && name_matcher.matcher.is_match(&id.name) {
+            matches.insert(format!("{}:{}", source.display(), id.name), (id.name, artifact));
+        }
+    }
+
+    match matches.len() {
+        0 => eyre::bail!("No contracts matching `{path_glob}:{name_glob}` were found"),
+        1 => {
+            let (name, artifact) = matches.into_values().next().unwrap();
+            if combined {
+                print_json(&fields_json(artifact, &name, fields)?)
+            } else {
+                print_field(artifact, &name, fields[0], pretty, disassemble)
+            }
+        }
+        _ => {
+            let mut out = serde_json::Map::new();
+            for (id, (name, artifact)) in matches {
+                let value = if combined {
+                    fields_json(artifact, &name, fields)?
+                } else {
+                    field_json(artifact, &name, fields[0])?
+                };
+                out.insert(id, value);
+            }
+            print_json(&out)
+        }
+    }
+}
+
+/// Compiles the project with the output selection required by `fields` and returns the artifact
+/// of `contract`.
+fn compile_artifact(
+    contract: &ContractInfo,
+    fields: &[ContractArtifactField],
+    build: CoreBuildArgs,
+) -> Result<ConfigurableContractArtifact> {
+    let mut contract = contract.clone();
+
+    // Build the project
+    let project = build_args(fields, build).project()?;
+    let mut compiler = ProjectCompiler::new().quiet(true);
+    if let Some(contract_path) = &mut contract.path {
+        let target_path = canonicalize(&*contract_path)?;
+        *contract_path = target_path.to_string_lossy().to_string();
+        compiler = compiler.files([target_path]);
+    }
+    let output = compiler.compile(&project)?;
+
+    // Find the artifact
+    let artifact = output.find_contract(&contract).ok_or_else(|| {
+        eyre::eyre!("Could not find artifact `{contract}` in the compiled artifacts")
+    })?;
+
+    Ok(artifact.clone())
+}
+
+/// Returns the build arguments with the output selection required by `fields`.
+fn build_args(fields: &[ContractArtifactField], build: CoreBuildArgs) -> CoreBuildArgs {
+    // Map fields to ContractOutputSelection
+    let mut cos = build.compiler.extra_output;
+    for &field in fields {
+        if let Some(selection) = field.output_selection() {
+            if !field.is_default() && !cos.iter().any(|selected| field == *selected) {
+                cos.push(selection);
+            }
+        }
+        // The gas of the methods is joined with their selectors
+        if field == ContractArtifactField::MethodGas &&
+            !cos.iter().any(|selected| ContractArtifactField::MethodIdentifiers == *selected)
+        {
+            cos.push(ContractOutputSelection::Evm(EvmOutputSelection::MethodIdentifiers));
+        }
+    }
+
+    // The AST is a source level output, not a contract level one
+    let ast = fields.contains(&ContractArtifactField::Ast) || build.compiler.ast;
+
+    // Run Optimized?
+    let optimized = if fields.contains(&ContractArtifactField::AssemblyOptimized) {
+        true
+    } else {
+        build.compiler.optimize
+    };
+
+    // Build modified Args
+    CoreBuildArgs {
+        compiler: CompilerArgs { extra_output: cos, optimize: optimized, ast, ..build.compiler },
+        ..build
+    }
+}
+
+/// Prints the given `field` of the artifact.
+fn print_field(
+    artifact: &ConfigurableContractArtifact,
+    contract_name: &str,
+    field: ContractArtifactField,
+    pretty: bool,
+    disassemble: bool,
+) -> Result<()> {
+    // Match on ContractArtifactFields and pretty-print
+    match field {
+        ContractArtifactField::Abi => {
+            let abi =
+                artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch lossless ABI"))?;
+            if pretty {
+                let source = foundry_cli::utils::abi_to_solidity(abi, contract_name)?;
+                println!("{source}");
+            } else {
+                print_json(abi)?;
+            }
+        }
+        ContractArtifactField::Bytecode => {
+            print_json_str(&artifact.bytecode, Some("object"))?;
+        }
+        ContractArtifactField::DeployedBytecode => {
+            print_json_str(&artifact.deployed_bytecode, Some("object"))?;
+        }
+        ContractArtifactField::ImmutableReferences => {
+            print_immutable_references(immutable_references(artifact), pretty)?;
+        }
+        ContractArtifactField::LinkReferences => {
+            print_link_references(link_references(artifact), pretty)?;
+        }
+        ContractArtifactField::DeployedLinkReferences => {
+            print_link_references(deployed_link_references(artifact), pretty)?;
+        }
+        ContractArtifactField::Assembly | ContractArtifactField::AssemblyOptimized => {
+            print_json_str(&artifact.assembly, None)?;
+        }
+        ContractArtifactField::MethodIdentifiers => {
+            print_json(&artifact.method_identifiers)?;
+        }
+        ContractArtifactField::GasEstimates => {
+            print_gas_estimates(artifact.gas_estimates.as_ref(), pretty)?;
+        }
+        ContractArtifactField::MethodGas => {
+            print_method_gas(artifact, pretty)?;
+        }
+        ContractArtifactField::StorageLayout => {
+            print_storage_layout(artifact.storage_layout.as_ref(), pretty)?;
+        }
+        ContractArtifactField::DevDoc => {
+            print_json(&artifact.devdoc)?;
+        }
+        ContractArtifactField::Ir => {
+            print_yul(artifact.ir.as_deref(), pretty)?;
+        }
+        ContractArtifactField::IrOptimized => {
+            print_yul(artifact.ir_optimized.as_deref(), pretty)?;
+        }
+        ContractArtifactField::Metadata => {
+            print_json(&artifact.metadata)?;
+        }
+        ContractArtifactField::UserDoc => {
+            print_json(&artifact.userdoc)?;
+        }
+        ContractArtifactField::Ewasm => {
+            print_json_str(&artifact.ewasm, None)?;
+        }
+        ContractArtifactField::Errors => {
+            print_json(&errors(artifact))?;
+        }
+        ContractArtifactField::Events => {
+            print_json(&events(artifact))?;
+        }
+        ContractArtifactField::Ast => {
+            print_ast(artifact.ast.as_ref(), pretty)?;
+        }
+        ContractArtifactField::Opcodes => {
+            opcodes::print_opcodes(artifact, pretty, disassemble)?;
+        }
+        ContractArtifactField::Mock => {
+            let abi = artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch ABI"))?;
+            print!("{}", mock::mock_source(abi, contract_name));
+        }
+        ContractArtifactField::ConstructorArgs => {
+            eyre::bail!("the `constructor-args` field requires a single contract");
+        }
+        ContractArtifactField::Libraries => {
+            eyre::bail!("the `libraries` field requires a single contract");
+        }
+    };
+
+    Ok(())
+}
+
+/// Returns the given `field` of the artifact as JSON.
+fn field_json(
+    artifact: &ConfigurableContractArtifact,
+    contract_name: &str,
+    field: ContractArtifactField,
+) -> Result<serde_json::Value> {
+    type Caf = ContractArtifactField;
+    let value = match field {
+        Caf::Abi => serde_json::to_value(&artifact.abi)?,
+        Caf::Bytecode => get_json_value(&artifact.bytecode, "object")?,
+        Caf::DeployedBytecode => get_json_value(&artifact.deployed_bytecode, "object")?,
+        Caf::ImmutableReferences => serde_json::to_value(immutable_references(artifact))?,
+        Caf::LinkReferences => serde_json::to_value(link_references(artifact))?,
+        Caf::DeployedLinkReferences => serde_json::to_value(deployed_link_references(artifact))?,
+        Caf::Assembly | Caf::AssemblyOptimized => serde_json::to_value(&artifact.assembly)?,
+        Caf::MethodIdentifiers => serde_json::to_value(&artifact.method_identifiers)?,
+        Caf::GasEstimates => serde_json::to_value(&artifact.gas_estimates)?,
+        Caf::MethodGas => serde_json::to_value(method_gas(artifact)?)?,
+        Caf::StorageLayout => serde_json::to_value(&artifact.storage_layout)?,
+        Caf::DevDoc => serde_json::to_value(&artifact.devdoc)?,
+        Caf::Ir => serde_json::to_value(&artifact.ir)?,
+        Caf::IrOptimized => serde_json::to_value(&artifact.ir_optimized)?,
+        Caf::Metadata => serde_json::to_value(&artifact.metadata)?,
+        Caf::UserDoc => serde_json::to_value(&artifact.userdoc)?,
+        Caf::Ewasm => serde_json::to_value(&artifact.ewasm)?,
+        Caf::Errors => errors(artifact).into(),
+        Caf::Events => events(artifact).into(),
+        Caf::Ast => serde_json::to_value(&artifact.ast)?,
+        Caf::Opcodes => {
+            serde_json::to_value(opcodes::histogram(&opcodes::runtime_operations(artifact)?))?
+        }
+        Caf::Mock => {
+            let abi = artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch ABI"))?;
+            mock::mock_source(abi, contract_name).into()
+        }
+        Caf::ConstructorArgs => {
+            eyre::bail!("the `constructor-args` field requires a single contract")
+        }
+        Caf::Libraries => eyre::bail!("the `libraries` field requires a single contract"),
+    };
+    Ok(value)
+}
+
+/// Returns the given `fields` of the artifact as a JSON object, keyed by field name.
+fn fields_json(
+    artifact: &ConfigurableContractArtifact,
+    contract_name: &str,
+    fields: &[ContractArtifactField],
+) -> Result<serde_json::Value> {
+    let mut out = serde_json::Map::new();
+    for &field in fields {
+        out.insert(field.to_string(), field_json(artifact, contract_name, field)?);
+    }
+    Ok(out.into())
+}
+
+/// Returns the selectors of all the errors, keyed by signature.
+fn errors(artifact: &ConfigurableContractArtifact) -> serde_json::Map<String, serde_json::Value> {
+    let mut out = serde_json::Map::new();
+    if let Some(abi) = &artifact.abi {
+        for er in abi.errors.iter().flat_map(|(_, errors)| errors) {
+            let types = er.inputs.iter().map(|p| p.ty.clone()).collect::<Vec<_>>();
+            let sig = format!("{:x}", er.selector());
+            let sig_trimmed = &sig[0..8];
+            out.insert(format!("{}({})", er.name, types.join(",")), sig_trimmed.to_string().into());
+        }
+    }
+    out
+}
+
+/// Returns the topics of all the events including anonymous ones, keyed by signature.
+fn events(artifact: &ConfigurableContractArtifact) -> serde_json::Map<String, serde_json::Value> {
+    let mut out = serde_json::Map::new();
+    if let Some(abi) = &artifact.abi {
+        for ev in abi.events.iter().flat_map(|(_, events)| events) {
+            let types = ev.inputs.iter().map(|p| p.ty.clone()).collect::<Vec<_>>();
+            out.insert(
+                format!("{}({})", ev.name, types.join(",")),
+                format!("{:?}", ev.signature()).into(),
+            );
+        }
+    }
+    out
+}
+
+pub fn print_storage_layout(storage_layout: Option<&StorageLayout>, pretty: bool) -> Result<()> {
+    let Some(storage_layout) = storage_layout else {
+        eyre::bail!("Could not get storage layout");
+    };
+
+    if !pretty {
+        return print_json(&storage_layout)
+    }
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["Name", "Type", "Slot", "Offset", "Bytes", "Contract"]);
+
+    for slot in &storage_layout.storage {
+        let storage_type = storage_layout.types.get(&slot.storage_type);
+        table.add_row([
+            slot.label.as_str(),
+            storage_type.map_or("?", |t| &t.label),
+            &slot.slot,
+            &slot.offset.to_string(),
+            storage_type.map_or("?", |t| &t.number_of_bytes),
+            &slot.contract,
+        ]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Returns the offsets of the immutables in the deployed bytecode, keyed by the AST id of their
+/// declaration.
+fn immutable_references(
+    artifact: &ConfigurableContractArtifact,
+) -> Option<&BTreeMap<String, Vec<Offsets>>> {
+    artifact.deployed_bytecode.as_ref().map(|bytecode| &bytecode.immutable_references)
+}
+
+/// Prints the immutable references emitted by solc.
+///
+/// An immutable is referenced at every offset its value is inserted at during deployment, an
+/// immutable that is never read has no references.
+pub fn print_immutable_references(
+    references: Option<&BTreeMap<String, Vec<Offsets>>>,
+    pretty: bool,
+) -> Result<()> {
+    let Some(references) = references else {
+        eyre::bail!("Could not get immutable references");
+    };
+
+    if !pretty {
+        return print_json(&references)
+    }
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["AST id", "Start", "Length"]);
+    for (id, offsets) in references {
+        for offsets in offsets {
+            table.add_row([id.clone(), offsets.start.to_string(), offsets.length.to_string()]);
+        }
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// The offsets of the library placeholders in a bytecode, keyed by source file and library name.
+type LinkReferences = BTreeMap<String, BTreeMap<String, Vec<Offsets>>>;
+
+/// Returns the library placeholders of the creation bytecode.
+fn link_references(artifact: &ConfigurableContractArtifact) -> Option<&LinkReferences> {
+    artifact.bytecode.as_ref().map(|bytecode| &bytecode.link_references)
+}
+
+/// Returns the library placeholders of the deployed bytecode.
+fn deployed_link_references(artifact: &ConfigurableContractArtifact) -> Option<&LinkReferences> {
+    artifact
+        .deployed_bytecode
+        .as_ref()
+        .and_then(|deployed| deployed.bytecode.as_ref())
+        .map(|bytecode| &bytecode.link_references)
+}
+
+/// Prints the link references emitted by solc.
+///
+/// Every placeholder of a library must be replaced with its address before deploying the
+/// contract, the libraries without references do not need to be linked.
+pub fn print_link_references(references: Option<&LinkReferences>, pretty: bool) -> Result<()> {
+    let Some(references) = references else {
+        eyre::bail!("Could not get link references");
+    };
+
+    if !pretty {
+        return print_json(&references)
+    }
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["Library", "Start", "Length"]);
+    for (file, libraries) in references {
+        for (library, offsets) in libraries {
+            for offsets in offsets {
+                table.add_row([
+                    format!("{file}:{library}"),
+                    offsets.start.to_string(),
+                    offsets.length.to_string(),
+                ]);
+            }
+        }
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Prints the gas estimates emitted by solc.
+///
+/// Unbounded estimates are reported by solc as `infinite` and are kept as such, both in the JSON
+/// output and in the pretty-printed table.
+pub fn print_gas_estimates(gas_estimates: Option<&GasEstimates>, pretty: bool) -> Result<()> {
+    let Some(gas_estimates) = gas_estimates else {
+        eyre::bail!("Could not get gas estimates");
+    };
+
+    if !pretty {
+        return print_json(&gas_estimates)
+    }
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["Kind", "Name", "Gas"]);
+
+    let creation = &gas_estimates.creation;
+    table.add_row(["creation", "codeDepositCost", &creation.code_deposit_cost]);
+    table.add_row(["creation", "executionCost", &creation.execution_cost]);
+    table.add_row(["creation", "totalCost", &creation.total_cost]);
+    for (name, gas) in &gas_estimates.external {
+        table.add_row(["external", name, gas]);
+    }
+    for (name, gas) in &gas_estimates.internal {
+        table.add_row(["internal", name, gas]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// The gas estimate of an external function, see [`method_gas`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+struct MethodGas<'a> {
+    signature: &'a str,
+    gas: &'a str,
+}
+
+/// Returns the external gas estimates of the functions, keyed by selector.
+///
+/// Unlike the gas estimates emitted by solc, keyed by signature, this distinguishes overloaded
+/// functions at a glance.
+fn method_gas(artifact: &ConfigurableContractArtifact) -> Result<BTreeMap<&str, MethodGas<'_>>> {
+    let Some(method_identifiers) = &artifact.method_identifiers else {
+        eyre::bail!("Could not get method identifiers");
+    };
+    let Some(gas_estimates) = &artifact.gas_estimates else {
+        eyre::bail!("Could not get gas estimates");
+    };
+
+    Ok(method_identifiers
+        .iter()
+        .filter_map(|(signature, selector)| {
+            let gas = gas_estimates.external.get(signature)?;
+            Some((selector.as_str(), MethodGas { signature, gas }))
+        })
+        .collect())
+}
+
+/// Prints the external gas estimates of the functions, keyed by selector.
+fn print_method_gas(artifact: &ConfigurableContractArtifact, pretty: bool) -> Result<()> {
+    let method_gas = method_gas(artifact)?;
+    if !pretty {
+        return print_json(&method_gas)
+    }
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["Selector", "Signature", "Gas"]);
+    for (selector, MethodGas { signature, gas }) in method_gas {
+        table.add_row([selector, signature, gas]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Prints the AST of the contract's source file.
+///
+/// ASTs of large files can be big, so unless pretty-printed they are streamed to stdout as compact
+/// JSON instead of being buffered.
+fn print_ast(ast: Option<&Ast>, pretty: bool) -> Result<()> {
+    let Some(ast) = ast else {
+        eyre::bail!("Could not get AST");
+    };
+
+    if pretty {
+        return print_json(ast)
+    }
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    serde_json::to_writer(&mut out, ast)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Contract level output selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContractArtifactField {
+    Abi,
+    Bytecode,
+    DeployedBytecode,
+    ImmutableReferences,
+    LinkReferences,
+    DeployedLinkReferences,
+    Assembly,
+    AssemblyOptimized,
+    MethodIdentifiers,
+    GasEstimates,
+    MethodGas,
+    StorageLayout,
+    DevDoc,
+    Ir,
+    IrOptimized,
+    Metadata,
+    UserDoc,
+    Ewasm,
+    Errors,
+    Events,
+    Ast,
+    Opcodes,
+    Mock,
+    ConstructorArgs,
+    Libraries,
+}
+
+macro_rules! impl_value_enum {
+    (enum $name:ident { $($field:ident => $main:literal $(| $alias:literal)*),+ $(,)? }) => {
+        impl $name {
+            /// All the variants of this enum.
+            pub const ALL: &'static [Self] = &[$(Self::$field),+];
+
+            /// Returns the string representation of `self`.
+            #[inline]
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$field => $main,
+                    )+
+                }
+            }
+
+            /// Returns all the aliases of `self`.
+            #[inline]
+            pub const fn aliases(&self) -> &'static [&'static str] {
+                match self {
+                    $(
+                        Self::$field => &[$($alias),*],
+                    )+
+                }
+            }
+        }
+
+        impl ::clap::ValueEnum for $name {
+            #[inline]
+            fn value_variants<'a>() -> &'a [Self] {
+                Self::ALL
+            }
+
+            #[inline]
+            fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+                Some(::clap::builder::PossibleValue::new(Self::as_str(self)).aliases(Self::aliases(self)))
+            }
+
+            #[inline]
+            fn from_str(input: &str, ignore_case: bool) -> Result<Self, String> {
+                let _ = ignore_case;
+                <Self as ::std::str::FromStr>::from_str(input)
+            }
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(
+                        $main $(| $alias)* => Ok(Self::$field),
+                    )+
+                    _ => Err(format!(concat!("Invalid ", stringify!($name), " value: {}"), s)),
+                }
+            }
+        }
+    };
+}
+
+impl_value_enum! {
+    enum ContractArtifactField {
+        Abi               => "abi",
+        Bytecode          => "bytecode" | "bytes" | "b",
+        DeployedBytecode  => "deployedBytecode" | "deployed_bytecode" | "deployed-bytecode"
+                             | "deployed" | "deployedbytecode",
+        ImmutableReferences => "immutableReferences" | "immutable_references"
+                             | "immutable-references" | "immutablereferences" | "immutables",
+        LinkReferences    => "linkReferences" | "link_references" | "link-references"
+                             | "linkreferences" | "links",
+        DeployedLinkReferences => "deployedLinkReferences" | "deployed_link_references"
+                             | "deployed-link-references" | "deployedlinkreferences"
+                             | "deployed-links",
+        Assembly          => "assembly" | "asm",
+        AssemblyOptimized => "assemblyOptimized" | "asmOptimized" | "assemblyoptimized"
+                             | "assembly_optimized" | "asmopt" | "assembly-optimized"
+                             | "asmo" | "asm-optimized" | "asmoptimized" | "asm_optimized",
+        MethodIdentifiers => "methodIdentifiers" | "methodidentifiers" | "methods"
+                             | "method_identifiers" | "method-identifiers" | "mi",
+        GasEstimates      => "gasEstimates" | "gas" | "gas_estimates" | "gas-estimates"
+                             | "gasestimates",
+        MethodGas         => "methodGas" | "method_gas" | "method-gas" | "methodgas",
+        StorageLayout     => "storageLayout" | "storage_layout" | "storage-layout"
+                             | "storagelayout" | "storage",
+        DevDoc            => "devdoc" | "dev-doc" | "devDoc",
+        Ir                => "ir" | "iR" | "IR",
+        IrOptimized       => "irOptimized" | "ir-optimized" | "iroptimized" | "iro" | "iropt",
+        Metadata          => "metadata" | "meta",
+        UserDoc           => "userdoc" | "userDoc" | "user-doc",
+        Ewasm             => "ewasm" | "e-wasm",
+        Errors            => "errors" | "er",
+        Events            => "events" | "ev",
+        Ast               => "ast" | "AST",
+        Opcodes           => "opcodes" | "ops",
+        Mock              => "mock",
+        ConstructorArgs   => "constructorArgs" | "constructor_args" | "constructor-args"
+                             | "constructorargs" | "args",
+        Libraries         => "libraries" | "libs",
+    }
+}
+
+impl ContractArtifactField {
+    /// Returns true if this field is generated by default.
+    pub const fn is_default(&self) -> bool {
+        matches!(
+            self,
+            Self::Bytecode |
+                Self::DeployedBytecode |
+                Self::ImmutableReferences |
+                Self::LinkReferences |
+                Self::DeployedLinkReferences |
+                Self::Opcodes |
+                Self::Libraries
+        )
+    }
+
+    /// Returns the contract level output selection of this field, if it has one.
+    pub fn output_selection(&self) -> Option<ContractOutputSelection> {
+        type Cos = ContractOutputSelection;
+        let selection = match self {
+            Self::Abi => Cos::Abi,
+            Self::Bytecode => Cos::Evm(EvmOutputSelection::ByteCode(BytecodeOutputSelection::All)),
+            Self::DeployedBytecode | Self::Opcodes => {
+                Cos::Evm(EvmOutputSelection::DeployedByteCode(DeployedBytecodeOutputSelection::All))
+            }
+            Self::ImmutableReferences => Cos::Evm(EvmOutputSelection::DeployedByteCode(
+                DeployedBytecodeOutputSelection::ImmutableReferences,
+            )),
+            Self::LinkReferences | Self::Libraries => {
+                Cos::Evm(EvmOutputSelection::ByteCode(BytecodeOutputSelection::LinkReferences))
+            }
+            Self::DeployedLinkReferences => Cos::Evm(EvmOutputSelection::DeployedByteCode(
+                DeployedBytecodeOutputSelection::LinkReferences,
+            )),
+            Self::Assembly | Self::AssemblyOptimized => Cos::Evm(EvmOutputSelection::Assembly),
+            Self::MethodIdentifiers => Cos::Evm(EvmOutputSelection::MethodIdentifiers),
+            Self::GasEstimates | Self::MethodGas => Cos::Evm(EvmOutputSelection::GasEstimates),
+            Self::StorageLayout => Cos::StorageLayout,
+            Self::DevDoc => Cos::DevDoc,
+            Self::Ir => Cos::Ir,
+            Self::IrOptimized => Cos::IrOptimized,
+            Self::Metadata => Cos::Metadata,
+            Self::UserDoc => Cos::UserDoc,
+            Self::Ewasm => Cos::Ewasm(EwasmOutputSelection::All),
+            Self::Errors => Cos::Abi,
+            Self::Events => Cos::Abi,
+            Self::Mock | Self::ConstructorArgs => Cos::Abi,
+            Self::Ast => return None,
+        };
+        Some(selection)
+    }
+}
+
+impl PartialEq<ContractOutputSelection> for ContractArtifactField {
+    fn eq(&self, other: &ContractOutputSelection) -> bool {
+        type Cos = ContractOutputSelection;
+        type Eos = EvmOutputSelection;
+        matches!(
+            (self, other),
+            (Self::Abi | Self::Events, Cos::Abi) |
+                (Self::Errors | Self::Mock | Self::ConstructorArgs, Cos::Abi) |
+                (Self::Bytecode, Cos::Evm(Eos::ByteCode(_))) |
+                (Self::DeployedBytecode | Self::Opcodes, Cos::Evm(Eos::DeployedByteCode(_))) |
+                (
+                    Self::LinkReferences | Self::Libraries,
+                    Cos::Evm(Eos::ByteCode(
+                        BytecodeOutputSelection::All | BytecodeOutputSelection::LinkReferences
+                    ))
+                ) |
+                (
+                    Self::DeployedLinkReferences,
+                    Cos::Evm(Eos::DeployedByteCode(
+                        DeployedBytecodeOutputSelection::All |
+                            DeployedBytecodeOutputSelection::LinkReferences
+                    ))
+                ) |
+                (
+                    Self::ImmutableReferences,
+                    Cos::Evm(Eos::DeployedByteCode(
+                        DeployedBytecodeOutputSelection::All |
+                            DeployedBytecodeOutputSelection::ImmutableReferences
+                    ))
+                ) |
+                (Self::Assembly | Self::AssemblyOptimized, Cos::Evm(Eos::Assembly)) |
+                (Self::MethodIdentifiers, Cos::Evm(Eos::MethodIdentifiers)) |
+                (Self::GasEstimates | Self::MethodGas, Cos::Evm(Eos::GasEstimates)) |
+                (Self::StorageLayout, Cos::StorageLayout) |
+                (Self::DevDoc, Cos::DevDoc) |
+                (Self::Ir, Cos::Ir) |
+                (Self::IrOptimized, Cos::IrOptimized) |
+                (Self::Metadata, Cos::Metadata) |
+                (Self::UserDoc, Cos::UserDoc) |
+                (Self::Ewasm, Cos::Ewasm(_))
+        )
+    }
+}
+
+impl fmt::Display for ContractArtifactField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+fn print_json(obj: &impl serde::Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(obj)?);
+    Ok(())
+}
+
+fn print_json_str(obj: &impl serde::Serialize, key: Option<&str>) -> Result<()> {
+    println!("{}", get_json_str(obj, key)?);
+    Ok(())
+}
+
+fn print_yul(yul: Option<&str>, pretty: bool) -> Result<()> {
+    let Some(yul) = yul else {
+        eyre::bail!("Could not get IR output");
+    };
+
+    static YUL_COMMENTS: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(///.*\n\s*)|(\s*/\*\*.*\*/)").unwrap());
+
+    if pretty {
+        println!("{}", YUL_COMMENTS.replace_all(yul, ""));
+    } else {
+        println!("{yul}");
+    }
+
+    Ok(())
+}
+
+/// Returns the value of `key` in the serialized `obj`, or `obj` itself if it has no such key.
+fn get_json_value(obj: &impl serde::Serialize, key: &str) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(obj)?;
+    if let Some(inner) = value.get_mut(key) {
+        value = inner.take();
+    }
+    Ok(value)
+}
+
+fn get_json_str(obj: &impl serde::Serialize, key: Option<&str>) -> Result<String> {
+    let value = serde_json::to_value(obj)?;
+    let mut value_ref = &value;
+    if let Some(key) = key {
+        if let Some(value2) = value.get(key) {
+            value_ref = value2;
+        }
+    }
+    let s = match value_ref.as_str() {
+        Some(s) => s.to_string(),
+        None => format!("{value_ref:#}"),
+    };
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contract_globs() {
+        let globs = |s: &str| globs(&s.parse().unwrap());
+        assert_eq!(globs("Counter"), None);
+        assert_eq!(globs("src/Counter.sol:Counter"), None);
+        assert_eq!(globs("src/tokens/*.sol"), Some(("src/tokens/*.sol".into(), "*".into())));
+        assert_eq!(globs("src/**/*.sol:*Token"), Some(("src/**/*.sol".into(), "*Token".into())));
+    }
+
+    #[test]
+    fn contract_output_selection() {
+        for &field in ContractArtifactField::ALL {
+            if let Some(selection) = field.output_selection() {
+                assert_eq!(field, selection);
+            }
+
+            let s = field.as_str();
+            assert_eq!(s, field.to_string());
+            assert_eq!(s.parse::<ContractArtifactField>().unwrap(), field);
+            for alias in field.aliases() {
+                assert_eq!(alias.parse::<ContractArtifactField>().unwrap(), field);
+            }
+        }
+    }
+}
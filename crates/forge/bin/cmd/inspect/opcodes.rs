@@ -0,0 +1,113 @@
+//! Opcode histogram and disassembly of the runtime bytecode.
+
+use alloy_primitives::hex;
+use comfy_table::{presets::ASCII_MARKDOWN, Table};
+use evm_disassembler::{disassemble_bytes, format_operations, types::Operation};
+use eyre::Result;
+use foundry_compilers::{
+    artifacts::{BytecodeObject, ConfigurableContractArtifact},
+    Artifact,
+};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Disassembles the runtime bytecode of the artifact, without its trailing CBOR metadata.
+///
+/// Library placeholders of unlinked bytecode are replaced with the zero address, which has the same
+/// length, so the offsets of the operations are not affected.
+pub fn runtime_operations(artifact: &ConfigurableContractArtifact) -> Result<Vec<Operation>> {
+    let Some(object) = artifact.get_deployed_bytecode_object() else {
+        eyre::bail!("Could not get deployed bytecode");
+    };
+
+    let code = match object.as_ref() {
+        BytecodeObject::Bytecode(bytes) => bytes.to_vec(),
+        BytecodeObject::Unlinked(unlinked) => {
+            static PLACEHOLDER: Lazy<Regex> =
+                Lazy::new(|| Regex::new(r"__\$[0-9a-fA-F]{34}\$__").unwrap());
+            let linked = PLACEHOLDER.replace_all(unlinked, "0".repeat(40));
+            hex::decode(linked.as_ref())?
+        }
+    };
+
+    Ok(disassemble_bytes(strip_metadata(&code).to_vec())?)
+}
+
+/// Returns the number of occurrences of every opcode.
+pub fn histogram(operations: &[Operation]) -> BTreeMap<String, usize> {
+    let mut histogram = BTreeMap::new();
+    for op in operations {
+        *histogram.entry(format!("{:?}", op.opcode)).or_default() += 1;
+    }
+    histogram
+}
+
+/// Prints the opcode histogram as JSON, or as a table sorted by count if `pretty`.
+///
+/// With `disassemble` the full disassembly is printed instead.
+pub fn print_opcodes(
+    artifact: &ConfigurableContractArtifact,
+    pretty: bool,
+    disassemble: bool,
+) -> Result<()> {
+    let operations = runtime_operations(artifact)?;
+    if disassemble {
+        print!("{}", format_operations(operations)?);
+        return Ok(())
+    }
+
+    let histogram = histogram(&operations);
+    if !pretty {
+        return super::print_json(&histogram)
+    }
+
+    let mut counts = histogram.into_iter().collect::<Vec<_>>();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+
+    let mut table = Table::new();
+    table.load_preset(ASCII_MARKDOWN);
+    table.set_header(["Opcode", "Count"]);
+    for (opcode, count) in counts {
+        table.add_row([opcode, count.to_string()]);
+    }
+
+    println!("{table}");
+    Ok(())
+}
+
+/// Strips the CBOR encoded metadata appended by solc, if any.
+///
+/// The metadata is not code, disassembling it would only add bogus operations.
+fn strip_metadata(code: &[u8]) -> &[u8] {
+    let [.., len_hi, len_lo] = code else { return code };
+    let len = u16::from_be_bytes([*len_hi, *len_lo]) as usize;
+    // The metadata is a CBOR map, followed by its length as two bytes
+    match code.len().checked_sub(len + 2) {
+        Some(start) if (0xa1..=0xa5).contains(&code[start]) => &code[..start],
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_strip_metadata() {
+        // PUSH1 0x80 PUSH1 0x40 MSTORE, followed by `{"solc": 0.8.23}`
+        let code = hex!("6080604052a164736f6c6343000817000a");
+        let code = strip_metadata(&code);
+        assert_eq!(code, hex!("6080604052"));
+        assert_eq!(strip_metadata(code), code);
+        assert_eq!(strip_metadata(&[]), &[] as &[u8]);
+    }
+
+    #[test]
+    fn push_immediates_are_skipped() {
+        // PUSH2 0x6001 contains a PUSH1 opcode in its immediate, followed by ADD
+        let operations = disassemble_bytes(hex!("61600101").to_vec()).unwrap();
+        let histogram = histogram(&operations);
+        assert_eq!(histogram, BTreeMap::from([("PUSH2".into(), 1), ("ADD".into(), 1)]));
+    }
+}
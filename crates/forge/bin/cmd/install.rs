@@ -1,3 +1,4 @@
+use super::deps::{dependency_manifest, print_manifest};
 use clap::{Parser, ValueHint};
 use eyre::{Context, Result};
 use foundry_cli::{
@@ -49,6 +50,12 @@ pub struct InstallArgs {
     #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
     pub root: Option<PathBuf>,
 
+    /// Print the manifest of the installed dependencies as JSON once done.
+    ///
+    /// Implies `--quiet`.
+    #[arg(long)]
+    json: bool,
+
     #[command(flatten)]
     opts: DependencyInstallOpts,
 }
@@ -58,7 +65,14 @@ impl_figment_convert_basic!(InstallArgs);
 impl InstallArgs {
     pub fn run(self) -> Result<()> {
         let mut config = self.try_load_config_emit_warnings()?;
-        self.opts.install(&mut config, self.dependencies)
+        let mut opts = self.opts;
+        opts.quiet |= self.json;
+        opts.install(&mut config, self.dependencies)?;
+        if self.json {
+            let deps = dependency_manifest(Git::from_config(&config))?;
+            print_manifest(&deps, true)?;
+        }
+        Ok(())
     }
 }
 
@@ -42,11 +42,13 @@
 pub mod bind;
 pub mod build;
 pub mod cache;
+pub mod clean;
 pub mod clone;
 pub mod config;
 pub mod coverage;
 pub mod create;
 pub mod debug;
+pub mod deps;
 pub mod doc;
 pub mod flatten;
 pub mod fmt;
@@ -63,5 +65,6 @@ pub mod snapshot;
 pub mod soldeer;
 pub mod test;
 pub mod tree;
+pub mod tweak;
 pub mod update;
 pub mod watch;
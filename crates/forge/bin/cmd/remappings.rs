@@ -1,8 +1,13 @@
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use foundry_cli::utils::LoadConfig;
-use foundry_config::impl_figment_convert_basic;
-use std::{collections::BTreeMap, path::PathBuf};
+use foundry_common::fs;
+use foundry_compilers::artifacts::remappings::Remapping;
+use foundry_config::{impl_figment_convert_basic, Config};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 /// CLI arguments for `forge remappings`.
 #[derive(Clone, Debug, Parser)]
@@ -14,8 +19,16 @@ pub struct RemappingArgs {
     #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
     root: Option<PathBuf>,
     /// Pretty-print the remappings, grouping each of them by context.
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["check", "write"])]
     pretty: bool,
+    /// Check that `remappings.txt` matches the auto-detected remappings.
+    ///
+    /// Prints the missing and extra entries and exits with an error if it does not.
+    #[arg(long, conflicts_with = "write")]
+    check: bool,
+    /// Write the auto-detected remappings to `remappings.txt`.
+    #[arg(long)]
+    write: bool,
 }
 impl_figment_convert_basic!(RemappingArgs);
 
@@ -23,6 +36,52 @@ impl RemappingArgs {
     pub fn run(self) -> Result<()> {
         let config = self.try_load_config_emit_warnings()?;
 
+        if self.check || self.write {
+            let expected = detect_remappings(&config);
+            let remappings_txt = config.root.0.join("remappings.txt");
+            if self.write {
+                fs::write(&remappings_txt, expected.join("\n"))?;
+                return Ok(());
+            }
+
+            let current = if remappings_txt.is_file() {
+                fs::read_to_string(&remappings_txt)?
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let diff = RemappingsDiff::new(&current, &expected);
+            if diff.is_empty() {
+                return Ok(());
+            }
+
+            if diff.reordered {
+                println!(
+                    "remappings.txt has the auto-detected remappings, but in a different order"
+                );
+            } else {
+                println!("remappings.txt differs from the auto-detected remappings:");
+                for remapping in &diff.extra {
+                    println!("- {remapping}");
+                }
+                for remapping in &diff.missing {
+                    println!("+ {remapping}");
+                }
+            }
+            let root = self
+                .root
+                .as_ref()
+                .map(|root| format!(" --root {}", root.display()))
+                .unwrap_or_default();
+            eyre::bail!(
+                "remappings.txt is out of date, run `forge remappings{root} --write` to fix it"
+            );
+        }
+
         if self.pretty {
             let mut groups = BTreeMap::<_, Vec<_>>::new();
             for remapping in config.remappings {
@@ -50,3 +109,71 @@ impl RemappingArgs {
         Ok(())
     }
 }
+
+/// Auto-detects the remappings of the project's libraries, relative to the project root and
+/// sorted, the same way `forge init` generates `remappings.txt`.
+fn detect_remappings(config: &Config) -> Vec<String> {
+    let root = &config.root.0;
+    config
+        .libs
+        .iter()
+        .flat_map(|lib| Remapping::find_many(&root.join(lib)))
+        .filter(|r| !["lib/", "src/", "contracts/"].contains(&r.name.as_str()))
+        .map(|r| r.into_relative(root).to_relative_remapping().to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// The difference between the remappings in `remappings.txt` and the expected ones.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RemappingsDiff {
+    /// The expected remappings that are not in the file.
+    missing: Vec<String>,
+    /// The remappings in the file that are not expected.
+    extra: Vec<String>,
+    /// Whether the file has exactly the expected remappings, but in a different order.
+    reordered: bool,
+}
+
+impl RemappingsDiff {
+    fn new(current: &[String], expected: &[String]) -> Self {
+        let missing = expected.iter().filter(|r| !current.contains(r)).cloned().collect::<Vec<_>>();
+        let extra = current.iter().filter(|r| !expected.contains(r)).cloned().collect::<Vec<_>>();
+        let reordered = missing.is_empty() && extra.is_empty() && current != expected;
+        Self { missing, extra, reordered }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && !self.reordered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(s: &[&str]) -> Vec<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn diffs_remappings() {
+        let expected = strings(&["a/=lib/a/src/", "b/=lib/b/src/"]);
+
+        assert!(RemappingsDiff::new(&expected, &expected).is_empty());
+
+        let diff = RemappingsDiff::new(&strings(&["b/=lib/b/src/", "a/=lib/a/src/"]), &expected);
+        assert_eq!(diff, RemappingsDiff { reordered: true, ..Default::default() });
+
+        let diff = RemappingsDiff::new(&strings(&["c/=lib/c/", "a/=lib/a/src/"]), &expected);
+        assert_eq!(
+            diff,
+            RemappingsDiff {
+                missing: strings(&["b/=lib/b/src/"]),
+                extra: strings(&["c/=lib/c/"]),
+                reordered: false,
+            }
+        );
+    }
+}
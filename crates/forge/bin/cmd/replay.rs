@@ -22,7 +22,7 @@ use foundry_evm::opts::EvmOpts;
 
 use clap::Parser;
 use foundry_config::{find_project_root_path, Config};
-use foundry_tweak::{build_tweaked_backend, ClonedProject};
+use foundry_tweak::{build_tweaked_backend, ClonedProject, TweakOptions};
 
 /// Replays an on-chain historical transaction locally on a fork of the blockchain with the on-chain
 /// contract tweaked by the current cloned project. In other words, `forge replay`:
@@ -97,7 +97,8 @@ impl ReplayArgs {
         let cloned_project = ClonedProject::load_with_root(&root)
             .map_err(|e| eyre!("failed to load the cloned project: {}", e))?;
         let tweaked_addr = cloned_project.metadata.address;
-        let tweaked_code = cloned_project.tweaked_code(&self.rpc, self.quick).await?;
+        let tweaked_code =
+            cloned_project.tweaked_code(&self.rpc, &TweakOptions::new(self.quick)).await?;
 
         let figment = Config::figment_with_root(&root).merge(&self.rpc);
         let evm_opts = figment.extract::<EvmOpts>()?;
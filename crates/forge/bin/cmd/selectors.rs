@@ -2,16 +2,21 @@ use alloy_primitives::hex;
 use clap::Parser;
 use comfy_table::Table;
 use eyre::Result;
+use forge::traces::identifier::SignaturesIdentifier;
 use foundry_cli::{
     opts::{CompilerArgs, CoreBuildArgs, ProjectPathsArgs},
     utils::FoundryPathExt,
 };
 use foundry_common::{
     compile::{compile_target, ProjectCompiler},
-    selectors::{import_selectors, SelectorImportData},
+    selectors::{decode_selector, import_selectors, SelectorImportData, SelectorType},
 };
 use foundry_compilers::{artifacts::output_selection::ContractOutputSelection, info::ContractInfo};
-use std::fs::canonicalize;
+use foundry_config::Config;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs::canonicalize,
+};
 
 /// CLI arguments for `forge selectors`.
 #[derive(Clone, Debug, Parser)]
@@ -56,6 +61,23 @@ pub enum SelectorsSubcommands {
         #[command(flatten)]
         project_paths: ProjectPathsArgs,
     },
+
+    /// Look up the signatures of a function selector, or of an event topic
+    ///
+    /// Queries the local signature cache and the signature database, and marks the signatures
+    /// matching a contract of the current project.
+    #[command(visible_alias = "lu")]
+    Lookup {
+        /// The 4-byte function selector, or the 32-byte event topic with `--event`.
+        selector: String,
+
+        /// Look up an event topic instead of a function selector.
+        #[arg(long)]
+        event: bool,
+
+        #[command(flatten)]
+        project_paths: ProjectPathsArgs,
+    },
 }
 
 impl SelectorsSubcommands {
@@ -260,7 +282,100 @@ impl SelectorsSubcommands {
                     }
                 }
             }
+            Self::Lookup { selector, event, project_paths } => {
+                lookup(&selector, event, project_paths).await?;
+            }
         }
         Ok(())
     }
 }
+
+/// Prints the candidate signatures of the function selector or event topic, along with the
+/// contracts of the project they match.
+async fn lookup(selector: &str, event: bool, project_paths: ProjectPathsArgs) -> Result<()> {
+    let (selector_type, len) =
+        if event { (SelectorType::Event, 32) } else { (SelectorType::Function, 4) };
+    let bytes = hex::decode(selector)?;
+    if bytes.len() != len {
+        eyre::bail!("expected a {len}-byte selector, got {} bytes", bytes.len());
+    }
+    let selector = hex::encode_prefixed(&bytes);
+
+    // Signatures from the local cache come first, then the ones from the database.
+    let identifier = SignaturesIdentifier::new(Config::foundry_cache_dir(), true)?;
+    let mut identifier = identifier.write().await;
+    let cached = if event {
+        identifier.identify_event(&bytes).await.map(|event| event.signature())
+    } else {
+        identifier.identify_function(&bytes).await.map(|func| func.signature())
+    };
+    let mut signatures = cached.into_iter().collect::<Vec<_>>();
+    match decode_selector(selector_type, &selector).await {
+        Ok(remote) => signatures.extend(remote),
+        Err(err) => warn!(?err, "failed to query the signature database"),
+    }
+
+    let matches = match project_signatures(&bytes, event, project_paths) {
+        Ok(matches) => matches,
+        Err(err) => {
+            warn!(?err, "failed to compile the project");
+            BTreeMap::new()
+        }
+    };
+    signatures.extend(matches.keys().cloned());
+
+    let mut seen = HashSet::new();
+    signatures.retain(|sig| seen.insert(sig.clone()));
+    if signatures.is_empty() {
+        eyre::bail!("No signatures found for {selector}");
+    }
+
+    for sig in signatures {
+        if let Some(contracts) = matches.get(&sig) {
+            println!("{sig} (matches {})", contracts.join(", "));
+        } else {
+            println!("{sig}");
+        }
+    }
+    Ok(())
+}
+
+/// Returns the signatures of the project contracts matching the selector or topic, along with the
+/// names of the contracts defining them.
+///
+/// Nothing matches if there is no project to compile.
+fn project_signatures(
+    bytes: &[u8],
+    event: bool,
+    project_paths: ProjectPathsArgs,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let build_args = CoreBuildArgs {
+        project_paths,
+        compiler: CompilerArgs {
+            extra_output: vec![ContractOutputSelection::Abi],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let project = build_args.project()?;
+    if !project.paths.has_input_files() {
+        return Ok(BTreeMap::new())
+    }
+    let output = ProjectCompiler::new().quiet(true).compile_with_paths(&project)?;
+    let mut matches = BTreeMap::<String, Vec<String>>::new();
+    for (id, artifact) in output.artifact_ids() {
+        let Some(abi) = &artifact.abi else { continue };
+        let sigs: Vec<_> = if event {
+            abi.events().filter(|e| e.selector()[..] == bytes[..]).map(|e| e.signature()).collect()
+        } else {
+            abi.functions()
+                .filter(|f| f.selector()[..] == bytes[..])
+                .map(|f| f.signature())
+                .collect()
+        };
+        for sig in sigs {
+            matches.entry(sig).or_default().push(id.name.clone());
+        }
+    }
+    Ok(matches)
+}
@@ -1,9 +1,10 @@
 use super::test;
 use alloy_primitives::U256;
-use clap::{builder::RangedU64ValueParser, Parser, ValueHint};
+use clap::{builder::RangedU64ValueParser, ArgAction, Parser, ValueEnum, ValueHint};
 use eyre::{Context, Result};
-use forge::result::{SuiteTestResult, TestKindReport, TestOutcome};
+use forge::result::{SuiteTestResult, TestKind, TestKindReport, TestOutcome};
 use foundry_cli::utils::STATIC_FUZZ_SEED;
+use foundry_config::InlineConfig;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::{
@@ -23,6 +24,9 @@ pub static RE_BASIC_SNAPSHOT_ENTRY: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?P<file>(.*?)):(?P<sig>(\w+)\s*\((.*?)\))\s*\(((gas:)?\s*(?P<gas>\d+)|(runs:\s*(?P<runs>\d+),\s*μ:\s*(?P<avg>\d+),\s*~:\s*(?P<med>\d+))|(runs:\s*(?P<invruns>\d+),\s*calls:\s*(?P<calls>\d+),\s*reverts:\s*(?P<reverts>\d+)))\)").unwrap()
 });
 
+/// The prefix of the section headers in a grouped snapshot file, e.g. `// Counter`.
+const SNAPSHOT_GROUP_PREFIX: &str = "// ";
+
 /// CLI arguments for `forge snapshot`.
 #[derive(Clone, Debug, Parser)]
 pub struct SnapshotArgs {
@@ -64,6 +68,19 @@ pub struct SnapshotArgs {
     )]
     snap: PathBuf,
 
+    /// Group the entries of the snapshot file into sections, each starting with a `// <group>`
+    /// header.
+    #[arg(long, value_enum, value_name = "GROUP")]
+    group_by: Option<GroupBy>,
+
+    /// Sort the entries of the snapshot file by test identifier, so the file does not depend on
+    /// the order the tests ran in.
+    ///
+    /// With `--sort false` the entries are written in the order of the test results, e.g. by gas
+    /// used with `--asc` or `--desc`.
+    #[arg(long, default_value_t = true, action = ArgAction::Set, value_name = "BOOL")]
+    sort: bool,
+
     /// Tolerates gas deviations up to the specified percentage.
     #[arg(
         long,
@@ -97,8 +114,9 @@ impl SnapshotArgs {
         // Set fuzz seed so gas snapshots are deterministic
         self.test.fuzz_seed = Some(U256::from_be_bytes(STATIC_FUZZ_SEED));
 
-        let outcome = self.test.execute_tests().await?;
+        let mut outcome = self.test.execute_tests().await?;
         outcome.ensure_ok()?;
+        let groups = std::mem::take(&mut outcome.snapshot_groups);
         let tests = self.config.apply(outcome);
 
         if let Some(path) = self.diff {
@@ -108,13 +126,22 @@ impl SnapshotArgs {
         } else if let Some(path) = self.check {
             let snap = path.as_ref().unwrap_or(&self.snap);
             let snaps = read_snapshot(snap)?;
-            if check(tests, snaps, self.tolerance) {
+            // Entries of tests that were filtered out can't be checked for staleness.
+            let check_stale = !self.test.has_filter();
+            if check(tests, snaps, self.tolerance, check_stale) {
                 std::process::exit(0)
             } else {
                 std::process::exit(1)
             }
         } else {
-            write_to_snapshot_file(&tests, self.snap, self.format)?;
+            write_to_snapshot_file(
+                &tests,
+                self.snap,
+                self.format,
+                self.group_by,
+                &groups,
+                self.sort,
+            )?;
         }
         Ok(())
     }
@@ -137,6 +164,29 @@ impl FromStr for Format {
     }
 }
 
+/// How to group the entries of a snapshot file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    /// Group the entries by test contract.
+    Contract,
+    /// Group the entries by the `@custom:snapshot-group` NatSpec tag of the test, falling back to
+    /// the test contract for untagged tests.
+    Tag,
+}
+
+impl GroupBy {
+    /// Returns the group of the given test.
+    fn group<'a>(&self, test: &'a SuiteTestResult, groups: &'a InlineConfig<String>) -> &'a str {
+        if *self == Self::Tag {
+            let function = test.signature.split('(').next().unwrap_or_default();
+            if let Some(group) = groups.get(&test.artifact_id, function) {
+                return group
+            }
+        }
+        test.contract_name()
+    }
+}
+
 /// Additional filters that can be applied on the test results
 #[derive(Clone, Debug, Default, Parser)]
 struct SnapshotConfig {
@@ -155,6 +205,44 @@ struct SnapshotConfig {
     /// Only include tests that used less gas that the given amount.
     #[arg(long, value_name = "MAX_GAS")]
     max: Option<u64>,
+
+    /// How to record the gas of fuzz tests.
+    ///
+    /// With a mode, fuzz tests are recorded like unit tests, with the selected aggregate of the
+    /// gas used by their runs. By default they are recorded with their runs, mean and median gas.
+    #[arg(long, value_enum, value_name = "MODE")]
+    fuzz_gas_mode: Option<FuzzGasMode>,
+}
+
+/// How to record the gas of fuzz tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum FuzzGasMode {
+    /// The median gas of the runs, the least sensitive to outliers.
+    Median,
+    /// The mean gas of the runs.
+    Mean,
+    /// The highest gas of the runs.
+    Max,
+    /// Do not record fuzz tests.
+    Exclude,
+}
+
+impl FuzzGasMode {
+    /// Replaces the gas of a fuzz test with the selected aggregate.
+    ///
+    /// Returns `None` if fuzz tests are excluded.
+    fn apply(&self, mut test: SuiteTestResult) -> Option<SuiteTestResult> {
+        if let TestKind::Fuzz { mean_gas, median_gas, max_gas, .. } = test.result.kind {
+            let gas = match self {
+                Self::Median => median_gas,
+                Self::Mean => mean_gas,
+                Self::Max => max_gas,
+                Self::Exclude => return None,
+            };
+            test.result.kind = TestKind::Unit { gas };
+        }
+        Some(test)
+    }
 }
 
 impl SnapshotConfig {
@@ -175,6 +263,10 @@ impl SnapshotConfig {
     fn apply(&self, outcome: TestOutcome) -> Vec<SuiteTestResult> {
         let mut tests = outcome
             .into_tests()
+            .filter_map(|test| match self.fuzz_gas_mode {
+                Some(mode) => mode.apply(test),
+                None => Some(test),
+            })
             .filter(|test| self.is_in_gas_range(test.gas_used()))
             .collect::<Vec<_>>();
 
@@ -255,6 +347,8 @@ impl FromStr for SnapshotEntry {
 }
 
 /// Reads a list of snapshot entries from a snapshot file
+///
+/// Blank lines and the section headers of grouped snapshot files are skipped.
 fn read_snapshot(path: impl AsRef<Path>) -> Result<Vec<SnapshotEntry>> {
     let path = path.as_ref();
     let mut entries = Vec::new();
@@ -264,28 +358,62 @@ fn read_snapshot(path: impl AsRef<Path>) -> Result<Vec<SnapshotEntry>> {
     )
     .lines()
     {
-        entries.push(SnapshotEntry::from_str(line?.as_str()).map_err(|err| eyre::eyre!("{err}"))?);
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with(SNAPSHOT_GROUP_PREFIX.trim_end()) {
+            continue
+        }
+        entries.push(SnapshotEntry::from_str(line.as_str()).map_err(|err| eyre::eyre!("{err}"))?);
     }
     Ok(entries)
 }
 
-/// Writes a series of tests to a snapshot file after sorting them
+/// Writes a series of tests to a snapshot file, sorted by test identifier if `sort` is set
+///
+/// If `group_by` is set, the tests are sorted by group first and every group is written as its own
+/// section.
 fn write_to_snapshot_file(
     tests: &[SuiteTestResult],
     path: impl AsRef<Path>,
     _format: Option<Format>,
+    group_by: Option<GroupBy>,
+    groups: &InlineConfig<String>,
+    sort: bool,
 ) -> Result<()> {
     let mut reports = tests
         .iter()
         .map(|test| {
-            format!("{}:{} {}", test.contract_name(), test.signature, test.result.kind.report())
+            let group = group_by.map(|group_by| group_by.group(test, groups)).unwrap_or_default();
+            let report = format!(
+                "{}:{} {}",
+                test.contract_name(),
+                test.signature,
+                test.result.kind.report()
+            );
+            (group, report)
         })
         .collect::<Vec<_>>();
 
-    // sort all reports
-    reports.sort();
+    // sort all reports, or only keep the groups together
+    if sort {
+        reports.sort();
+    } else {
+        reports.sort_by_key(|(group, _)| *group);
+    }
+
+    let mut lines = Vec::with_capacity(reports.len());
+    let mut current_group = None;
+    for (group, report) in reports {
+        if group_by.is_some() && current_group != Some(group) {
+            if current_group.is_some() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{SNAPSHOT_GROUP_PREFIX}{group}"));
+            current_group = Some(group);
+        }
+        lines.push(report);
+    }
 
-    let content = reports.join("\n");
+    let content = lines.join("\n");
     Ok(fs::write(path, content)?)
 }
 
@@ -314,16 +442,24 @@ impl SnapshotDiff {
 
 /// Compares the set of tests with an existing snapshot
 ///
+/// If `check_stale` is set, snapshot entries that don't match any of the tests are reported as
+/// well.
+///
 /// Returns true all tests match
-fn check(tests: Vec<SuiteTestResult>, snaps: Vec<SnapshotEntry>, tolerance: Option<u32>) -> bool {
-    let snaps = snaps
+fn check(
+    tests: Vec<SuiteTestResult>,
+    snaps: Vec<SnapshotEntry>,
+    tolerance: Option<u32>,
+    check_stale: bool,
+) -> bool {
+    let mut snaps = snaps
         .into_iter()
         .map(|s| ((s.contract_name, s.signature), s.gas_used))
         .collect::<HashMap<_, _>>();
     let mut has_diff = false;
     for test in tests {
         if let Some(target_gas) =
-            snaps.get(&(test.contract_name().to_string(), test.signature.clone())).cloned()
+            snaps.remove(&(test.contract_name().to_string(), test.signature.clone()))
         {
             let source_gas = test.result.kind.report();
             if !within_tolerance(source_gas.gas(), target_gas.gas(), tolerance) {
@@ -345,6 +481,16 @@ fn check(tests: Vec<SuiteTestResult>, snaps: Vec<SnapshotEntry>, tolerance: Opti
             has_diff = true;
         }
     }
+    if check_stale {
+        let mut stale = snaps.into_keys().collect::<Vec<_>>();
+        stale.sort();
+        for (contract_name, signature) in stale {
+            eprintln!(
+                "Snapshot entry \"{contract_name}::{signature}\" does not match any test anymore"
+            );
+            has_diff = true;
+        }
+    }
     !has_diff
 }
 
@@ -369,8 +515,13 @@ fn diff(tests: Vec<SuiteTestResult>, snaps: Vec<SnapshotEntry>) -> Result<()> {
     let mut overall_gas_change = 0i128;
     let mut overall_gas_used = 0i128;
 
+    // equal changes are ordered by signature, so the output does not depend on the test order
     diffs.sort_by(|a, b| {
-        a.gas_diff().abs().partial_cmp(&b.gas_diff().abs()).unwrap_or(Ordering::Equal)
+        a.gas_diff()
+            .abs()
+            .partial_cmp(&b.gas_diff().abs())
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.signature.cmp(&b.signature))
     });
 
     for diff in diffs {
@@ -446,6 +597,41 @@ mod tests {
         assert!(within_tolerance(100, 100, None));
     }
 
+    #[test]
+    fn can_parse_group_by() {
+        let args = SnapshotArgs::parse_from(["foundry-cli", "--group-by", "tag"]);
+        assert_eq!(args.group_by, Some(GroupBy::Tag));
+        let args = SnapshotArgs::parse_from(["foundry-cli"]);
+        assert_eq!(args.group_by, None);
+    }
+
+    #[test]
+    fn can_record_fuzz_gas() {
+        let args = SnapshotArgs::parse_from(["foundry-cli"]);
+        assert_eq!(args.config.fuzz_gas_mode, None);
+
+        let test = SuiteTestResult {
+            artifact_id: "Test.t.sol:Test".to_string(),
+            signature: "testFuzz(uint256)".to_string(),
+            result: forge::result::TestResult {
+                kind: TestKind::Fuzz {
+                    first_case: Default::default(),
+                    runs: 256,
+                    mean_gas: 100,
+                    median_gas: 90,
+                    max_gas: 200,
+                },
+                ..Default::default()
+            },
+        };
+        let gas =
+            |mode: FuzzGasMode| mode.apply(test.clone()).map(|test| test.result.kind.report());
+        assert_eq!(gas(FuzzGasMode::Median), Some(TestKindReport::Unit { gas: 90 }));
+        assert_eq!(gas(FuzzGasMode::Mean), Some(TestKindReport::Unit { gas: 100 }));
+        assert_eq!(gas(FuzzGasMode::Max), Some(TestKindReport::Unit { gas: 200 }));
+        assert_eq!(gas(FuzzGasMode::Exclude), None);
+    }
+
     #[test]
     fn can_parse_basic_snapshot_entry() {
         let s = "Test:deposit() (gas: 7222)";
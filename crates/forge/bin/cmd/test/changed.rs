@@ -0,0 +1,64 @@
+//! Selection of the test files affected by the changes of a git range.
+
+use crate::cmd::tree::importers;
+use eyre::Result;
+use foundry_cli::utils::{CommandUtils, Git};
+use foundry_compilers::{resolver::parse::SolData, Graph};
+use foundry_config::Config;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+/// Returns the test files that are affected by the files changed in the git `range`, i.e. the
+/// changed test files and the test files transitively importing a changed file.
+///
+/// Prints a note for every changed source file that is not imported by any test file.
+pub fn changed_test_files(config: &Config, range: &str) -> Result<BTreeSet<PathBuf>> {
+    let repo_root = Git::root_of(&config.root.0)?;
+    let diff =
+        Git::new(&repo_root).cmd().args(["diff", "--name-only", range, "--"]).get_stdout_lossy()?;
+    let changed = diff
+        .lines()
+        .filter_map(|file| dunce::canonicalize(repo_root.join(file)).ok())
+        .collect::<Vec<_>>();
+
+    let graph = Graph::<SolData>::resolve(&config.project_paths())?;
+    let importers = importers(&graph);
+    let test_dir = dunce::canonicalize(&config.test).unwrap_or_else(|_| config.test.clone());
+    let mut tests = BTreeSet::new();
+    for file in changed {
+        let Some(&idx) = graph.files().get(&file) else { continue };
+        let dependents = affected_tests(&graph, &importers, idx, &test_dir);
+        if dependents.is_empty() {
+            let file = file.strip_prefix(&config.root.0).unwrap_or(&file);
+            println!("Note: {} changed but is not imported by any test", file.display());
+        }
+        tests.extend(dependents);
+    }
+    Ok(tests)
+}
+
+/// Returns the test files among the node and all the files transitively importing it.
+fn affected_tests(
+    graph: &Graph<SolData>,
+    importers: &[Vec<usize>],
+    idx: usize,
+    test_dir: &Path,
+) -> BTreeSet<PathBuf> {
+    let mut visited = BTreeSet::from([idx]);
+    let mut queue = vec![idx];
+    while let Some(idx) = queue.pop() {
+        for &importer in &importers[idx] {
+            if visited.insert(importer) {
+                queue.push(importer);
+            }
+        }
+    }
+
+    visited
+        .into_iter()
+        .map(|idx| graph.node(idx).path().to_path_buf())
+        .filter(|path| path.starts_with(test_dir))
+        .collect()
+}
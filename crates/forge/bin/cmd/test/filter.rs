@@ -1,8 +1,12 @@
-use clap::Parser;
-use foundry_common::TestFilter;
+use clap::{Parser, ValueEnum};
+use foundry_common::{TestFilter, TestFunctionKind};
 use foundry_compilers::{FileFilter, ProjectPathsConfig};
 use foundry_config::{filter::GlobMatcher, Config};
-use std::{fmt, path::Path};
+use std::{
+    collections::BTreeSet,
+    fmt,
+    path::{Path, PathBuf},
+};
 
 /// The filter to use during testing.
 ///
@@ -39,6 +43,12 @@ pub struct FilterArgs {
     )]
     pub path_pattern_inverse: Option<GlobMatcher>,
 
+    /// Only run tests of the specified kind.
+    ///
+    /// Can be repeated to run several kinds of tests.
+    #[arg(long = "kind", value_enum, value_name = "KIND")]
+    pub test_kinds: Vec<TestKindFilter>,
+
     /// Only show coverage for files that do not match the specified regex pattern.
     #[arg(long = "no-match-coverage", visible_alias = "nmco", value_name = "REGEX")]
     pub coverage_pattern_inverse: Option<regex::Regex>,
@@ -52,7 +62,8 @@ impl FilterArgs {
             self.contract_pattern.is_none() &&
             self.contract_pattern_inverse.is_none() &&
             self.path_pattern.is_none() &&
-            self.path_pattern_inverse.is_none()
+            self.path_pattern_inverse.is_none() &&
+            self.test_kinds.is_empty()
     }
 
     /// Merges the set filter globs with the config's values
@@ -78,7 +89,7 @@ impl FilterArgs {
         if self.coverage_pattern_inverse.is_none() {
             self.coverage_pattern_inverse = config.coverage_pattern_inverse.clone().map(Into::into);
         }
-        ProjectPathsAwareFilter { args_filter: self, paths: config.project_paths() }
+        ProjectPathsAwareFilter { args_filter: self, paths: config.project_paths(), files: None }
     }
 }
 
@@ -91,6 +102,7 @@ impl fmt::Debug for FilterArgs {
             .field("no-match-contract", &self.contract_pattern_inverse.as_ref().map(|r| r.as_str()))
             .field("match-path", &self.path_pattern.as_ref().map(|g| g.as_str()))
             .field("no-match-path", &self.path_pattern_inverse.as_ref().map(|g| g.as_str()))
+            .field("kind", &self.test_kinds)
             .field("no-match-coverage", &self.coverage_pattern_inverse.as_ref().map(|g| g.as_str()))
             .finish_non_exhaustive()
     }
@@ -138,6 +150,10 @@ impl TestFilter for FilterArgs {
         }
         ok
     }
+
+    fn matches_test_kind(&self, kind: TestFunctionKind) -> bool {
+        self.test_kinds.is_empty() || self.test_kinds.iter().any(|filter| filter.matches(kind))
+    }
 }
 
 impl fmt::Display for FilterArgs {
@@ -163,21 +179,60 @@ impl fmt::Display for FilterArgs {
         if let Some(p) = &self.coverage_pattern_inverse {
             writeln!(f, "\tno-match-coverage: `{}`", p.as_str())?;
         }
+        for kind in &self.test_kinds {
+            writeln!(f, "\tkind: `{}`", kind.to_possible_value().unwrap().get_name())?;
+        }
         Ok(())
     }
 }
 
+/// The kinds of test functions that can be filtered for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TestKindFilter {
+    /// Unit tests, i.e. `test*` functions without parameters.
+    Unit,
+    /// Fuzz tests, i.e. `test*` functions with parameters.
+    Fuzz,
+    /// Invariant tests, i.e. `invariant*` and `statefulFuzz*` functions.
+    Invariant,
+}
+
+impl TestKindFilter {
+    /// Returns whether the test function kind is of this kind.
+    pub fn matches(&self, kind: TestFunctionKind) -> bool {
+        match self {
+            Self::Unit => matches!(kind, TestFunctionKind::UnitTest { .. }),
+            Self::Fuzz => kind.is_fuzz_test(),
+            Self::Invariant => kind.is_invariant_test(),
+        }
+    }
+}
+
 /// A filter that combines all command line arguments and the paths of the current projects
 #[derive(Clone, Debug)]
 pub struct ProjectPathsAwareFilter {
     args_filter: FilterArgs,
     paths: ProjectPathsConfig,
+    /// If set, only the tests in these files are run.
+    files: Option<BTreeSet<PathBuf>>,
 }
 
 impl ProjectPathsAwareFilter {
     /// Returns true if the filter is empty.
     pub fn is_empty(&self) -> bool {
-        self.args_filter.is_empty()
+        self.args_filter.is_empty() && self.files.is_none()
+    }
+
+    /// Only run the tests in the given files, in addition to the other filters.
+    pub fn restrict_to_files(&mut self, files: BTreeSet<PathBuf>) {
+        self.files = Some(files);
+    }
+
+    /// Returns true if the file is allowed by the restriction to a set of files, if any.
+    fn matches_files(&self, file: &Path) -> bool {
+        self.files.as_ref().map_or(true, |files| {
+            files.contains(file) || files.contains(&self.paths.root.join(file))
+        })
     }
 
     /// Returns the CLI arguments.
@@ -201,6 +256,9 @@ impl FileFilter for ProjectPathsAwareFilter {
     ///
     /// If no file regex is set this returns true by default
     fn is_match(&self, mut file: &Path) -> bool {
+        if !self.matches_files(file) {
+            return false;
+        }
         file = file.strip_prefix(&self.paths.root).unwrap_or(file);
         self.args_filter.is_match(file)
     }
@@ -216,14 +274,25 @@ impl TestFilter for ProjectPathsAwareFilter {
     }
 
     fn matches_path(&self, mut path: &Path) -> bool {
+        if !self.matches_files(path) {
+            return false;
+        }
         // we don't want to test files that belong to a library
         path = path.strip_prefix(&self.paths.root).unwrap_or(path);
         self.args_filter.matches_path(path) && !self.paths.has_library_ancestor(path)
     }
+
+    fn matches_test_kind(&self, kind: TestFunctionKind) -> bool {
+        self.args_filter.matches_test_kind(kind)
+    }
 }
 
 impl fmt::Display for ProjectPathsAwareFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.args_filter.fmt(f)
+        self.args_filter.fmt(f)?;
+        if let Some(files) = &self.files {
+            writeln!(f, "\trestricted to: {} file(s)", files.len())?;
+        }
+        Ok(())
     }
 }
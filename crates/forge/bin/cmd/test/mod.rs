@@ -1,14 +1,16 @@
 use super::{install, test::filter::ProjectPathsAwareFilter, watch::WatchArgs};
-use alloy_primitives::U256;
-use clap::Parser;
-use eyre::Result;
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider as _;
+use clap::{Parser, ValueHint};
+use eyre::{Context, Result};
 use forge::{
-    decode::decode_console_logs,
+    flamegraph::GasFlamegraph,
     gas_report::GasReport,
-    multi_runner::matches_contract,
-    result::{SuiteResult, TestOutcome, TestStatus},
+    multi_runner::{is_matching_test, matches_contract},
+    result::{SkipReason, SkippedTest, SuiteResult, TestOutcome, TestStatus},
+    state::StateDump,
     traces::{
-        decode_trace_arena, identifier::SignaturesIdentifier, render_trace_arena,
+        decode_trace_arena, identifier::SignaturesIdentifier, render_trace_arena, CallTraceArena,
         CallTraceDecoderBuilder, TraceKind,
     },
     MultiContractRunner, MultiContractRunnerBuilder, TestFilter, TestOptions, TestOptionsBuilder,
@@ -19,8 +21,10 @@ use foundry_cli::{
 };
 use foundry_common::{
     compile::{ContractSources, ProjectCompiler},
-    evm::EvmArgs,
-    fs, shell,
+    evm::{EvmArgs, ForkBlockNumber},
+    fs, get_contract_name,
+    provider::ProviderBuilder,
+    shell, TestFunctionExt,
 };
 use foundry_compilers::{
     artifacts::output_selection::OutputSelection,
@@ -33,25 +37,30 @@ use foundry_config::{
         value::{Dict, Map},
         Metadata, Profile, Provider,
     },
+    filter::GlobMatcher,
     get_available_profiles, Config,
 };
 use foundry_debugger::Debugger;
-use foundry_evm::traces::identifier::TraceIdentifiers;
+use foundry_evm::{
+    inspectors::cheatcodes::MatchedExpectedRevert, traces::identifier::TraceIdentifiers,
+};
 use regex::Regex;
 use std::{
-    collections::{BTreeMap, BTreeSet},
-    path::PathBuf,
+    collections::{BTreeMap, BTreeSet, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
     sync::{mpsc::channel, Arc},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use watchexec::config::{InitConfig, RuntimeConfig};
 use yansi::Paint;
 
+mod changed;
 mod filter;
 mod summary;
 use summary::TestSummaryReporter;
 
-pub use filter::FilterArgs;
+pub use filter::{FilterArgs, ProjectPathsAwareFilter};
 
 // Loads project's figment and merges the build cli arguments into it
 foundry_config::merge_impl_figment_convert!(TestArgs, opts, evm_opts);
@@ -82,18 +91,64 @@ pub struct TestArgs {
     #[arg(long, env = "FORGE_GAS_REPORT")]
     gas_report: bool,
 
+    /// Export the gas usage of each test as folded stacks to the given file.
+    ///
+    /// The output can be rendered with standard flamegraph tooling, e.g. `inferno-flamegraph`.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    flamegraph: Option<PathBuf>,
+
     /// Exit with code 0 even if a test fails.
     #[arg(long, env = "FORGE_ALLOW_FAILURE")]
     allow_failure: bool,
 
+    /// Only allow the failures of the tests matching the glob, e.g. `'testFork*'`.
+    ///
+    /// These tests still run, and their failures are reported separately.
+    #[arg(long, value_name = "GLOB")]
+    allow_failure_match: Vec<String>,
+
     /// Output test results in JSON format.
     #[arg(long, help_heading = "Display options")]
     json: bool,
 
+    /// Stream the test results as JSON lines, as their suites complete.
+    ///
+    /// Every line is either a `test` object, with the same result as in the `--json` output, or
+    /// the final `summary` object.
+    #[arg(long, help_heading = "Display options", conflicts_with_all = ["json", "list"])]
+    json_lines: bool,
+
+    /// Label the addresses of the traces with the names of a JSON file mapping addresses to
+    /// names, e.g. `{"0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": "WETH"}`.
+    ///
+    /// Labels set by the tests with `vm.label` take precedence.
+    #[arg(long, help_heading = "Display options", value_name = "PATH", value_hint = ValueHint::FilePath)]
+    labels: Option<PathBuf>,
+
+    /// Write the state at the end of the unit tests to the given JSON file, in the format loaded
+    /// by `anvil --load-state`.
+    ///
+    /// The states of the tests are merged in name order, unless `--snapshot-state-per-test` is
+    /// set. Fuzz and invariant tests have no final state and are not included.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::AnyPath)]
+    snapshot_state: Option<PathBuf>,
+
+    /// Write the state of every unit test to `<PATH>/<contract>.<test>.json` instead.
+    #[arg(long, requires = "snapshot_state")]
+    snapshot_state_per_test: bool,
+
     /// Stop running tests after the first failure.
     #[arg(long)]
     pub fail_fast: bool,
 
+    /// Re-run a failing test up to the given number of times, e.g. to work around transient RPC
+    /// errors of fork tests.
+    ///
+    /// A test only fails if it fails every attempt. Tests passing after a retry are reported as
+    /// flaky.
+    #[arg(long, value_name = "RETRIES", default_value_t = 0)]
+    pub retries: u32,
+
     /// The Etherscan (or equivalent) API key.
     #[arg(long, env = "ETHERSCAN_API_KEY", value_name = "KEY")]
     etherscan_api_key: Option<String>,
@@ -109,10 +164,21 @@ pub struct TestArgs {
     #[arg(long, env = "FOUNDRY_FUZZ_RUNS", value_name = "RUNS")]
     pub fuzz_runs: Option<u64>,
 
+    /// The maximum number of inputs rejected by `vm.assume` before a fuzz test is aborted.
+    #[arg(long, env = "FOUNDRY_FUZZ_MAX_TEST_REJECTS", value_name = "REJECTS")]
+    pub fuzz_max_rejects: Option<u32>,
+
     /// File to rerun fuzz failures from.
     #[arg(long)]
     pub fuzz_input_file: Option<String>,
 
+    /// Seed the fuzz dictionary with storage values read from the forked chain.
+    ///
+    /// Only applies to fuzz tests running on a fork, and costs an RPC request for every storage
+    /// slot that is not cached yet.
+    #[arg(long)]
+    pub fuzz_fork_dictionary: bool,
+
     /// Max concurrent threads to use.
     /// Default value is the number of available CPUs.
     #[arg(long, short = 'j', visible_alias = "jobs")]
@@ -130,6 +196,37 @@ pub struct TestArgs {
     #[arg(long)]
     pub rerun: bool,
 
+    /// Only run the test files affected by the files changed in the given git range, e.g.
+    /// `origin/master...HEAD`.
+    ///
+    /// A test file is affected if it changed or if it transitively imports a changed file.
+    #[arg(long, value_name = "GIT_RANGE", help_heading = "Test filtering")]
+    pub changed: Option<String>,
+
+    /// Refetch the cached state of forks of the latest block once it is older than the given
+    /// duration, e.g. `10m` or `1h`.
+    ///
+    /// Forks of a pinned block stay cached. A TTL of `0` disables caching of the other forks.
+    #[arg(long, value_name = "DURATION", value_parser = parse_cache_ttl)]
+    pub rpc_cache_ttl: Option<Duration>,
+
+    /// Collect coverage like `forge coverage`, and fail if the line coverage of the project is
+    /// below the given percentage, e.g. `80`.
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        value_parser = parse_percentage,
+        conflicts_with = "watch"
+    )]
+    pub min_coverage: Option<f64>,
+
+    /// Exclude the source files matching the glob, e.g. `src/generated/**`, from the coverage
+    /// analysis and the report totals of `--min-coverage` and `forge coverage`.
+    ///
+    /// This flag can be used multiple times.
+    #[arg(long, value_name = "GLOB")]
+    pub ignore: Vec<GlobMatcher>,
+
     #[command(flatten)]
     evm_opts: EvmArgs,
 
@@ -143,9 +240,22 @@ pub struct TestArgs {
     #[arg(long, help_heading = "Display options")]
     pub summary: bool,
 
+    /// Print one line per test contract, with its pass/fail/skip counts and total time.
+    ///
+    /// The individual test results are only printed for failed tests.
+    #[arg(long, help_heading = "Display options")]
+    pub compact: bool,
+
     /// Print detailed test summary table.
     #[arg(long, help_heading = "Display options", requires = "summary")]
     pub detailed: bool,
+
+    /// Print the tests that were not run, along with the reason: `filtered`,
+    /// `skipped-at-runtime` or `compile-excluded`.
+    ///
+    /// With `--json`, the output is an object with the test `results` and the `skipped` tests.
+    #[arg(long, help_heading = "Display options")]
+    pub show_skipped: bool,
 }
 
 impl TestArgs {
@@ -156,7 +266,7 @@ impl TestArgs {
 
     pub async fn run(self) -> Result<TestOutcome> {
         trace!(target: "forge::test", "executing test command");
-        shell::set_shell(shell::Shell::from_args(self.opts.silent, self.json))?;
+        shell::set_shell(shell::Shell::from_args(self.opts.silent, self.json || self.json_lines))?;
         self.execute_tests().await
     }
 
@@ -248,6 +358,22 @@ impl TestArgs {
             rayon::ThreadPoolBuilder::new().num_threads(test_threads).build_global()?;
         }
 
+        evm_opts.rpc_cache_ttl = self.rpc_cache_ttl;
+
+        // Pin the latest block once, otherwise every test suite forks the latest block when it
+        // starts and may see a different state.
+        if self.evm_opts.fork_block_number == Some(ForkBlockNumber::Latest) {
+            if let Some(fork_url) = &evm_opts.fork_url {
+                let provider = ProviderBuilder::new(fork_url)
+                    .compute_units_per_second(evm_opts.get_compute_units_per_second())
+                    .build()?;
+                let number = provider.get_block_number().await?;
+                trace!(target: "forge::test", number, "pinned the latest block");
+                evm_opts.fork_block_number = Some(number);
+                config.fork_block_number = Some(number);
+            }
+        }
+
         // Explicitly enable isolation for gas reports for more correct gas accounting.
         if self.gas_report {
             evm_opts.isolate = true;
@@ -270,12 +396,21 @@ impl TestArgs {
         }
 
         let mut filter = self.filter(&config);
+        if let Some(range) = &self.changed {
+            let files = changed::changed_test_files(&config, range)?;
+            if files.is_empty() {
+                println!("No test files are affected by the changes in `{range}`");
+                return self.outcome(BTreeMap::new());
+            }
+            filter.restrict_to_files(files);
+        }
         trace!(target: "forge::test", ?filter, "using filter");
 
+        utils::run_prebuild_hook(&config, self.json || self.json_lines || self.opts.silent)?;
         let sources_to_compile = self.get_sources_to_compile(&config, &filter)?;
 
         let compiler = ProjectCompiler::new()
-            .quiet_if(self.json || self.opts.silent)
+            .quiet_if(self.json || self.json_lines || self.opts.silent)
             .files(sources_to_compile);
 
         let output = compiler.compile(&project)?;
@@ -290,10 +425,11 @@ impl TestArgs {
             .invariant(config.invariant.clone())
             .profiles(profiles)
             .build(&output, project_root)?;
+        let snapshot_groups = test_options.inline_snapshot_group.clone();
 
         // Determine print verbosity and executor verbosity.
         let verbosity = evm_opts.verbosity;
-        if self.gas_report && evm_opts.verbosity < 3 {
+        if (self.gas_report || self.flamegraph.is_some()) && evm_opts.verbosity < 3 {
             evm_opts.verbosity = 3;
         }
 
@@ -310,6 +446,8 @@ impl TestArgs {
             .with_fork(evm_opts.get_fork(&config, env.clone()))
             .with_test_options(test_options)
             .enable_isolation(evm_opts.isolate)
+            .set_dump_state(self.snapshot_state.is_some())
+            .set_retries(self.retries)
             .build(project_root, &output, env, evm_opts)?;
 
         if let Some(debug_test_pattern) = &self.debug {
@@ -324,7 +462,12 @@ impl TestArgs {
         }
 
         let libraries = runner.libraries.clone();
-        let outcome = self.run_tests(runner, config, verbosity, &filter).await?;
+        let mut outcome = self.run_tests(runner, config, verbosity, &filter).await?;
+        outcome.snapshot_groups = snapshot_groups;
+
+        if let Some(path) = &self.snapshot_state {
+            write_state_dumps(path, &outcome, self.snapshot_state_per_test)?;
+        }
 
         if should_debug {
             // Get first non-empty suite result. We will have only one such entry.
@@ -380,10 +523,27 @@ impl TestArgs {
             );
         }
 
+        let skipped =
+            if self.show_skipped { skipped_tests(&runner, &config, filter) } else { Vec::new() };
+
         if self.json {
             let results = runner.test_collect(filter);
-            println!("{}", serde_json::to_string(&results)?);
-            return Ok(TestOutcome::new(results, self.allow_failure));
+            let mut outcome = self.outcome(results)?;
+            outcome.skipped = skipped;
+            if self.show_skipped {
+                let json = serde_json::json!({
+                    "results": outcome.results,
+                    "skipped": outcome.all_skipped(),
+                });
+                println!("{}", serde_json::to_string(&json)?);
+            } else {
+                println!("{}", serde_json::to_string(&outcome.results)?);
+            }
+            return Ok(outcome);
+        }
+
+        if self.json_lines {
+            return self.stream_json_lines(runner, &config, filter, skipped).await;
         }
 
         let remote_chain_id = runner.evm_opts.get_remote_chain_id().await;
@@ -419,12 +579,16 @@ impl TestArgs {
             )?);
         }
         let mut decoder = builder.build();
+        let labels = self.labels.as_deref().map(read_labels).transpose()?.unwrap_or_default();
 
         let mut gas_report = self
             .gas_report
             .then(|| GasReport::new(config.gas_reports.clone(), config.gas_reports_ignore.clone()));
 
-        let mut outcome = TestOutcome::empty(self.allow_failure);
+        let mut flamegraph = self.flamegraph.is_some().then(GasFlamegraph::default);
+
+        let mut outcome = self.outcome(BTreeMap::new())?;
+        outcome.skipped = skipped;
 
         let mut any_test_failed = false;
         for (contract_name, suite_result) in rx {
@@ -434,14 +598,19 @@ impl TestArgs {
             decoder.clear_addresses();
 
             // We identify addresses if we're going to print *any* trace or gas report.
-            let identify_addresses = verbosity >= 3 || self.gas_report || self.debug.is_some();
+            let identify_addresses = verbosity >= 3 ||
+                self.gas_report ||
+                self.flamegraph.is_some() ||
+                self.debug.is_some();
 
             // Print suite header.
-            println!();
+            if !self.compact {
+                println!();
+            }
             for warning in suite_result.warnings.iter() {
                 eprintln!("{} {warning}", "Warning:".yellow().bold());
             }
-            if !tests.is_empty() {
+            if !tests.is_empty() && !self.compact {
                 let len = tests.len();
                 let tests = if len > 1 { "tests" } else { "test" };
                 println!("Ran {len} {tests} for {contract_name}");
@@ -449,15 +618,18 @@ impl TestArgs {
 
             // Process individual test results, printing logs and traces when necessary.
             for (name, result) in tests {
-                shell::println(result.short_result(name))?;
+                // In compact mode, only failed tests are displayed.
+                let display = !self.compact || result.status.is_failure();
+                if display {
+                    shell::println(result.short_result(name))?;
+                }
 
                 // We only display logs at level 2 and above
-                if verbosity >= 2 {
-                    // We only decode logs from Hardhat and DS-style console events
-                    let console_logs = decode_console_logs(&result.logs);
-                    if !console_logs.is_empty() {
+                if display && verbosity >= 2 {
+                    // We only print logs from Hardhat and DS-style console events
+                    if !result.decoded_logs.is_empty() {
                         println!("Logs:");
-                        for log in console_logs {
+                        for log in &result.decoded_logs {
                             println!("  {log}");
                         }
                         println!();
@@ -466,10 +638,12 @@ impl TestArgs {
 
                 // We shouldn't break out of the outer loop directly here so that we finish
                 // processing the remaining tests and print the suite summary.
-                any_test_failed |= result.status == TestStatus::Failure;
+                any_test_failed |=
+                    result.status == TestStatus::Failure && !outcome.is_allowed_failure(name);
 
                 // Clear the addresses and labels from previous runs.
                 decoder.clear_addresses();
+                decoder.labels.extend(labels.iter().map(|(k, v)| (*k, v.clone())));
                 decoder
                     .labels
                     .extend(result.labeled_addresses.iter().map(|(k, v)| (*k, v.clone())));
@@ -486,18 +660,22 @@ impl TestArgs {
                     // - 3: only display traces for failed tests
                     // - 4: also display the setup trace for failed tests
                     // - 5..: display all traces for all tests
-                    let should_include = match kind {
-                        TraceKind::Execution => {
-                            (verbosity == 3 && result.status.is_failure()) || verbosity >= 4
-                        }
-                        TraceKind::Setup => {
-                            (verbosity == 4 && result.status.is_failure()) || verbosity >= 5
-                        }
-                        TraceKind::Deployment => false,
-                    };
+                    let should_include = display &&
+                        match kind {
+                            TraceKind::Execution => {
+                                (verbosity == 3 && result.status.is_failure()) || verbosity >= 4
+                            }
+                            TraceKind::Setup => {
+                                (verbosity == 4 && result.status.is_failure()) || verbosity >= 5
+                            }
+                            TraceKind::Deployment => false,
+                        };
 
                     if should_include {
                         decode_trace_arena(arena, &decoder).await?;
+                        if matches!(kind, TraceKind::Execution) {
+                            mark_expected_reverts(arena, &result.matched_expected_reverts);
+                        }
                         decoded_traces.push(render_trace_arena(arena));
                     }
                 }
@@ -509,6 +687,14 @@ impl TestArgs {
                     }
                 }
 
+                if let Some(flamegraph) = &mut flamegraph {
+                    for (kind, arena) in &result.traces {
+                        if !kind.is_deployment() {
+                            flamegraph.analyze(arena, &decoder).await;
+                        }
+                    }
+                }
+
                 if let Some(gas_report) = &mut gas_report {
                     gas_report
                         .analyze(result.traces.iter().map(|(_, arena)| arena), &decoder)
@@ -534,7 +720,11 @@ impl TestArgs {
             }
 
             // Print suite summary.
-            shell::println(suite_result.summary())?;
+            if self.compact {
+                shell::println(format!("{contract_name}: {}", suite_result.summary()))?;
+            } else {
+                shell::println(suite_result.summary())?;
+            }
 
             // Add the suite result to the outcome.
             outcome.results.insert(contract_name, suite_result);
@@ -555,6 +745,11 @@ impl TestArgs {
             outcome.gas_report = Some(finalized);
         }
 
+        if let (Some(flamegraph), Some(path)) = (flamegraph, &self.flamegraph) {
+            flamegraph.write(path)?;
+            shell::println(format!("Gas flamegraph written to {}", path.display()))?;
+        }
+
         if !outcome.results.is_empty() {
             shell::println(outcome.summary(duration))?;
 
@@ -565,6 +760,16 @@ impl TestArgs {
             }
         }
 
+        if self.show_skipped {
+            let skipped = outcome.all_skipped();
+            if !skipped.is_empty() {
+                shell::println("\nSkipped tests:")?;
+                for test in skipped {
+                    shell::println(format!("  {test}"))?;
+                }
+            }
+        }
+
         // Reattach the task.
         if let Err(e) = handle.await {
             match e.try_into_panic() {
@@ -579,6 +784,84 @@ impl TestArgs {
         Ok(outcome)
     }
 
+    /// Runs the tests, printing every result as a JSON line as soon as its suite completes,
+    /// followed by a summary line.
+    async fn stream_json_lines(
+        &self,
+        runner: MultiContractRunner,
+        config: &Config,
+        filter: &ProjectPathsAwareFilter,
+        skipped: Vec<SkippedTest>,
+    ) -> Result<TestOutcome> {
+        let (tx, rx) = channel::<(String, SuiteResult)>();
+        let timer = Instant::now();
+        let handle = tokio::task::spawn_blocking({
+            let filter = filter.clone();
+            move || runner.test(&filter, tx, false)
+        });
+
+        let mut outcome = self.outcome(BTreeMap::new())?;
+        outcome.skipped = skipped;
+        let mut stdout = std::io::stdout().lock();
+        for (contract_name, suite_result) in rx {
+            for (name, result) in &suite_result.test_results {
+                let line = serde_json::json!({
+                    "type": "test",
+                    "suite": contract_name,
+                    "test": name,
+                    "result": result,
+                });
+                writeln!(stdout, "{line}")?;
+            }
+            stdout.flush()?;
+
+            let any_test_failed =
+                suite_result.failures().any(|(name, _)| !outcome.is_allowed_failure(name));
+            outcome.results.insert(contract_name, suite_result);
+            if self.fail_fast && any_test_failed {
+                break;
+            }
+        }
+
+        let mut summary = serde_json::json!({
+            "type": "summary",
+            "passed": outcome.passed(),
+            "failed": outcome.failed(),
+            "skipped": outcome.skipped(),
+            "suites": outcome.results.len(),
+            "duration": humantime_serde::re::humantime::format_duration(timer.elapsed())
+                .to_string(),
+        });
+        if self.show_skipped {
+            summary["not_run"] = serde_json::to_value(outcome.all_skipped())?;
+        }
+        writeln!(stdout, "{summary}")?;
+        stdout.flush()?;
+        drop(stdout);
+
+        if let Err(e) = handle.await {
+            match e.try_into_panic() {
+                Ok(payload) => std::panic::resume_unwind(payload),
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        persist_run_failures(config, &outcome);
+        Ok(outcome)
+    }
+
+    /// Returns the outcome of the given results, with the failures allowed by `--allow-failure`
+    /// and `--allow-failure-match`.
+    fn outcome(&self, results: BTreeMap<String, SuiteResult>) -> Result<TestOutcome> {
+        let mut outcome = TestOutcome::new(results, self.allow_failure);
+        outcome.allow_failure_globs = self
+            .allow_failure_match
+            .iter()
+            .map(|glob| glob.parse().wrap_err_with(|| format!("invalid glob `{glob}`")))
+            .collect::<Result<_>>()?;
+        Ok(outcome)
+    }
+
     /// Returns the flattened [`FilterArgs`] arguments merged with [`Config`].
     /// Loads and applies filter from file if only last test run failures performed.
     pub fn filter(&self, config: &Config) -> ProjectPathsAwareFilter {
@@ -589,6 +872,11 @@ impl TestArgs {
         filter.merge_with_config(config)
     }
 
+    /// Returns whether the tests to run were restricted via CLI filters, `--rerun` or `--changed`.
+    pub fn has_filter(&self) -> bool {
+        self.rerun || self.changed.is_some() || !self.filter.is_empty()
+    }
+
     /// Returns whether `BuildArgs` was configured with `--watch`
     pub fn is_watch(&self) -> bool {
         self.watch.watch.is_some()
@@ -619,9 +907,15 @@ impl Provider for TestArgs {
         if let Some(fuzz_runs) = self.fuzz_runs {
             fuzz_dict.insert("runs".to_string(), fuzz_runs.into());
         }
+        if let Some(fuzz_max_rejects) = self.fuzz_max_rejects {
+            fuzz_dict.insert("max_test_rejects".to_string(), fuzz_max_rejects.into());
+        }
         if let Some(fuzz_input_file) = self.fuzz_input_file.clone() {
             fuzz_dict.insert("failure_persist_file".to_string(), fuzz_input_file.into());
         }
+        if self.fuzz_fork_dictionary {
+            fuzz_dict.insert("fork_dictionary".to_string(), true.into());
+        }
         dict.insert("fuzz".to_string(), fuzz_dict.into());
 
         if let Some(etherscan_api_key) =
@@ -642,6 +936,48 @@ impl Provider for TestArgs {
     }
 }
 
+/// Collects the tests of the project that won't be run, i.e. the test functions that don't match
+/// the filter and the test source files that were not compiled.
+fn skipped_tests(
+    runner: &MultiContractRunner,
+    config: &Config,
+    filter: &ProjectPathsAwareFilter,
+) -> Vec<SkippedTest> {
+    let mut skipped = Vec::new();
+    for (id, contract) in &runner.contracts {
+        // Tests of dependencies are never run.
+        if filter.paths().has_library_ancestor(&id.source) {
+            continue
+        }
+        let matches = filter.matches_path(&id.source) && filter.matches_contract(&id.name);
+        skipped.extend(
+            contract
+                .abi
+                .functions()
+                .filter(|func| func.is_any_test() && !(matches && is_matching_test(func, filter)))
+                .map(|func| SkippedTest {
+                    id: id.identifier(),
+                    signature: Some(func.signature()),
+                    reason: SkipReason::Filtered,
+                }),
+        );
+    }
+
+    let compiled =
+        runner.known_contracts.keys().map(|id| id.source.as_path()).collect::<HashSet<_>>();
+    for file in source_files_iter(&config.test, MultiCompilerLanguage::FILE_EXTENSIONS) {
+        let file = file.strip_prefix(&config.root.0).unwrap_or(&file);
+        if !compiled.contains(file) {
+            skipped.push(SkippedTest {
+                id: file.display().to_string(),
+                signature: None,
+                reason: SkipReason::CompileExcluded,
+            });
+        }
+    }
+    skipped
+}
+
 /// Lists all matching tests
 fn list(
     runner: MultiContractRunner,
@@ -672,6 +1008,24 @@ fn last_run_failures(config: &Config) -> Option<regex::Regex> {
     }
 }
 
+/// Marks the reverted calls of the decoded trace which were expected with `vm.expectRevert`, so
+/// that they can be told apart from the unexpected ones.
+///
+/// The calls are matched in order by depth and revert data.
+fn mark_expected_reverts(arena: &mut CallTraceArena, reverts: &[MatchedExpectedRevert]) {
+    let mut reverts = reverts.iter().peekable();
+    for node in arena.nodes_mut() {
+        let Some(revert) = reverts.peek() else { break };
+        let trace = &mut node.trace;
+        if trace.success || trace.depth as u64 != revert.depth || trace.output != revert.data {
+            continue
+        }
+        let data = trace.decoded.return_data.take().unwrap_or_else(|| trace.output.to_string());
+        trace.decoded.return_data = Some(format!("{data} (expected)"));
+        reverts.next();
+    }
+}
+
 /// Persist filter with last test run failures (only if there's any failure).
 fn persist_run_failures(config: &Config, outcome: &TestOutcome) {
     if outcome.failed() > 0 && fs::create_file(&config.test_failures_file).is_ok() {
@@ -689,24 +1043,127 @@ fn persist_run_failures(config: &Config, outcome: &TestOutcome) {
     }
 }
 
+/// Writes the states at the end of the unit tests of the outcome to `path`, merged in name order,
+/// or with `per_test`, to a `<contract>.<test>.json` file per test in the `path` directory.
+fn write_state_dumps(path: &Path, outcome: &TestOutcome, per_test: bool) -> Result<()> {
+    let states = outcome.results.iter().flat_map(|(suite, suite_result)| {
+        suite_result
+            .test_results
+            .iter()
+            .filter_map(move |(name, result)| Some((suite, name, result.state.as_ref()?)))
+    });
+
+    if per_test {
+        fs::create_dir_all(path)?;
+        for (suite, name, state) in states {
+            let contract = get_contract_name(suite);
+            let test = name.split('(').next().unwrap_or(name);
+            fs::write_json_file(&path.join(format!("{contract}.{test}.json")), state)?;
+        }
+    } else {
+        let mut merged = StateDump::default();
+        for (_, _, state) in states {
+            merged.merge(state.clone());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write_json_file(path, &merged)?;
+    }
+    Ok(())
+}
+
+/// Reads a JSON file mapping addresses to their labels.
+fn read_labels(path: &Path) -> Result<BTreeMap<Address, String>> {
+    fs::read_json_file(path)
+        .wrap_err_with(|| format!("failed to read labels file {}", path.display()))
+}
+
+/// Parses a cache TTL, either `0` or a duration with a unit.
+fn parse_cache_ttl(s: &str) -> Result<Duration> {
+    if s == "0" {
+        return Ok(Duration::ZERO)
+    }
+    Ok(humantime_serde::re::humantime::parse_duration(s)?)
+}
+
+/// Parses a percentage between `0` and `100`.
+fn parse_percentage(s: &str) -> Result<f64> {
+    let percentage: f64 = s.parse()?;
+    if !(0.0..=100.0).contains(&percentage) {
+        eyre::bail!("the percentage must be between 0 and 100");
+    }
+    Ok(percentage)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use foundry_config::{Chain, InvariantConfig};
     use foundry_test_utils::forgetest_async;
 
+    #[test]
+    fn can_parse_min_coverage() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--min-coverage", "80.5"]);
+        assert_eq!(args.min_coverage, Some(80.5));
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--min-coverage", "101"]).is_err());
+        assert!(TestArgs::try_parse_from(["foundry-cli", "-w", "--min-coverage", "80"]).is_err());
+    }
+
     #[test]
     fn watch_parse() {
         let args: TestArgs = TestArgs::parse_from(["foundry-cli", "-vw"]);
         assert!(args.watch.watch.is_some());
     }
 
+    #[test]
+    fn flamegraph_parse() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--flamegraph", "gas.folded"]);
+        assert_eq!(args.flamegraph, Some(PathBuf::from("gas.folded")));
+    }
+
+    #[test]
+    fn allow_failure_parse() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--allow-failure"]);
+        let outcome = args.outcome(BTreeMap::new()).unwrap();
+        assert!(outcome.allow_failure);
+
+        let args: TestArgs = TestArgs::parse_from([
+            "foundry-cli",
+            "--allow-failure-match",
+            "testFork*",
+            "--allow-failure-match",
+            "*Flaky*",
+        ]);
+        let outcome = args.outcome(BTreeMap::new()).unwrap();
+        assert!(!outcome.allow_failure);
+        assert!(outcome.is_allowed_failure("testForkBalance()"));
+        assert!(outcome.is_allowed_failure("testFlakyOracle()"));
+        assert!(!outcome.is_allowed_failure("testBalance()"));
+    }
+
     #[test]
     fn fuzz_seed() {
         let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--fuzz-seed", "0x10"]);
         assert!(args.fuzz_seed.is_some());
     }
 
+    #[test]
+    fn fuzz_fork_dictionary() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--fuzz-fork-dictionary"]);
+        assert!(args.fuzz_fork_dictionary);
+        let config = args.load_config();
+        assert!(config.fuzz.fork_dictionary);
+    }
+
+    #[test]
+    fn fuzz_max_rejects() {
+        let args = TestArgs::parse_from(["foundry-cli", "--fuzz-max-rejects", "42"]);
+        assert_eq!(args.fuzz_max_rejects, Some(42));
+        let config = args.load_config();
+        assert_eq!(config.fuzz.max_test_rejects, 42);
+    }
+
     // <https://github.com/foundry-rs/foundry/issues/5913>
     #[test]
     fn fuzz_seed_exists() {
@@ -715,6 +1172,15 @@ mod tests {
         assert!(args.fuzz_seed.is_some());
     }
 
+    #[test]
+    fn rpc_cache_ttl() {
+        let args = TestArgs::parse_from(["foundry-cli", "--rpc-cache-ttl", "1h"]);
+        assert_eq!(args.rpc_cache_ttl, Some(Duration::from_secs(3600)));
+        let args = TestArgs::parse_from(["foundry-cli", "--rpc-cache-ttl", "0"]);
+        assert_eq!(args.rpc_cache_ttl, Some(Duration::ZERO));
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--rpc-cache-ttl", "soon"]).is_err());
+    }
+
     #[test]
     fn extract_chain() {
         let test = |arg: &str, expected: Chain| {
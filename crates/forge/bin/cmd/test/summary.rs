@@ -42,6 +42,11 @@ impl TestSummaryReporter {
                     .set_alignment(CellAlignment::Center)
                     .add_attribute(Attribute::Bold),
             );
+            row.add_cell(
+                Cell::new("setUp Gas")
+                    .set_alignment(CellAlignment::Center)
+                    .add_attribute(Attribute::Bold),
+            );
         }
         table.set_header(row);
 
@@ -83,6 +88,8 @@ impl TestSummaryReporter {
             if self.is_detailed {
                 row.add_cell(Cell::new(suite_path));
                 row.add_cell(Cell::new(format!("{:.2?}", suite.duration).to_string()));
+                let setup_gas = suite.setup_gas.map(|gas| gas.to_string()).unwrap_or_default();
+                row.add_cell(Cell::new(setup_gas).set_alignment(CellAlignment::Center));
             }
 
             self.table.add_row(row);
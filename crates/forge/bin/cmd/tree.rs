@@ -1,10 +1,15 @@
-use clap::Parser;
+use clap::{Parser, ValueHint};
 use eyre::Result;
 use foundry_cli::{opts::ProjectPathsArgs, utils::LoadConfig};
 use foundry_compilers::{
     resolver::{parse::SolData, Charset, TreeOptions},
     Graph,
 };
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
 
 /// CLI arguments for `forge tree`.
 #[derive(Clone, Debug, Parser)]
@@ -19,6 +24,14 @@ pub struct TreeArgs {
     #[arg(long, default_value = "utf8")]
     charset: Charset,
 
+    /// Maximum depth of imports to print.
+    #[arg(long, value_name = "DEPTH")]
+    depth: Option<usize>,
+
+    /// Print the files importing the given file instead, i.e. its reverse dependencies.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    invert: Option<PathBuf>,
+
     #[command(flatten)]
     opts: ProjectPathsArgs,
 }
@@ -29,9 +42,144 @@ impl TreeArgs {
     pub fn run(self) -> Result<()> {
         let config = self.try_load_config_emit_warnings()?;
         let graph = Graph::<SolData>::resolve(&config.project_paths())?;
-        let opts = TreeOptions { charset: self.charset, no_dedupe: self.no_dedupe };
-        graph.print_with_options(opts);
+        if self.depth.is_none() && self.invert.is_none() {
+            let opts = TreeOptions { charset: self.charset, no_dedupe: self.no_dedupe };
+            graph.print_with_options(opts);
+            return Ok(());
+        }
+
+        let mut files = graph.files().iter().map(|(path, &idx)| (path, idx)).collect::<Vec<_>>();
+        files.sort_unstable();
+
+        let (roots, edges) = if let Some(file) = &self.invert {
+            let file = dunce::canonicalize(file)?;
+            let Some(&root) = graph.files().get(&file) else {
+                eyre::bail!("{} is not part of the project", file.display());
+            };
+            (vec![root], importers(&graph))
+        } else {
+            let inputs = files
+                .iter()
+                .map(|&(_, idx)| idx)
+                .filter(|&idx| idx < graph.num_input_files())
+                .collect();
+            let mut edges = vec![Vec::new(); graph.files().len()];
+            for &(_, idx) in &files {
+                edges[idx] = graph.imported_nodes(idx).to_vec();
+            }
+            (inputs, edges)
+        };
+
+        let printer = TreePrinter {
+            graph: &graph,
+            root: &config.root.0,
+            edges,
+            charset: self.charset,
+            no_dedupe: self.no_dedupe,
+            depth: self.depth.unwrap_or(usize::MAX),
+        };
+        print!("{}", printer.print(&roots));
 
         Ok(())
     }
 }
+
+/// Returns the files importing every node of the graph, i.e. the reversed edges of the graph,
+/// sorted by path.
+pub fn importers(graph: &Graph<SolData>) -> Vec<Vec<usize>> {
+    let mut files = graph.files().iter().map(|(path, &idx)| (path, idx)).collect::<Vec<_>>();
+    files.sort_unstable();
+
+    let mut edges = vec![Vec::new(); graph.files().len()];
+    for (_, idx) in files {
+        for &import in graph.imported_nodes(idx) {
+            edges[import].push(idx);
+        }
+    }
+    edges
+}
+
+/// Prints a subset of the import graph, following the given edges.
+struct TreePrinter<'a> {
+    graph: &'a Graph<SolData>,
+    /// The project root, paths are printed relative to it.
+    root: &'a Path,
+    /// The edges to follow for every node, indexed by node.
+    edges: Vec<Vec<usize>>,
+    charset: Charset,
+    no_dedupe: bool,
+    depth: usize,
+}
+
+impl TreePrinter<'_> {
+    fn print(&self, roots: &[usize]) -> String {
+        let mut out = String::new();
+        let mut visited = HashSet::new();
+        for &root in roots {
+            self.print_node(&mut out, root, &mut Vec::new(), &mut Vec::new(), &mut visited);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prints the node and its children, `levels` tracks whether every printed ancestor is the
+    /// last child of its parent, and `ancestors` the nodes on the path from the root.
+    fn print_node(
+        &self,
+        out: &mut String,
+        idx: usize,
+        levels: &mut Vec<bool>,
+        ancestors: &mut Vec<usize>,
+        visited: &mut HashSet<usize>,
+    ) {
+        let (vertical, tee, end, blank) = match self.charset {
+            Charset::Utf8 => ("│   ", "├── ", "└── ", "    "),
+            Charset::Ascii => ("|   ", "|-- ", "`-- ", "    "),
+        };
+
+        if let Some((&last, parents)) = levels.split_last() {
+            for &is_last in parents {
+                out.push_str(if is_last { blank } else { vertical });
+            }
+            out.push_str(if last { end } else { tee });
+        }
+
+        let path: &Path = self.graph.node(idx).path();
+        let path = path.strip_prefix(self.root).unwrap_or(path);
+        let _ = write!(out, "{}", path.display());
+        let children = &self.edges[idx];
+        // Shared nodes are only expanded once, and cycles never are.
+        let cyclic = ancestors.contains(&idx);
+        if (visited.contains(&idx) && !self.no_dedupe || cyclic) && !children.is_empty() {
+            out.push_str(" (*)\n");
+            return;
+        }
+        out.push('\n');
+
+        // Nodes cut off by the depth are not marked as visited, so that they are expanded where
+        // they appear at a shallower depth.
+        if levels.len() >= self.depth {
+            return;
+        }
+        visited.insert(idx);
+        ancestors.push(idx);
+        for (i, &child) in children.iter().enumerate() {
+            levels.push(i == children.len() - 1);
+            self.print_node(out, child, levels, ancestors, visited);
+            levels.pop();
+        }
+        ancestors.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_depth_and_invert() {
+        let args = TreeArgs::parse_from(["foundry-cli", "--depth", "1", "--invert", "src/A.sol"]);
+        assert_eq!(args.depth, Some(1));
+        assert_eq!(args.invert, Some(PathBuf::from("src/A.sol")));
+    }
+}
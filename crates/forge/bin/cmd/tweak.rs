@@ -0,0 +1,97 @@
+use alloy_primitives::hex;
+use alloy_provider::Provider;
+use clap::{Parser, ValueHint};
+use eyre::{Context, Result};
+use foundry_cli::opts::RpcOpts;
+use foundry_common::{fs, provider::ProviderBuilder};
+use foundry_config::find_project_root_path;
+use foundry_tweak::{ClonedProject, TweakOptions};
+use std::path::PathBuf;
+
+/// CLI arguments for `forge tweak`.
+///
+/// Generates the runtime code of the on-chain contract of a `forge clone`d project, as it would be
+/// deployed with the current sources of the project, e.g. to inject it into a fork with
+/// `anvil_setCode`.
+#[derive(Clone, Debug, Parser)]
+pub struct TweakArgs {
+    /// The root of the cloned project.
+    ///
+    /// Defaults to the root of the current project.
+    #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    root: Option<PathBuf>,
+
+    /// Skip replaying the transactions preceding the creation transaction in its block.
+    ///
+    /// May generate a different code than the live deployment!
+    #[arg(long, short)]
+    quick: bool,
+
+    /// Write the tweaked code to the given file instead of printing it.
+    #[arg(long, short, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    out: Option<PathBuf>,
+
+    /// Set the tweaked code of the contract on the node at the given RPC URL, with
+    /// `anvil_setCode`.
+    #[arg(long, value_name = "URL")]
+    apply: Option<String>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl TweakArgs {
+    pub async fn run(self) -> Result<()> {
+        let root = match self.root {
+            Some(root) => root,
+            None => find_project_root_path(None)?,
+        };
+        let root = dunce::canonicalize(&root)
+            .wrap_err_with(|| format!("failed to resolve {}", root.display()))?;
+        let project = ClonedProject::load_with_root(&root)
+            .wrap_err_with(|| format!("failed to load the cloned project at {}", root.display()))?;
+        let address = project.metadata.address;
+
+        let code = project.tweaked_code(&self.rpc, &TweakOptions::new(self.quick)).await?;
+        let encoded = hex::encode_prefixed(&code);
+
+        if let Some(out) = &self.out {
+            fs::write(out, &encoded)?;
+            println!("Wrote the tweaked code of {address} to {}", out.display());
+        } else if self.apply.is_none() {
+            println!("{encoded}");
+        }
+
+        if let Some(url) = &self.apply {
+            let provider = ProviderBuilder::new(url).build()?;
+            provider
+                .raw_request::<_, ()>("anvil_setCode".into(), (address, &encoded))
+                .await
+                .wrap_err_with(|| format!("failed to set the code of {address} on {url}"))?;
+            println!("Set the tweaked code of {address} on {url}");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_tweak_args() {
+        let args = TweakArgs::parse_from([
+            "foundry-cli",
+            "--root",
+            "cloned",
+            "--quick",
+            "--apply",
+            "http://localhost:8545",
+        ]);
+        assert_eq!(args.root, Some(PathBuf::from("cloned")));
+        assert!(args.quick);
+        assert_eq!(args.apply.as_deref(), Some("http://localhost:8545"));
+        assert_eq!(args.out, None);
+    }
+}
@@ -8,7 +8,9 @@ use foundry_cli::{handler, utils};
 use foundry_evm::inspectors::cheatcodes::{set_execution_context, ForgeContext};
 
 mod cmd;
-use cmd::{cache::CacheSubcommands, generate::GenerateSubcommands, watch};
+use cmd::{
+    cache::CacheSubcommands, clean, coverage::CoverageArgs, generate::GenerateSubcommands, watch,
+};
 
 mod opts;
 use opts::{Forge, ForgeSubcommand};
@@ -30,6 +32,8 @@ fn main() -> Result<()> {
         ForgeSubcommand::Test(cmd) => {
             if cmd.is_watch() {
                 utils::block_on(watch::watch_test(cmd))
+            } else if cmd.min_coverage.is_some() {
+                utils::block_on(CoverageArgs::from_test_args(cmd).run())
             } else {
                 let outcome = utils::block_on(cmd.run())?;
                 outcome.ensure_ok()
@@ -64,8 +68,10 @@ fn main() -> Result<()> {
         ForgeSubcommand::Update(cmd) => cmd.run(),
         ForgeSubcommand::Install(cmd) => cmd.run(),
         ForgeSubcommand::Remove(cmd) => cmd.run(),
+        ForgeSubcommand::Deps(cmd) => cmd.run(),
         ForgeSubcommand::Remappings(cmd) => cmd.run(),
         ForgeSubcommand::Replay(cmd) => utils::block_on(cmd.run()),
+        ForgeSubcommand::Tweak(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::Init(cmd) => cmd.run(),
         ForgeSubcommand::Completions { shell } => {
             generate(shell, &mut Forge::command(), "forge", &mut std::io::stdout());
@@ -80,10 +86,14 @@ fn main() -> Result<()> {
             );
             Ok(())
         }
-        ForgeSubcommand::Clean { root } => {
+        ForgeSubcommand::Clean { root, targets } => {
             let config = utils::load_config_with_root(root);
             let project = config.project()?;
-            config.cleanup(&project)?;
+            if targets.is_empty() {
+                config.cleanup(&project)?;
+            } else {
+                clean::clean_targets(&project, &targets)?;
+            }
             Ok(())
         }
         ForgeSubcommand::Snapshot(cmd) => {
@@ -121,6 +131,7 @@ fn main() -> Result<()> {
 /// cheatcodes.
 fn init_execution_context(subcommand: &ForgeSubcommand) {
     let context = match subcommand {
+        ForgeSubcommand::Test(cmd) if cmd.min_coverage.is_some() => ForgeContext::Coverage,
         ForgeSubcommand::Test(_) => ForgeContext::Test,
         ForgeSubcommand::Coverage(_) => ForgeContext::Coverage,
         ForgeSubcommand::Snapshot(_) => ForgeContext::Snapshot,
@@ -1,8 +1,9 @@
 use crate::cmd::{
     bind::BindArgs, build::BuildArgs, cache::CacheArgs, clone::CloneArgs, config, coverage,
-    create::CreateArgs, debug::DebugArgs, doc::DocArgs, flatten, fmt::FmtArgs, geiger, generate,
-    init::InitArgs, inspect, install::InstallArgs, remappings::RemappingArgs, remove::RemoveArgs,
-    replay::ReplayArgs, selectors::SelectorsSubcommands, snapshot, soldeer, test, tree, update,
+    create::CreateArgs, debug::DebugArgs, deps::DepsArgs, doc::DocArgs, flatten, fmt::FmtArgs,
+    geiger, generate, init::InitArgs, inspect, install::InstallArgs, remappings::RemappingArgs,
+    remove::RemoveArgs, replay::ReplayArgs, selectors::SelectorsSubcommands, snapshot, soldeer,
+    test, tree, tweak::TweakArgs, update,
 };
 use clap::{Parser, Subcommand, ValueHint};
 use forge_script::ScriptArgs;
@@ -75,6 +76,9 @@ pub enum ForgeSubcommand {
     #[command(visible_alias = "rm")]
     Remove(RemoveArgs),
 
+    /// List the installed dependencies.
+    Deps(DepsArgs),
+
     /// Get the automatically inferred remappings for the project.
     #[command(visible_alias = "re")]
     Remappings(RemappingArgs),
@@ -82,6 +86,10 @@ pub enum ForgeSubcommand {
     #[command(visible_alias = "rp")]
     Replay(ReplayArgs),
 
+    /// Generate the code of the on-chain contract of a cloned project, as deployed with its
+    /// current sources.
+    Tweak(TweakArgs),
+
     /// Verify smart contracts on Etherscan.
     #[command(visible_alias = "v")]
     VerifyContract(VerifyArgs),
@@ -117,6 +125,12 @@ pub enum ForgeSubcommand {
         /// or the current working directory.
         #[arg(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
         root: Option<PathBuf>,
+
+        /// Only remove the artifacts of the given contract names or source files.
+        ///
+        /// By default all artifacts and the cache are removed.
+        #[arg(value_name = "TARGETS")]
+        targets: Vec<String>,
     },
 
     /// Manage the Foundry cache.
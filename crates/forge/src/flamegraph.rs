@@ -0,0 +1,94 @@
+//! Gas flamegraphs.
+
+use crate::traces::{CallTraceArena, CallTraceDecoder, CallTraceNode};
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+/// Collects the gas used by call traces as folded stacks.
+///
+/// Every line of the output is a `;`-separated call stack followed by the gas spent in the
+/// innermost frame itself (excluding its subcalls). This is the format consumed by standard
+/// flamegraph tooling such as `inferno-flamegraph` or `flamegraph.pl`.
+///
+/// Identical stacks are merged, so traces of multiple tests are aggregated per call path, while
+/// the root frame (the test function) keeps the output attributable per test.
+#[derive(Clone, Debug, Default)]
+pub struct GasFlamegraph {
+    /// Gas used by each folded stack.
+    pub stacks: BTreeMap<String, u64>,
+}
+
+impl GasFlamegraph {
+    /// Analyzes the given trace and accumulates its gas usage into the folded stacks.
+    pub async fn analyze(&mut self, arena: &CallTraceArena, decoder: &CallTraceDecoder) {
+        let nodes = arena.nodes();
+
+        let mut frames = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            frames.push(frame_name(node, decoder).await);
+        }
+
+        for node in nodes {
+            let children_gas: u64 =
+                node.children.iter().map(|&child| nodes[child].trace.gas_used).sum();
+            let self_gas = node.trace.gas_used.saturating_sub(children_gas);
+            if self_gas == 0 {
+                continue;
+            }
+
+            let mut stack = vec![frames[node.idx].as_str()];
+            let mut parent = node.parent;
+            while let Some(idx) = parent {
+                stack.push(&frames[idx]);
+                parent = nodes[idx].parent;
+            }
+            stack.reverse();
+
+            *self.stacks.entry(stack.join(";")).or_default() += self_gas;
+        }
+    }
+
+    /// Returns `true` if no gas has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Renders the folded stacks, one per line.
+    pub fn folded(&self) -> String {
+        let mut out = String::new();
+        for (stack, gas) in &self.stacks {
+            let _ = writeln!(out, "{stack} {gas}");
+        }
+        out
+    }
+
+    /// Writes the folded stacks to the given file.
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        foundry_common::fs::write(path, self.folded())?;
+        Ok(())
+    }
+}
+
+/// Returns the name of the frame for the given node, in the form `Contract::function`.
+async fn frame_name(node: &CallTraceNode, decoder: &CallTraceDecoder) -> String {
+    let trace = &node.trace;
+    let contract = decoder
+        .contracts
+        .get(&trace.address)
+        .map(|name| name.rsplit(':').next().unwrap_or(name).to_string())
+        .or_else(|| decoder.labels.get(&trace.address).cloned())
+        .unwrap_or_else(|| trace.address.to_string());
+
+    let function = if trace.kind.is_any_create() {
+        "new".to_string()
+    } else {
+        decoder
+            .decode_function(trace)
+            .await
+            .call_data
+            .map(|call_data| call_data.signature.split('(').next().unwrap_or_default().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    // `;` separates frames and whitespace separates the stack from the sample count.
+    format!("{contract}::{function}").replace([';', ' '], "_")
+}
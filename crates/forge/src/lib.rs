@@ -16,6 +16,8 @@ use std::path::Path;
 
 pub mod coverage;
 
+pub mod flamegraph;
+
 pub mod gas_report;
 
 pub mod multi_runner;
@@ -26,6 +28,7 @@ pub use runner::ContractRunner;
 
 mod progress;
 pub mod result;
+pub mod state;
 
 // TODO: remove
 pub use foundry_common::traits::TestFilter;
@@ -44,6 +47,10 @@ pub struct TestOptions {
     pub inline_fuzz: InlineConfig<FuzzConfig>,
     /// Contains per-test specific "invariant" configurations.
     pub inline_invariant: InlineConfig<InvariantConfig>,
+    /// Contains per-test gas limits, declared with `@custom:gas-limit`.
+    pub inline_gas_limit: InlineConfig<u64>,
+    /// Contains per-test gas snapshot groups, declared with `@custom:snapshot-group`.
+    pub inline_snapshot_group: InlineConfig<String>,
 }
 
 impl TestOptions {
@@ -59,6 +66,8 @@ impl TestOptions {
         let natspecs: Vec<NatSpec> = NatSpec::parse(output, root);
         let mut inline_invariant = InlineConfig::<InvariantConfig>::default();
         let mut inline_fuzz = InlineConfig::<FuzzConfig>::default();
+        let mut inline_gas_limit = InlineConfig::<u64>::default();
+        let mut inline_snapshot_group = InlineConfig::<String>::default();
 
         for natspec in natspecs {
             // Perform general validation
@@ -72,10 +81,20 @@ impl TestOptions {
             let f: &str = &natspec.function;
             let line: String = natspec.debug_context();
 
-            match base_fuzz.try_merge(&configs) {
-                Ok(Some(conf)) => inline_fuzz.insert(c, f, conf),
-                Ok(None) => { /* No inline config found, do nothing */ }
+            let fuzz = match base_fuzz.try_merge(&configs) {
+                Ok(conf) => conf,
                 Err(e) => Err(InlineConfigError { line: line.clone(), source: e })?,
+            };
+            // `@custom:fuzz-runs` takes precedence over the other configs
+            let fuzz = match natspec.fuzz_runs() {
+                Some(Ok(runs)) => {
+                    Some(FuzzConfig { runs, ..fuzz.unwrap_or_else(|| base_fuzz.clone()) })
+                }
+                Some(Err(e)) => Err(InlineConfigError { line: line.clone(), source: e })?,
+                None => fuzz,
+            };
+            if let Some(conf) = fuzz {
+                inline_fuzz.insert(c, f, conf);
             }
 
             match base_invariant.try_merge(&configs) {
@@ -83,9 +102,26 @@ impl TestOptions {
                 Ok(None) => { /* No inline config found, do nothing */ }
                 Err(e) => Err(InlineConfigError { line: line.clone(), source: e })?,
             }
+
+            match natspec.gas_limit() {
+                Some(Ok(limit)) => inline_gas_limit.insert(c, f, limit),
+                Some(Err(e)) => Err(InlineConfigError { line, source: e })?,
+                None => { /* No gas limit found, do nothing */ }
+            }
+
+            if let Some(group) = natspec.snapshot_group() {
+                inline_snapshot_group.insert(c, f, group);
+            }
         }
 
-        Ok(Self { fuzz: base_fuzz, invariant: base_invariant, inline_fuzz, inline_invariant })
+        Ok(Self {
+            fuzz: base_fuzz,
+            invariant: base_invariant,
+            inline_fuzz,
+            inline_invariant,
+            inline_gas_limit,
+            inline_snapshot_group,
+        })
     }
 
     /// Returns a "fuzz" test runner instance. Parameters are used to select tight scoped fuzz
@@ -144,6 +180,24 @@ impl TestOptions {
         self.inline_invariant.get(contract_id, test_fn).unwrap_or(&self.invariant)
     }
 
+    /// Returns the gas limit declared for a contract-function pair, if any.
+    ///
+    /// - `contract_id` is the id of the test contract, expressed as a relative path from the
+    ///   project root.
+    /// - `test_fn` is the name of the test function declared inside the test contract.
+    pub fn gas_limit(&self, contract_id: &str, test_fn: &str) -> Option<u64> {
+        self.inline_gas_limit.get(contract_id, test_fn).copied()
+    }
+
+    /// Returns the gas snapshot group declared for a contract-function pair, if any.
+    ///
+    /// - `contract_id` is the id of the test contract, expressed as a relative path from the
+    ///   project root.
+    /// - `test_fn` is the name of the test function declared inside the test contract.
+    pub fn snapshot_group(&self, contract_id: &str, test_fn: &str) -> Option<&str> {
+        self.inline_snapshot_group.get(contract_id, test_fn).map(String::as_str)
+    }
+
     pub fn fuzzer_with_cases(
         &self,
         cases: u32,
@@ -60,6 +60,10 @@ pub struct MultiContractRunner {
     pub coverage: bool,
     /// Whether to collect debug info
     pub debug: bool,
+    /// Whether to dump the state at the end of unit tests
+    pub dump_state: bool,
+    /// The number of times a failing test is retried
+    pub retries: u32,
     /// Settings related to fuzz and/or invariant tests
     pub test_options: TestOptions,
     /// Whether to enable call isolation
@@ -268,6 +272,8 @@ impl MultiContractRunner {
             initial_balance: self.evm_opts.initial_balance,
             sender: self.sender.unwrap_or_default(),
             debug: self.debug,
+            dump_state: self.dump_state,
+            retries: self.retries,
             progress,
             tokio_handle,
             span,
@@ -299,6 +305,10 @@ pub struct MultiContractRunnerBuilder {
     pub coverage: bool,
     /// Whether or not to collect debug info
     pub debug: bool,
+    /// Whether or not to dump the state at the end of unit tests
+    pub dump_state: bool,
+    /// The number of times a failing test is retried
+    pub retries: u32,
     /// Whether to enable call isolation
     pub isolation: bool,
     /// Settings related to fuzz and/or invariant tests
@@ -315,6 +325,8 @@ impl MultiContractRunnerBuilder {
             fork: Default::default(),
             coverage: Default::default(),
             debug: Default::default(),
+            dump_state: Default::default(),
+            retries: Default::default(),
             isolation: Default::default(),
             test_options: Default::default(),
         }
@@ -355,6 +367,16 @@ impl MultiContractRunnerBuilder {
         self
     }
 
+    pub fn set_dump_state(mut self, enable: bool) -> Self {
+        self.dump_state = enable;
+        self
+    }
+
+    pub fn set_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
     pub fn enable_isolation(mut self, enable: bool) -> Self {
         self.isolation = enable;
         self
@@ -425,6 +447,8 @@ impl MultiContractRunnerBuilder {
             config: self.config,
             coverage: self.coverage,
             debug: self.debug,
+            dump_state: self.dump_state,
+            retries: self.retries,
             test_options: self.test_options.unwrap_or_default(),
             isolation: self.isolation,
             known_contracts,
@@ -440,6 +464,7 @@ pub fn matches_contract(id: &ArtifactId, abi: &JsonAbi, filter: &dyn TestFilter)
 }
 
 /// Returns `true` if the function is a test function that matches the given filter.
-pub(crate) fn is_matching_test(func: &Function, filter: &dyn TestFilter) -> bool {
-    func.is_any_test() && filter.matches_test(&func.signature())
+pub fn is_matching_test(func: &Function, filter: &dyn TestFilter) -> bool {
+    let kind = func.test_function_kind();
+    kind.is_any_test() && filter.matches_test_kind(kind) && filter.matches_test(&func.signature())
 }
@@ -4,14 +4,17 @@ use crate::{
     decode::decode_console_logs,
     fuzz::{BaseCounterExample, FuzzedCases},
     gas_report::GasReport,
+    state::StateDump,
 };
 use alloy_primitives::{Address, Log};
 use eyre::Report;
 use foundry_common::{evm::Breakpoints, get_contract_name, get_file_name, shell};
+use foundry_config::{filter::GlobMatcher, InlineConfig};
 use foundry_evm::{
     coverage::HitMaps,
     executors::{EvmError, RawCallResult},
     fuzz::{CounterExample, FuzzCase, FuzzFixtures, FuzzTestResult},
+    inspectors::cheatcodes::MatchedExpectedRevert,
     traces::{CallTraceArena, CallTraceDecoder, TraceKind, Traces},
 };
 use serde::{Deserialize, Serialize};
@@ -31,6 +34,10 @@ pub struct TestOutcome {
     pub results: BTreeMap<String, SuiteResult>,
     /// Whether to allow test failures without failing the entire test run.
     pub allow_failure: bool,
+    /// The globs of the tests whose failures do not fail the test run, e.g. known flaky tests.
+    ///
+    /// The allowed failures are reported separately from the other failures.
+    pub allow_failure_globs: Vec<GlobMatcher>,
     /// The decoder used to decode traces and logs.
     ///
     /// This is `None` if traces and logs were not decoded.
@@ -39,12 +46,26 @@ pub struct TestOutcome {
     pub last_run_decoder: Option<CallTraceDecoder>,
     /// The gas report, if requested.
     pub gas_report: Option<GasReport>,
+    /// The gas snapshot groups of the tests, declared with `@custom:snapshot-group`.
+    pub snapshot_groups: InlineConfig<String>,
+    /// The tests that were not run because of the filters or because they were not compiled.
+    ///
+    /// Only collected if requested, see [`all_skipped`](Self::all_skipped).
+    pub skipped: Vec<SkippedTest>,
 }
 
 impl TestOutcome {
     /// Creates a new test outcome with the given results.
     pub fn new(results: BTreeMap<String, SuiteResult>, allow_failure: bool) -> Self {
-        Self { results, allow_failure, last_run_decoder: None, gas_report: None }
+        Self {
+            results,
+            allow_failure,
+            allow_failure_globs: Vec::new(),
+            last_run_decoder: None,
+            gas_report: None,
+            snapshot_groups: InlineConfig::default(),
+            skipped: Vec::new(),
+        }
     }
 
     /// Creates a new empty test outcome.
@@ -72,6 +93,43 @@ impl TestOutcome {
         self.results.values().flat_map(|suite| suite.tests())
     }
 
+    /// Returns whether the failure of the test with the given name is allowed by the
+    /// [`allow_failure_globs`](Self::allow_failure_globs).
+    pub fn is_allowed_failure(&self, name: &str) -> bool {
+        self.allow_failure_globs.iter().any(|glob| glob.matcher.is_match(name))
+    }
+
+    /// Returns an iterator over the failing tests whose failures are allowed, with the identifier
+    /// of their suite.
+    pub fn allowed_failures(&self) -> impl Iterator<Item = (&String, &String, &TestResult)> {
+        self.results.iter().flat_map(move |(suite_name, suite)| {
+            suite
+                .failures()
+                .filter(|(name, _)| self.is_allowed_failure(name))
+                .map(move |(name, result)| (suite_name, name, result))
+        })
+    }
+
+    /// Returns an iterator over the failing tests whose failures are not allowed.
+    pub fn disallowed_failures(&self) -> impl Iterator<Item = (&String, &TestResult)> {
+        self.failures().filter(|(name, _)| !self.is_allowed_failure(name))
+    }
+
+    /// Returns all the tests that were not run, i.e. the collected [`skipped`](Self::skipped) tests
+    /// and the ones skipped at runtime, sorted by their identifier.
+    pub fn all_skipped(&self) -> Vec<SkippedTest> {
+        let mut skipped = self.skipped.clone();
+        for (id, suite) in &self.results {
+            skipped.extend(suite.skips().map(|(signature, _)| SkippedTest {
+                id: id.clone(),
+                signature: Some(signature.clone()),
+                reason: SkipReason::SkippedAtRuntime,
+            }));
+        }
+        skipped.sort();
+        skipped
+    }
+
     /// Flattens the test outcome into a list of individual tests.
     // TODO: Replace this with `tests` and make it return `TestRef<'_>`
     pub fn into_tests_cloned(&self) -> impl Iterator<Item = SuiteTestResult> + '_ {
@@ -131,11 +189,17 @@ impl TestOutcome {
         let num_test_suites = self.results.len();
         let suites = if num_test_suites == 1 { "suite" } else { "suites" };
         let total_passed = self.passed();
-        let total_failed = self.failed();
+        let total_failed = self.disallowed_failures().count();
+        let total_allowed = self.allowed_failures().count();
         let total_skipped = self.skipped();
-        let total_tests = total_passed + total_failed + total_skipped;
+        let total_tests = total_passed + total_failed + total_allowed + total_skipped;
+        let allowed = if total_allowed > 0 {
+            format!(", {} allowed failures", total_allowed.yellow())
+        } else {
+            String::new()
+        };
         format!(
-            "\nRan {} test {} in {:.2?} ({:.2?} CPU time): {} tests passed, {} failed, {} skipped ({} total tests)",
+            "\nRan {} test {} in {:.2?} ({:.2?} CPU time): {} tests passed, {} failed{allowed}, {} skipped ({} total tests)",
             num_test_suites,
             suites,
             wall_clock_time,
@@ -150,7 +214,14 @@ impl TestOutcome {
     /// Checks if there are any failures and failures are disallowed.
     pub fn ensure_ok(&self) -> eyre::Result<()> {
         let outcome = self;
-        let failures = outcome.failures().count();
+        let failures = outcome.disallowed_failures().count();
+        if shell::verbosity().is_normal() && outcome.allowed_failures().next().is_some() {
+            shell::println("")?;
+            shell::println("Allowed failures:")?;
+            for (suite_name, name, result) in outcome.allowed_failures() {
+                shell::println(format!("{} in {suite_name}", result.short_result(name)))?;
+            }
+        }
         if outcome.allow_failure || failures == 0 {
             return Ok(());
         }
@@ -163,14 +234,18 @@ impl TestOutcome {
         shell::println("")?;
         shell::println("Failing tests:")?;
         for (suite_name, suite) in outcome.results.iter() {
-            let failed = suite.failed();
+            let failures = suite
+                .failures()
+                .filter(|(name, _)| !outcome.is_allowed_failure(name))
+                .collect::<Vec<_>>();
+            let failed = failures.len();
             if failed == 0 {
                 continue;
             }
 
             let term = if failed > 1 { "tests" } else { "test" };
             shell::println(format!("Encountered {failed} failing {term} in {suite_name}"))?;
-            for (name, result) in suite.failures() {
+            for (name, result) in failures {
                 shell::println(result.short_result(name))?;
             }
             shell::println("")?;
@@ -197,6 +272,10 @@ pub struct SuiteResult {
     pub test_results: BTreeMap<String, TestResult>,
     /// Generated warnings.
     pub warnings: Vec<String>,
+    /// The gas used by `setUp`, if the suite has one.
+    ///
+    /// `setUp` is only run once per suite, and every test starts from the resulting state.
+    pub setup_gas: Option<u64>,
 }
 
 impl SuiteResult {
@@ -205,7 +284,13 @@ impl SuiteResult {
         test_results: BTreeMap<String, TestResult>,
         warnings: Vec<String>,
     ) -> Self {
-        Self { duration, test_results, warnings }
+        Self { duration, test_results, warnings, setup_gas: None }
+    }
+
+    /// Sets the gas used by `setUp`.
+    pub fn with_setup_gas(mut self, setup_gas: Option<u64>) -> Self {
+        self.setup_gas = setup_gas;
+        self
     }
 
     /// Returns an iterator over all individual succeeding tests and their names.
@@ -238,6 +323,11 @@ impl SuiteResult {
         self.failures().count()
     }
 
+    /// Returns the number of reverts expected with `vm.expectRevert` in the tests that passed.
+    pub fn expected_reverts(&self) -> usize {
+        self.successes().map(|(_, t)| t.expected_reverts).sum()
+    }
+
     /// Iterator over all tests and their names
     pub fn tests(&self) -> impl Iterator<Item = (&String, &TestResult)> {
         self.test_results.iter()
@@ -264,8 +354,12 @@ impl SuiteResult {
     pub fn summary(&self) -> String {
         let failed = self.failed();
         let result = if failed == 0 { "ok".green() } else { "FAILED".red() };
+        let expected_reverts = match self.expected_reverts() {
+            0 => String::new(),
+            n => format!("{n} expected reverts; "),
+        };
         format!(
-            "Suite result: {}. {} passed; {} failed; {} skipped; finished in {:.2?} ({:.2?} CPU time)",
+            "Suite result: {}. {} passed; {} failed; {} skipped; {expected_reverts}finished in {:.2?} ({:.2?} CPU time)",
             result,
             self.passed().green(),
             failed.red(),
@@ -307,6 +401,50 @@ impl SuiteTestResult {
     }
 }
 
+/// The reason a test was not run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// The test does not match the test filters.
+    Filtered,
+    /// The test was skipped at runtime, e.g. with `vm.skip`.
+    SkippedAtRuntime,
+    /// The source file of the test was not compiled, e.g. because of the `skip` config or because
+    /// none of its contracts match the filters.
+    CompileExcluded,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Filtered => "filtered",
+            Self::SkippedAtRuntime => "skipped-at-runtime",
+            Self::CompileExcluded => "compile-excluded",
+        })
+    }
+}
+
+/// A test that was not run.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SkippedTest {
+    /// The identifier of the test contract in the form `<path>:<contract name>`, or the path of
+    /// the source file if it was not compiled.
+    pub id: String,
+    /// The signature of the test function, `None` if the source file was not compiled.
+    pub signature: Option<String>,
+    /// Why the test was not run.
+    pub reason: SkipReason,
+}
+
+impl fmt::Display for SkippedTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.signature {
+            Some(signature) => write!(f, "{}:{signature} ({})", self.id, self.reason),
+            None => write!(f, "{} ({})", self.id, self.reason),
+        }
+    }
+}
+
 /// The status of a test.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TestStatus {
@@ -357,6 +495,9 @@ pub struct TestResult {
     pub logs: Vec<Log>,
 
     /// The decoded DSTest logging events and Hardhat's `console.log` from [logs](Self::logs).
+    ///
+    /// These are kept in emission order, with `console.log` format strings already resolved, and
+    /// are what is printed under `Logs:` and serialized in the `--json` output.
     pub decoded_logs: Vec<String>,
 
     /// What kind of test this was
@@ -381,6 +522,21 @@ pub struct TestResult {
 
     /// pc breakpoint char map
     pub breakpoints: Breakpoints,
+
+    /// The state at the end of the test, if requested. Only set for unit tests.
+    #[serde(skip)]
+    pub state: Option<StateDump>,
+
+    /// The number of times the test was retried after failing, see `--retries`.
+    pub retries: u32,
+
+    /// The number of reverts of the test which were expected with `vm.expectRevert`. Only set
+    /// for unit tests.
+    pub expected_reverts: usize,
+
+    /// The reverts expected with `vm.expectRevert`, used to annotate them in the traces.
+    #[serde(skip)]
+    pub matched_expected_reverts: Vec<MatchedExpectedRevert>,
 }
 
 impl fmt::Display for TestResult {
@@ -458,6 +614,7 @@ impl TestResult {
     pub fn single_fail(mut self, err: EvmError) -> Self {
         self.status = TestStatus::Failure;
         self.reason = Some(err.to_string());
+        self.decoded_logs = decode_console_logs(&self.logs);
         self
     }
 
@@ -484,7 +641,13 @@ impl TestResult {
         };
         self.reason = reason;
         self.decoded_logs = decode_console_logs(&self.logs);
-        self.breakpoints = raw_call_result.cheatcodes.map(|c| c.breakpoints).unwrap_or_default();
+        if let Some(cheatcodes) = raw_call_result.cheatcodes {
+            self.breakpoints = cheatcodes.breakpoints;
+            self.expected_reverts = cheatcodes.matched_expected_reverts.len();
+            self.matched_expected_reverts = cheatcodes.matched_expected_reverts;
+        } else {
+            self.breakpoints = Default::default();
+        }
         self.duration = Duration::default();
         self.gas_report_traces = Vec::new();
         self
@@ -496,6 +659,7 @@ impl TestResult {
         self.kind = TestKind::Fuzz {
             median_gas: result.median_gas(false),
             mean_gas: result.mean_gas(false),
+            max_gas: result.max_gas(false),
             first_case: result.first_case,
             runs: result.gas_by_case.len(),
         };
@@ -581,6 +745,17 @@ impl TestResult {
         self
     }
 
+    /// Fails a successful test if the gas it used exceeds the given limit.
+    ///
+    /// For fuzz tests the median gas is compared. Invariant tests are not affected.
+    pub fn enforce_gas_limit(&mut self, limit: u64) {
+        let gas = self.kind.report().gas();
+        if self.status == TestStatus::Success && gas > limit {
+            self.status = TestStatus::Failure;
+            self.reason = Some(format!("gas limit exceeded: used {gas}, limit is {limit}"));
+        }
+    }
+
     /// Returns `true` if this is the result of a fuzz test
     pub fn is_fuzz(&self) -> bool {
         matches!(self.kind, TestKind::Fuzz { .. })
@@ -588,7 +763,16 @@ impl TestResult {
 
     /// Formats the test result into a string (for printing).
     pub fn short_result(&self, name: &str) -> String {
-        format!("{self} {name} {}", self.kind.report())
+        let mut s = format!("{self} {name} {}", self.kind.report());
+        if self.retries > 0 {
+            let attempts = self.retries + 1;
+            let retried = match self.status {
+                TestStatus::Failure => format!(" [failed {attempts} attempts]"),
+                _ => format!(" [flaky: passed after {attempts} attempts]"),
+            };
+            s.push_str(&retried.yellow().to_string());
+        }
+        s
     }
 
     /// Function to merge given coverage in current test result coverage.
@@ -650,6 +834,9 @@ pub enum TestKind {
         runs: usize,
         mean_gas: u64,
         median_gas: u64,
+        /// The highest gas used by a run
+        #[serde(default)]
+        max_gas: u64,
     },
     /// An invariant test.
     Invariant { runs: usize, calls: usize, reverts: usize },
@@ -666,7 +853,7 @@ impl TestKind {
     pub fn report(&self) -> TestKindReport {
         match *self {
             Self::Unit { gas } => TestKindReport::Unit { gas },
-            Self::Fuzz { first_case: _, runs, mean_gas, median_gas } => {
+            Self::Fuzz { first_case: _, runs, mean_gas, median_gas, max_gas: _ } => {
                 TestKindReport::Fuzz { runs, mean_gas, median_gas }
             }
             Self::Invariant { runs, calls, reverts } => {
@@ -692,6 +879,8 @@ pub struct TestSetup {
     pub coverage: Option<HitMaps>,
     /// Defined fuzz test fixtures
     pub fuzz_fixtures: FuzzFixtures,
+    /// The gas used by the `setUp` call, excluding the call stipend
+    pub gas_used: u64,
 }
 
 impl TestSetup {
@@ -726,7 +915,16 @@ impl TestSetup {
         coverage: Option<HitMaps>,
         fuzz_fixtures: FuzzFixtures,
     ) -> Self {
-        Self { address, logs, traces, labeled_addresses, reason: None, coverage, fuzz_fixtures }
+        Self {
+            address,
+            logs,
+            traces,
+            labeled_addresses,
+            reason: None,
+            coverage,
+            fuzz_fixtures,
+            gas_used: 0,
+        }
     }
 
     pub fn failed_with(
@@ -743,6 +941,7 @@ impl TestSetup {
             reason: Some(reason),
             coverage: None,
             fuzz_fixtures: FuzzFixtures::default(),
+            gas_used: 0,
         }
     }
 
@@ -4,7 +4,8 @@ use crate::{
     fuzz::{invariant::BasicTxDetails, BaseCounterExample},
     multi_runner::{is_matching_test, TestContract},
     progress::{start_fuzz_progress, TestsProgress},
-    result::{SuiteResult, TestResult, TestSetup},
+    result::{SuiteResult, TestResult, TestSetup, TestStatus},
+    state::StateDump,
     TestFilter, TestOptions,
 };
 use alloy_dyn_abi::DynSolValue;
@@ -67,6 +68,10 @@ pub struct ContractRunner<'a> {
     pub sender: Address,
     /// Whether debug traces should be generated.
     pub debug: bool,
+    /// Whether to dump the state at the end of unit tests.
+    pub dump_state: bool,
+    /// The number of times a failing test is retried.
+    pub retries: u32,
     /// Overall test run progress.
     pub progress: Option<&'a TestsProgress>,
     /// The handle to the tokio runtime.
@@ -151,6 +156,11 @@ impl<'a> ContractRunner<'a> {
         let result = if call_setup {
             trace!("calling setUp");
             let res = self.executor.setup(None, address, Some(self.revert_decoder));
+            let gas_used = match &res {
+                Ok(raw) => raw.gas_used.wrapping_sub(raw.stipend),
+                Err(EvmError::Execution(err)) => err.raw.gas_used.wrapping_sub(err.raw.stipend),
+                Err(_) => 0,
+            };
             let (setup_logs, setup_traces, labeled_addresses, reason, coverage) = match res {
                 Ok(RawCallResult { traces, labels, logs, coverage, .. }) => {
                     trace!(%address, "successfully called setUp");
@@ -178,6 +188,7 @@ impl<'a> ContractRunner<'a> {
                 reason,
                 coverage,
                 fuzz_fixtures: self.fuzz_fixtures(address),
+                gas_used,
             }
         } else {
             TestSetup::success(
@@ -318,6 +329,7 @@ impl<'a> ContractRunner<'a> {
 
         self.executor.inspector_mut().tracer = prev_tracer;
 
+        let setup_gas = call_setup.then_some(setup.gas_used);
         if setup.reason.is_some() {
             // The setup failed, so we return a single test result for `setUp`
             return SuiteResult::new(
@@ -325,6 +337,7 @@ impl<'a> ContractRunner<'a> {
                 [("setUp()".to_string(), TestResult::setup_fail(setup))].into(),
                 warnings,
             )
+            .with_setup_gas(setup_gas)
         }
 
         // Filter out functions sequentially since it's very fast and there is no need to do it
@@ -369,8 +382,7 @@ impl<'a> ContractRunner<'a> {
                 )
                 .entered();
 
-                let setup = setup.clone();
-                let mut res = match kind {
+                let run = |setup: TestSetup| match kind {
                     TestFunctionKind::UnitTest { should_fail } => {
                         self.run_unit_test(func, should_fail, setup)
                     }
@@ -397,6 +409,18 @@ impl<'a> ContractRunner<'a> {
                     _ => unreachable!(),
                 };
 
+                // Retry failing tests, e.g. the fork tests failing because of an RPC error
+                let mut res = run(setup.clone());
+                while res.status == TestStatus::Failure && res.retries < self.retries {
+                    let retries = res.retries + 1;
+                    debug!(retries, reason = ?res.reason, "retrying failed test");
+                    res = run(setup.clone());
+                    res.retries = retries;
+                }
+                if let Some(limit) = test_options.gas_limit(self.name, &func.name) {
+                    res.enforce_gas_limit(limit);
+                }
+
                 res.duration = start.elapsed();
 
                 (sig, res)
@@ -404,7 +428,7 @@ impl<'a> ContractRunner<'a> {
             .collect::<BTreeMap<_, _>>();
 
         let duration = start.elapsed();
-        SuiteResult::new(duration, test_results, warnings)
+        SuiteResult::new(duration, test_results, warnings).with_setup_gas(setup_gas)
     }
 
     /// Runs a single unit test.
@@ -420,7 +444,7 @@ impl<'a> ContractRunner<'a> {
         setup: TestSetup,
     ) -> TestResult {
         let address = setup.address;
-        let test_result = TestResult::new(setup);
+        let mut test_result = TestResult::new(setup);
 
         // Run unit test
         let (mut raw_call_result, reason) = match self.executor.call(
@@ -437,6 +461,11 @@ impl<'a> ContractRunner<'a> {
             Err(err) => return test_result.single_fail(err),
         };
 
+        if self.dump_state {
+            test_result.state =
+                Some(StateDump::new(self.executor.backend(), &raw_call_result.state_changeset));
+        }
+
         let success =
             self.executor.is_raw_call_mut_success(address, &mut raw_call_result, should_fail);
         test_result.single_result(success, reason, raw_call_result)
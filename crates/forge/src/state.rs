@@ -0,0 +1,167 @@
+//! Dumps of the EVM state at the end of a test.
+
+use alloy_primitives::{Address, Bytes, U256};
+use foundry_evm::{
+    backend::Backend,
+    revm::db::{AccountState, CacheDB},
+    utils::StateChangeset,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// An account of a [`StateDump`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDumpAccount {
+    pub nonce: u64,
+    pub balance: U256,
+    pub code: Bytes,
+    pub storage: BTreeMap<U256, U256>,
+}
+
+/// The accounts known to the EVM at the end of a test, in the format loaded by
+/// `anvil --load-state`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    pub accounts: BTreeMap<Address, StateDumpAccount>,
+}
+
+impl StateDump {
+    /// Dumps the accounts of the active database of the backend, i.e. the accounts created by the
+    /// test setup and, on a fork, the accounts fetched from it, with the changes of the test
+    /// applied.
+    pub fn new(backend: &Backend, changeset: &StateChangeset) -> Self {
+        let mut dump = match backend.active_fork_db() {
+            Some(db) => Self::from_db(db),
+            None => Self::from_db(backend.mem_db()),
+        };
+        dump.apply(changeset);
+        dump
+    }
+
+    fn from_db<ExtDB>(db: &CacheDB<ExtDB>) -> Self {
+        let accounts = db
+            .accounts
+            .iter()
+            .filter(|(_, account)| !matches!(account.account_state, AccountState::NotExisting))
+            .map(|(address, account)| {
+                let code = account
+                    .info
+                    .code
+                    .as_ref()
+                    .or_else(|| db.contracts.get(&account.info.code_hash))
+                    .map(|code| code.original_bytes())
+                    .unwrap_or_default();
+                let record = StateDumpAccount {
+                    nonce: account.info.nonce,
+                    balance: account.info.balance,
+                    code,
+                    storage: account.storage.iter().map(|(k, v)| (*k, *v)).collect(),
+                };
+                (*address, record)
+            })
+            .collect();
+        Self { accounts }
+    }
+
+    /// Applies the changes of a call to the dumped accounts.
+    pub fn apply(&mut self, changeset: &StateChangeset) {
+        for (address, account) in changeset {
+            if !account.is_touched() {
+                continue
+            }
+            if account.is_selfdestructed() {
+                self.accounts.remove(address);
+                continue
+            }
+
+            let record = self.accounts.entry(*address).or_default();
+            record.nonce = account.info.nonce;
+            record.balance = account.info.balance;
+            if let Some(code) = &account.info.code {
+                record.code = code.original_bytes();
+            }
+            record.storage.extend(account.storage.iter().map(|(k, v)| (*k, v.present_value)));
+        }
+    }
+
+    /// Merges the accounts of another dump into this one, overwriting the accounts and storage
+    /// slots of both.
+    pub fn merge(&mut self, other: Self) {
+        for (address, account) in other.accounts {
+            match self.accounts.get_mut(&address) {
+                Some(record) => {
+                    record.nonce = account.nonce;
+                    record.balance = account.balance;
+                    record.code = account.code;
+                    record.storage.extend(account.storage);
+                }
+                None => {
+                    self.accounts.insert(address, account);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use foundry_evm::revm::primitives::{Account, AccountInfo, Bytecode, EvmStorageSlot, HashMap};
+
+    #[test]
+    fn applies_the_changes_of_the_test() {
+        let kept = address!("00000000000000000000000000000000000000aa");
+        let changed = address!("00000000000000000000000000000000000000bb");
+        let destroyed = address!("00000000000000000000000000000000000000cc");
+
+        let mut backend = Backend::spawn(None);
+        for address in [kept, changed, destroyed] {
+            backend.insert_account_info(
+                address,
+                AccountInfo {
+                    balance: U256::from(1),
+                    code: Some(Bytecode::new_raw(Bytes::from_static(&[0x00]))),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(2), ..Default::default() });
+        account.storage = HashMap::from([(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(3)),
+        )]);
+        account.mark_touch();
+        let mut selfdestructed = Account::from(AccountInfo::default());
+        selfdestructed.mark_touch();
+        selfdestructed.mark_selfdestruct();
+
+        let changeset = HashMap::from([(changed, account), (destroyed, selfdestructed)]);
+        let dump = StateDump::new(&backend, &changeset);
+
+        assert_eq!(dump.accounts[&kept].balance, U256::from(1));
+        assert_eq!(dump.accounts[&kept].code, Bytes::from_static(&[0x00]));
+        let changed = &dump.accounts[&changed];
+        assert_eq!(changed.balance, U256::from(2));
+        assert_eq!(changed.code, Bytes::from_static(&[0x00]));
+        assert_eq!(changed.storage, BTreeMap::from([(U256::from(1), U256::from(3))]));
+        assert!(!dump.accounts.contains_key(&destroyed));
+    }
+
+    #[test]
+    fn merges_dumps() {
+        let address = address!("00000000000000000000000000000000000000aa");
+        let account = |balance: u64, slot: u64| StateDumpAccount {
+            balance: U256::from(balance),
+            storage: BTreeMap::from([(U256::from(slot), U256::from(slot))]),
+            ..Default::default()
+        };
+
+        let mut dump = StateDump { accounts: BTreeMap::from([(address, account(1, 1))]) };
+        dump.merge(StateDump { accounts: BTreeMap::from([(address, account(2, 2))]) });
+        assert_eq!(dump.accounts[&address].balance, U256::from(2));
+        assert_eq!(dump.accounts[&address].storage.len(), 2);
+    }
+}
@@ -1,6 +1,10 @@
-use foundry_common::fs::read_json_file;
-use foundry_config::Config;
-use foundry_test_utils::forgetest;
+use foundry_common::{compile::ProjectCompiler, fs::read_json_file};
+use foundry_compilers::PathStyle;
+use foundry_config::{BuildConfig, Config};
+use foundry_test_utils::{
+    forgetest,
+    util::{TestProject, OTHER_SOLC_VERSION, SOLC_VERSION},
+};
 use globset::Glob;
 use std::{collections::BTreeMap, path::PathBuf};
 
@@ -35,6 +39,67 @@ contract Dummy {
     similar_asserts::assert_eq!(output, expected);
 });
 
+// tests that only solc-shaped diagnostics are printed when --format-json-errors is passed
+forgetest!(compile_json_errors, |prj, cmd| {
+    prj.add_source(
+        "jsonError",
+        r"
+contract Dummy {
+    uint256 public number;
+    function something(uint256 newNumber) public {
+        number = newnumber; // error here
+    }
+}
+",
+    )
+    .unwrap();
+
+    cmd.args(["compile", "--format-json-errors"]);
+
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    let errors = output["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["severity"], "error");
+    assert_eq!(errors[0]["sourceLocation"]["file"], "src/jsonError.sol");
+    assert!(errors[0]["formattedMessage"].as_str().unwrap().contains("newnumber"));
+    assert!(output.get("contracts").is_none());
+});
+
+// tests that --build-info writes the full compiler input and output, named after the input hash
+forgetest_init!(can_emit_build_info, |prj, cmd| {
+    cmd.args(["build", "--build-info", "--force"]).assert_success();
+
+    let build_info_dir = prj.root().join("out/build-info");
+    let files = std::fs::read_dir(&build_info_dir).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(!files.is_empty());
+    for file in files {
+        let path = file.path();
+        let build_info: serde_json::Value = read_json_file(&path).unwrap();
+        assert_eq!(build_info["id"].as_str(), path.file_stem().and_then(|stem| stem.to_str()));
+        assert!(build_info["input"]["sources"].is_object(), "{}", path.display());
+        assert!(build_info["output"]["contracts"].is_object(), "{}", path.display());
+    }
+});
+
+// tests that --bytecode-hash overrides the config and is recorded in the build info
+forgetest_init!(can_override_bytecode_hash, |prj, cmd| {
+    cmd.args(["build", "--build-info", "--force", "--bytecode-hash", "none"]).assert_success();
+
+    let build_info_dir = prj.root().join("out/build-info");
+    for file in std::fs::read_dir(&build_info_dir).unwrap() {
+        let path = file.unwrap().path();
+        let build_info: serde_json::Value = read_json_file(&path).unwrap();
+        let metadata = &build_info["input"]["settings"]["metadata"];
+        assert_eq!(metadata["bytecodeHash"], "none", "{}", path.display());
+    }
+
+    let artifact: serde_json::Value =
+        read_json_file(&prj.root().join("out/Counter.sol/Counter.json")).unwrap();
+    let code = artifact["deployedBytecode"]["object"].as_str().unwrap();
+    // The CBOR metadata does not contain the `ipfs` key
+    assert!(!code.contains("6970667358"), "{code}");
+});
+
 // tests build output is as expected
 forgetest_init!(exact_build_output, |prj, cmd| {
     cmd.args(["build", "--force"]);
@@ -42,6 +107,27 @@ forgetest_init!(exact_build_output, |prj, cmd| {
     assert!(stdout.contains("Compiling"), "\n{stdout}");
 });
 
+// tests that --silent only prints the errors of a failed build
+forgetest_init!(can_build_silent, |prj, cmd| {
+    cmd.args(["build", "--force", "--silent"]);
+    assert_eq!(cmd.stdout_lossy(), "");
+
+    prj.add_source(
+        "Broken",
+        r"
+contract Broken {
+    function get() public returns (uint256) {
+        return missing;
+    }
+}
+",
+    )
+    .unwrap();
+    cmd.assert_err();
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("missing"), "\n{stderr}");
+});
+
 // tests build output is as expected
 forgetest_init!(build_sizes_no_forge_std, |prj, cmd| {
     cmd.args(["build", "--sizes"]);
@@ -51,6 +137,21 @@ forgetest_init!(build_sizes_no_forge_std, |prj, cmd| {
     assert!(stdout.contains("Counter"), "\n{stdout}");
 });
 
+// tests that the sizes can be printed as JSON, with the runtime and init code sizes split
+forgetest_init!(can_build_sizes_json, |prj, cmd| {
+    cmd.args(["build", "--sizes", "--json"]);
+    let stdout = cmd.stdout_lossy();
+    let sizes: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let counter = &sizes["Counter"];
+    let runtime_size = counter["runtime_size"].as_i64().unwrap();
+    let init_size = counter["init_size"].as_i64().unwrap();
+    assert!(runtime_size > 0 && init_size > runtime_size, "\n{stdout}");
+    assert_eq!(counter["runtime_margin"].as_i64().unwrap(), 24576 - runtime_size);
+    assert_eq!(counter["init_margin"].as_i64().unwrap(), 49152 - init_size);
+    assert!(sizes.get("CounterTest").is_none(), "\n{stdout}");
+    assert!(sizes.get("console").is_none(), "\n{stdout}");
+});
+
 // tests that skip key in config can be used to skip non-compilable contract
 forgetest_init!(test_can_skip_contract, |prj, cmd| {
     prj.add_source(
@@ -77,3 +178,220 @@ contract ValidContract {}
 
     cmd.args(["build"]).assert_success();
 });
+
+// tests that `--skip-tests` does not compile the tests, unless they are imported by the sources
+forgetest!(can_build_skip_tests, |prj, cmd| {
+    prj.add_source(
+        "Counter.sol",
+        r#"
+import {Helper} from "../test/Helper.sol";
+
+contract Counter {
+    function help() external pure returns (uint256) {
+        return Helper.one();
+    }
+}
+"#,
+    )
+    .unwrap();
+    prj.add_test(
+        "Helper.sol",
+        r#"
+library Helper {
+    function one() internal pure returns (uint256) {
+        return 1;
+    }
+}
+"#,
+    )
+    .unwrap();
+    prj.add_test(
+        "Counter.t.sol",
+        r#"
+import {Missing} from "missing-test-dependency/Missing.sol";
+
+contract CounterTest {}
+"#,
+    )
+    .unwrap();
+
+    cmd.arg("build").assert_err();
+
+    cmd.forge_fuse().args(["build", "--skip-tests"]).assert_success();
+    assert!(prj.artifacts().join("Counter.sol/Counter.json").exists());
+    assert!(prj.artifacts().join("Helper.sol/Helper.json").exists());
+    assert!(!prj.artifacts().join("Counter.t.sol").exists());
+});
+
+// tests that `forge build` reports contracts with the same name in different files
+forgetest!(can_detect_duplicate_contract_names, |prj, cmd| {
+    prj.add_source("Token.sol", "contract Token {}").unwrap();
+    prj.add_source("legacy/OldToken.sol", "contract Token {}").unwrap();
+    prj.add_source("Other.sol", "contract Other {}").unwrap();
+
+    cmd.arg("build");
+    let (_, stderr) = cmd.unchecked_output_lossy();
+    assert!(stderr.contains("Contracts with duplicate names found"), "{stderr}");
+    assert!(stderr.contains("Token: src/Token.sol, src/legacy/OldToken.sol"), "{stderr}");
+    assert!(!stderr.contains("Other"), "{stderr}");
+
+    cmd.forge_fuse().args(["build", "--deny-duplicate-names"]).assert_err();
+    let err = cmd.stderr_lossy();
+    assert!(err.contains("Token: src/Token.sol, src/legacy/OldToken.sol"), "{err}");
+});
+
+// tests that `--multi-version` compiles every source with its own version, ignoring the pinned one
+forgetest!(can_build_multi_version, |prj, cmd| {
+    prj.add_raw_source(
+        "A",
+        &format!(
+            "// SPDX-License-Identifier: MIT\npragma solidity ={SOLC_VERSION};\ncontract A {{}}"
+        ),
+    )
+    .unwrap();
+    prj.add_raw_source(
+        "B",
+        &format!(
+            "// SPDX-License-Identifier: MIT\npragma solidity ={OTHER_SOLC_VERSION};\ncontract B {{}}"
+        ),
+    )
+    .unwrap();
+    prj.write_config(Config { solc: Some(SOLC_VERSION.into()), ..Default::default() });
+
+    cmd.arg("build").assert_err();
+
+    cmd.forge_fuse().args(["build", "--multi-version"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains(&format!("Compiler {SOLC_VERSION}: 1 contract(s)")), "{stdout}");
+    assert!(stdout.contains(&format!("Compiler {OTHER_SOLC_VERSION}: 1 contract(s)")), "{stdout}");
+});
+
+// tests that several projects can be compiled concurrently, independently of the current dir
+#[test]
+fn can_compile_projects_concurrently() {
+    let projects = ["A", "B"].map(|name| {
+        let prj = TestProject::new("can_compile_projects_concurrently", PathStyle::Dapptools);
+        prj.add_source(name, &format!("contract {name} {{}}")).unwrap();
+        (name, prj)
+    });
+
+    std::thread::scope(|s| {
+        let handles = projects
+            .iter()
+            .map(|(name, prj)| {
+                s.spawn(move || {
+                    let project = Config::with_root(prj.root()).sanitized().project().unwrap();
+                    let output =
+                        ProjectCompiler::new().quiet(true).compile_with_paths(&project).unwrap();
+                    let names = output.artifact_ids().map(|(id, _)| id.name).collect::<Vec<_>>();
+                    (*name, names)
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            let (name, names) = handle.join().unwrap();
+            assert_eq!(names, [name]);
+        }
+    });
+}
+
+// tests that `--define` is substituted in the remappings and recorded in the build info
+forgetest!(can_build_with_defines, |prj, cmd| {
+    prj.create_file(
+        "configs/mainnet/Params.sol",
+        "// SPDX-License-Identifier: MIT\nlibrary Params { uint256 constant CHAIN_ID = 1; }",
+    );
+    prj.add_source(
+        "Configured",
+        r#"
+import {Params} from "@config/Params.sol";
+contract Configured {
+    uint256 public chainId = Params.CHAIN_ID;
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["build", "--remappings", "@config/=configs/${NETWORK}/"]);
+    cmd.assert_err();
+
+    cmd.args(["--define", "NETWORK=mainnet", "--build-info"]).assert_success();
+
+    let build_info_dir = prj.root().join("out/build-info");
+    let build_info = std::fs::read_dir(build_info_dir).unwrap().next().unwrap().unwrap().path();
+    let build_info: serde_json::Value = read_json_file(&build_info).unwrap();
+    assert_eq!(build_info["defines"], serde_json::json!({ "NETWORK": "mainnet" }));
+});
+
+// tests that `--ast-json-out` writes the AST of every source along with a manifest
+forgetest!(can_build_ast_json_out, |prj, cmd| {
+    prj.add_source("Lib", "// SPDX-License-Identifier: MIT\nlibrary Lib {}").unwrap();
+    prj.add_source(
+        "Counter",
+        r#"
+import {Lib} from "./Lib.sol";
+contract Counter {
+    uint256 public number;
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["build", "--ast-json-out"]).assert_success();
+
+    let asts = prj.root().join("out/asts");
+    let manifest: serde_json::Value = read_json_file(&asts.join("manifest.json")).unwrap();
+    let entry = &manifest["src/Counter.sol"];
+    assert_eq!(entry["ast"], "src/Counter.sol.json");
+    assert!(entry["version"].is_string(), "{manifest}");
+    assert!(manifest["src/Lib.sol"].is_object(), "{manifest}");
+
+    let ast: serde_json::Value = read_json_file(&asts.join("src/Counter.sol.json")).unwrap();
+    assert_eq!(ast["nodeType"], "SourceUnit");
+    assert_eq!(ast["absolutePath"], "src/Counter.sol");
+});
+
+// tests that builds to separate artifacts folders keep separate caches
+forgetest!(can_build_to_separate_out_dirs, |prj, cmd| {
+    prj.add_source("Counter", "contract Counter { uint256 public number; }").unwrap();
+
+    for out in ["out-a", "out-b"] {
+        cmd.forge_fuse().args(["build", "--out", out]);
+        let output = cmd.stdout_lossy();
+        assert!(output.contains("Compiler run successful"), "{output}");
+        assert!(prj.root().join(out).join("Counter.sol/Counter.json").exists());
+        assert!(prj.root().join(out).join("cache/solidity-files-cache.json").exists());
+    }
+    assert!(!prj.root().join("cache/solidity-files-cache.json").exists());
+
+    cmd.forge_fuse().args(["build", "--out", "out-a"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("No files changed, compilation skipped"), "{output}");
+
+    cmd.forge_fuse().args(["inspect", "Counter", "abi", "--out", "out-b"]);
+    assert!(cmd.stdout_lossy().contains("number"));
+});
+
+// tests that the prebuild hook runs before the compilation and fails the build if it fails
+#[cfg(not(target_os = "windows"))]
+forgetest!(can_run_prebuild_hook, |prj, cmd| {
+    let hook = "mkdir -p src && echo 'contract Generated {}' > src/Generated.sol && echo generated";
+    prj.write_config(Config {
+        build: BuildConfig { prebuild: Some(hook.into()) },
+        ..Default::default()
+    });
+
+    cmd.arg("build");
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("generated"), "{output}");
+    assert!(output.contains("Compiler run successful"), "{output}");
+    assert!(prj.root().join("out/Generated.sol/Generated.json").exists());
+
+    prj.write_config(Config {
+        build: BuildConfig { prebuild: Some("exit 3".into()) },
+        ..Default::default()
+    });
+    cmd.forge_fuse().arg("build");
+    let (_, stderr) = cmd.unchecked_output_lossy();
+    assert!(stderr.contains("the prebuild hook `exit 3` exited with code 3"), "{stderr}");
+});
@@ -13,6 +13,7 @@ use foundry_test_utils::{
 };
 use semver::Version;
 use std::{
+    collections::BTreeMap,
     env, fs,
     path::{Path, PathBuf},
     process::{Command, Stdio},
@@ -106,6 +107,40 @@ forgetest!(
     }
 );
 
+// checks that `cache clean --rpc-only` can be invoked and only cleans the foundry rpc cache
+// this test is not isolated and modifies ~ so it is ignored
+forgetest!(
+    #[ignore]
+    can_cache_clean_rpc_only,
+    |_prj, cmd| {
+        let rpc_cache_dir = Config::foundry_rpc_cache_dir().unwrap();
+        let etherscan_cache_dir = Config::foundry_etherscan_cache_dir().unwrap();
+        let rpc_path = rpc_cache_dir.as_path();
+        let etherscan_path = etherscan_cache_dir.as_path();
+        fs::create_dir_all(rpc_path).unwrap();
+        fs::create_dir_all(etherscan_path).unwrap();
+        cmd.args(["cache", "clean", "--rpc-only"]);
+        cmd.assert_empty_stdout();
+
+        assert!(!rpc_path.exists());
+        assert!(etherscan_path.exists());
+
+        Config::clean_foundry_cache().unwrap();
+    }
+);
+
+// checks that `cache clean --compiler-only` only cleans the compiler cache of the project
+forgetest_init!(can_cache_clean_compiler_only, |prj, cmd| {
+    cmd.arg("build").assert_success();
+    assert!(prj.cache().exists());
+    assert!(prj.artifacts().exists());
+
+    cmd.forge_fuse().args(["cache", "clean", "--compiler-only"]);
+    cmd.assert_empty_stdout();
+    assert!(!prj.cache().exists());
+    assert!(!prj.artifacts().exists());
+});
+
 // checks that `cache clean all --etherscan` can be invoked and only cleans the foundry etherscan
 // cache. This test is not isolated and modifies ~ so it is ignored
 forgetest!(
@@ -253,10 +288,20 @@ forgetest!(can_init_no_git, |prj, cmd| {
     prj.assert_config_exists();
 
     assert!(!prj.root().join(".git").exists());
+    assert!(prj.root().join(".gitignore").exists());
     assert!(prj.root().join("lib/forge-std").exists());
     assert!(!prj.root().join("lib/forge-std/.git").exists());
 });
 
+// Checks that a template can't be used without git
+forgetest!(can_not_init_template_no_git, |prj, cmd| {
+    prj.wipe();
+
+    cmd.arg("init").arg(prj.root()).args(["--template", "foundry-rs/forge-template", "--no-git"]);
+    cmd.assert_err();
+    assert!(!prj.root().join(".git").exists());
+});
+
 // Checks that quiet mode does not print anything
 forgetest!(can_init_quiet, |prj, cmd| {
     prj.wipe();
@@ -561,6 +606,27 @@ forgetest_init!(can_clean_config, |prj, cmd| {
     assert!(!artifact.exists());
 });
 
+// checks that `clean` only removes the artifacts of the given targets
+forgetest_init!(can_clean_targets, |prj, cmd| {
+    cmd.arg("build");
+    cmd.assert_non_empty_stdout();
+
+    let test_artifact = prj.root().join(format!("out/{TEMPLATE_TEST_CONTRACT_ARTIFACT_JSON}"));
+    let artifact = prj.root().join(format!("out/{TEMPLATE_CONTRACT_ARTIFACT_JSON}"));
+    assert!(test_artifact.exists());
+    assert!(artifact.exists());
+
+    cmd.forge_fuse().args(["clean", "CounterTest"]);
+    cmd.assert_non_empty_stdout();
+    assert!(!test_artifact.exists());
+    assert!(artifact.exists());
+
+    // the removed artifact is recompiled on the next build
+    cmd.forge_fuse().arg("build");
+    cmd.assert_non_empty_stdout();
+    assert!(test_artifact.exists());
+});
+
 // checks that `clean` removes fuzz and invariant cache dirs
 forgetest_init!(can_clean_test_cache, |prj, cmd| {
     let config = Config {
@@ -758,6 +824,154 @@ contract ATest is DSTest {
     let _ = cmd.output();
 });
 
+// test that `forge snapshot --check` fails on entries without a matching test
+forgetest!(can_check_snapshot_stale_entries, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "ATest.t.sol",
+        r#"
+import "./test.sol";
+contract ATest is DSTest {
+    function testExample() public {
+        assertTrue(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.arg("snapshot").assert_success();
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success();
+
+    let snapshot = prj.root().join(".gas-snapshot");
+    let mut content = fs::read_to_string(&snapshot).unwrap();
+    content.push_str("\nATest:testRemoved() (gas: 100)");
+    fs::write(&snapshot, content).unwrap();
+
+    cmd.forge_fuse().args(["snapshot", "--check"]);
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("ATest::testRemoved()"), "{stderr}");
+    cmd.assert_non_empty_stderr();
+
+    // entries of filtered out tests are not reported
+    cmd.forge_fuse().args(["snapshot", "--check", "--match-test", "testExample"]).assert_success();
+});
+
+// test that snapshot entries are sorted by test identifier, and unsorted files can be checked
+forgetest!(can_sort_snapshot, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "ATest.t.sol",
+        r#"
+import "./test.sol";
+contract BTest is DSTest {
+    uint256[] values;
+
+    function testB() public {
+        values.push(1);
+        values.push(2);
+    }
+}
+
+contract ATest is DSTest {
+    function testA() public {
+        assertTrue(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    let snapshot = prj.root().join(".gas-snapshot");
+    let identifiers = |content: &str| {
+        content.lines().map(|line| line.split(' ').next().unwrap().to_string()).collect::<Vec<_>>()
+    };
+
+    cmd.args(["snapshot", "--asc"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    assert_eq!(identifiers(&content), ["ATest:testA()", "BTest:testB()"], "{content}");
+
+    cmd.forge_fuse().args(["snapshot", "--desc", "--sort", "false"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    assert_eq!(identifiers(&content), ["BTest:testB()", "ATest:testA()"], "{content}");
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success();
+});
+
+// test that `forge snapshot --fuzz-gas-mode` records fuzz tests like unit tests, or excludes them
+forgetest_init!(can_snapshot_fuzz_gas_mode, |prj, cmd| {
+    let snapshot = prj.root().join(".gas-snapshot");
+
+    cmd.args(["snapshot"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    let fuzz = content.lines().find(|line| line.contains("testFuzz_SetNumber")).unwrap();
+    assert!(fuzz.contains("(runs: "), "{content}");
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success();
+
+    cmd.forge_fuse().args(["snapshot", "--fuzz-gas-mode", "median"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    let fuzz = content.lines().find(|line| line.contains("testFuzz_SetNumber")).unwrap();
+    assert!(fuzz.ends_with(')') && fuzz.contains("(gas: "), "{content}");
+    cmd.forge_fuse().args(["snapshot", "--check", "--fuzz-gas-mode", "median"]).assert_success();
+
+    cmd.forge_fuse().args(["snapshot", "--fuzz-gas-mode", "exclude"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    assert!(!content.contains("testFuzz_SetNumber"), "{content}");
+    assert!(content.contains("CounterTest:test_Increment()"), "{content}");
+});
+
+// test that `forge snapshot --group-by` writes sections that can be checked against
+forgetest!(can_group_snapshot, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "ATest.t.sol",
+        r#"
+import "./test.sol";
+contract ATest is DSTest {
+    function testA() public {
+        assertTrue(true);
+    }
+
+    /// @custom:snapshot-group deposits
+    function testDeposit() public {
+        assertTrue(true);
+    }
+}
+
+contract BTest is DSTest {
+    /// @custom:snapshot-group deposits
+    function testDeposit() public {
+        assertTrue(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    let snapshot = prj.root().join(".gas-snapshot");
+    let headers = |content: &str| {
+        content
+            .lines()
+            .filter(|line| line.starts_with("// "))
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    };
+
+    cmd.args(["snapshot", "--group-by", "contract"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    assert_eq!(headers(&content), ["// ATest", "// BTest"], "{content}");
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success();
+
+    cmd.forge_fuse().args(["snapshot", "--group-by", "tag"]).assert_success();
+    let content = fs::read_to_string(&snapshot).unwrap();
+    assert_eq!(headers(&content), ["// ATest", "// deposits"], "{content}");
+    assert!(content.contains("// deposits\nATest:testDeposit()"), "{content}");
+    cmd.forge_fuse().args(["snapshot", "--check"]).assert_success();
+    cmd.forge_fuse().args(["snapshot", "--diff"]).assert_success();
+});
+
 // test that `forge build` does not print `(with warnings)` if file path is ignored
 forgetest!(can_compile_without_warnings_ignored_file_paths, |prj, cmd| {
     // Ignoring path and setting empty error_codes as default would set would set some error codes
@@ -1009,6 +1223,38 @@ forgetest!(can_install_empty, |prj, cmd| {
     cmd.assert_empty_stdout();
 });
 
+// checks that `install --json` and `deps --list --json` print the dependency manifest
+forgetest!(can_print_dependency_manifest, |_prj, cmd| {
+    cmd.git_init();
+
+    cmd.forge_fuse().args(["install", "foundry-rs/forge-std", "--no-commit", "--json"]);
+    let installed: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+
+    cmd.forge_fuse().args(["deps", "--list", "--json"]);
+    let listed: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert_eq!(installed, listed);
+
+    let deps = listed.as_array().unwrap();
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0]["name"], "lib/forge-std");
+    assert_eq!(deps[0]["path"], "lib/forge-std");
+    assert_eq!(deps[0]["url"], "https://github.com/foundry-rs/forge-std");
+    assert!(deps[0]["commit"].is_string());
+});
+
+// checks that `deps --list` prints an empty manifest when `.gitmodules` has no submodules
+forgetest!(can_print_empty_dependency_manifest, |prj, cmd| {
+    cmd.git_init();
+    prj.create_file(".gitmodules", "");
+
+    cmd.forge_fuse().args(["deps", "--list"]);
+    assert!(cmd.stdout_lossy().trim().is_empty());
+
+    cmd.forge_fuse().args(["deps", "--list", "--json"]);
+    let listed: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert_eq!(listed, serde_json::json!([]));
+});
+
 // test to check that package can be reinstalled after manually removing the directory
 forgetest!(can_reinstall_after_manual_remove, |prj, cmd| {
     cmd.git_init();
@@ -1605,6 +1851,74 @@ forgetest_init!(can_inspect_ir_optimized, |_prj, cmd| {
     cmd.assert_success();
 });
 
+// checks `forge inspect <contract> gasEstimates` reports creation and external estimates
+forgetest_init!(can_inspect_gas_estimates, |_prj, cmd| {
+    cmd.args(["inspect", TEMPLATE_CONTRACT, "gasEstimates"]);
+    let stdout = cmd.stdout_lossy();
+    let estimates: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(estimates["creation"]["totalCost"].is_string(), "{stdout}");
+    assert!(estimates["external"]["increment()"].is_string(), "{stdout}");
+
+    cmd.forge_fuse().args(["inspect", TEMPLATE_CONTRACT, "gasEstimates", "--pretty"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("totalCost") && stdout.contains("increment()"), "{stdout}");
+});
+
+// checks `forge inspect --artifact` reads fields from a prebuilt artifact without compiling
+forgetest_init!(can_inspect_prebuilt_artifact, |prj, cmd| {
+    cmd.arg("build").assert_success();
+
+    let artifact = prj.root().join("out/Counter.sol/Counter.json");
+    let partner = prj.root().join("partner.json");
+    fs::copy(artifact, &partner).unwrap();
+    fs::remove_dir_all(prj.root().join("src")).unwrap();
+
+    cmd.forge_fuse().args(["inspect", TEMPLATE_CONTRACT, "methods", "--artifact"]).arg(&partner);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("\"increment()\": \"d09de08a\""), "{stdout}");
+});
+
+// checks that all the contracts matching a glob can be inspected at once
+forgetest!(can_inspect_glob, |prj, cmd| {
+    prj.add_source(
+        "Tokens.sol",
+        r#"
+contract TokenA {
+    function a() external {}
+}
+
+contract TokenB {
+    function b() external {}
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["inspect", "src/*.sol", "methods"]);
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert_eq!(output["src/Tokens.sol:TokenA"]["a()"], "0dbe671f");
+    assert_eq!(output["src/Tokens.sol:TokenB"]["b()"], "4df7e3d0");
+
+    cmd.forge_fuse().args(["inspect", "src/*.sol:*B", "methods"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("\"b()\": \"4df7e3d0\""), "{stdout}");
+    assert!(!stdout.contains("TokenA"), "{stdout}");
+});
+
+// checks that the storage layout can be diffed against a git revision
+forgetest_init!(can_inspect_storage_layout_diff, |prj, cmd| {
+    let counter = prj.root().join("src/Counter.sol");
+    let source = fs::read_to_string(&counter).unwrap();
+    let source = source
+        .replace("uint256 public number;", "uint256 public number;\n    uint256 public other;");
+    fs::write(&counter, source).unwrap();
+
+    cmd.args(["inspect", TEMPLATE_CONTRACT, "storage-layout", "--diff", "HEAD", "--pretty"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("+ [non-breaking] other"), "{stdout}");
+    assert!(!stdout.contains("are breaking"), "{stdout}");
+});
+
 // checks forge bind works correctly on the default project
 forgetest_init!(can_bind, |_prj, cmd| {
     cmd.arg("bind");
@@ -1791,3 +2105,396 @@ forgetest_init!(can_inspect_counter_pretty, |prj, cmd| {
 }"
     );
 });
+
+// checks that `forge remappings --check` reports the diff against the auto-detected remappings
+forgetest_init!(can_check_remappings, |prj, cmd| {
+    let remappings_txt = prj.root().join("remappings.txt");
+    fs::write(&remappings_txt, "old/=lib/old/\n").unwrap();
+
+    cmd.args(["remappings", "--check"]);
+    let (stdout, stderr) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("- old/=lib/old/"), "{stdout}");
+    assert!(stdout.contains("+ forge-std/=lib/forge-std/src/"), "{stdout}");
+    assert!(stderr.contains("forge remappings --write"), "{stderr}");
+
+    cmd.forge_fuse().args(["remappings", "--write"]).assert_success();
+    assert_eq!(fs::read_to_string(&remappings_txt).unwrap(), "forge-std/=lib/forge-std/src/");
+    cmd.forge_fuse().args(["remappings", "--check"]).assert_success();
+});
+
+// checks that `forge inspect` can print the AST of the contract's source file
+forgetest_init!(can_inspect_ast, |prj, cmd| {
+    cmd.args(["inspect", "src/Counter.sol:Counter", "ast"]);
+    let output = cmd.stdout_lossy();
+    assert_eq!(output.lines().count(), 1);
+    let ast: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(ast["nodeType"], "SourceUnit");
+    assert!(ast["absolutePath"].as_str().unwrap().ends_with("Counter.sol"));
+});
+
+// checks that `forge inspect` prints the opcode histogram and disassembly of the runtime bytecode
+forgetest_init!(can_inspect_opcodes, |_prj, cmd| {
+    cmd.args(["inspect", "src/Counter.sol:Counter", "opcodes"]);
+    let histogram: BTreeMap<String, usize> = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert!(histogram["SSTORE"] >= 2, "{histogram:?}");
+    assert!(histogram["PUSH1"] > 0, "{histogram:?}");
+
+    cmd.arg("--disassemble");
+    let disassembly = cmd.stdout_lossy();
+    assert!(disassembly.contains("SSTORE"), "{disassembly}");
+    assert!(!disassembly.trim_start().starts_with('{'), "{disassembly}");
+
+    cmd.forge_fuse().args(["inspect", "Counter", "abi", "--disassemble"]).assert_err();
+});
+
+// checks that `forge inspect` prints the offsets of the immutables in the deployed bytecode
+forgetest!(can_inspect_immutable_references, |prj, cmd| {
+    prj.add_source(
+        "Immutables",
+        r#"
+contract Immutables {
+    address public immutable owner = msg.sender;
+    uint256 public immutable unused = 1;
+
+    function isOwner() public view returns (bool) {
+        return msg.sender == owner;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["inspect", "Immutables", "immutableReferences"]);
+    let references: BTreeMap<String, Vec<serde_json::Value>> =
+        serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    // both immutables are read by their getter
+    assert_eq!(references.len(), 2, "{references:?}");
+    assert!(references.values().flatten().all(|offsets| offsets["length"] == 32));
+
+    cmd.arg("--pretty");
+    let table = cmd.stdout_lossy();
+    assert!(table.contains("| AST id | Start | Length |"), "{table}");
+});
+
+// checks that `forge inspect` prints the library placeholders of unlinked bytecode
+forgetest!(can_inspect_link_references, |prj, cmd| {
+    prj.add_source(
+        "Linked",
+        r#"
+library Math {
+    function double(uint256 x) public pure returns (uint256) {
+        return 2 * x;
+    }
+}
+
+contract Linked {
+    function quadruple(uint256 x) public pure returns (uint256) {
+        return Math.double(Math.double(x));
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    for field in ["linkReferences", "deployedLinkReferences"] {
+        cmd.forge_fuse().args(["inspect", "Linked", field]);
+        let references: BTreeMap<String, BTreeMap<String, Vec<serde_json::Value>>> =
+            serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+        let (file, libraries) = references.into_iter().next().unwrap();
+        assert!(file.ends_with("Linked.sol"), "{file}");
+        assert!(!libraries["Math"].is_empty(), "{libraries:?}");
+        assert!(libraries["Math"].iter().all(|offsets| offsets["length"] == 20));
+
+        cmd.arg("--pretty");
+        let table = cmd.stdout_lossy();
+        assert!(table.contains("| Library "), "{table}");
+        assert!(table.contains("Linked.sol:Math"), "{table}");
+    }
+
+    cmd.forge_fuse().args(["inspect", "Math", "linkReferences"]);
+    assert_eq!(cmd.stdout_lossy().trim(), "{}");
+});
+
+// checks that `forge inspect` encodes constructor arguments and reports invalid ones
+forgetest!(can_inspect_constructor_args, |prj, cmd| {
+    prj.add_source(
+        "Token",
+        r#"
+contract Token {
+    constructor(address owner, uint256 supply) {}
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "inspect",
+        "Token",
+        "constructor-args",
+        "--args",
+        "0x00000000000000000000000000000000000000aa",
+        "1",
+    ]);
+    assert_eq!(
+        cmd.stdout_lossy().trim(),
+        "0x00000000000000000000000000000000000000000000000000000000000000aa\
+         0000000000000000000000000000000000000000000000000000000000000001"
+    );
+
+    cmd.forge_fuse().args(["inspect", "Token", "constructor-args", "--args", "0xaa", "one"]);
+    let err = cmd.stderr_lossy();
+    assert!(err.contains("invalid constructor argument #0 `owner` of type `address`"), "{err}");
+});
+
+// checks that `forge inspect` can print several fields at once
+forgetest_init!(can_inspect_multiple_fields, |prj, cmd| {
+    cmd.args([
+        "inspect",
+        "src/Counter.sol:Counter",
+        "--field",
+        "abi",
+        "--field",
+        "storage-layout",
+        "--field",
+        "bytecode",
+    ]);
+    let fields: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert!(fields["abi"].is_array(), "{fields}");
+    assert_eq!(fields["storageLayout"]["storage"][0]["label"], "number", "{fields}");
+    assert!(fields["bytecode"].as_str().unwrap().starts_with("0x"), "{fields}");
+
+    cmd.forge_fuse().args(["inspect", "Counter", "abi", "--field", "bytecode"]);
+    cmd.assert_err();
+});
+
+// checks that `forge inspect abi --abi-format human` prints one signature per line
+forgetest_init!(can_inspect_human_readable_abi, |prj, cmd| {
+    cmd.args(["inspect", "Counter", "abi", "--abi-format", "human"]);
+    assert_eq!(
+        cmd.stdout_lossy().trim(),
+        "function increment()\nfunction number() view returns (uint256)\nfunction setNumber(uint256)"
+    );
+
+    cmd.forge_fuse().args(["inspect", "Counter", "bytecode", "--abi-format", "human"]);
+    cmd.assert_err();
+});
+
+// checks that `forge inspect` prints the libraries of a contract in deployment order
+forgetest!(can_inspect_library_deploy_order, |prj, cmd| {
+    prj.add_source(
+        "Libraries",
+        r#"
+library Math {
+    function add(uint256 a, uint256 b) public pure returns (uint256) {
+        return a + b;
+    }
+}
+
+library Counting {
+    function next(uint256 a) public pure returns (uint256) {
+        return Math.add(a, 1);
+    }
+}
+
+contract Consumer {
+    function run(uint256 a) external pure returns (uint256) {
+        return Counting.next(a);
+    }
+}
+
+library Foo {
+    function foo() public {
+        Bar.bar();
+    }
+
+    function flum() public {}
+}
+
+library Bar {
+    function bar() public {
+        Foo.flum();
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["inspect", "Consumer", "libraries"]);
+    assert_eq!(cmd.stdout_lossy().trim(), "src/Libraries.sol:Math\nsrc/Libraries.sol:Counting");
+
+    cmd.forge_fuse().args(["inspect", "Foo", "libraries"]);
+    let (_, stderr) = cmd.unchecked_output_lossy();
+    assert!(stderr.contains("cyclic dependency found between libraries"), "{stderr}");
+});
+
+// checks that `forge inspect` reports the gas of overloaded functions by selector
+forgetest!(can_inspect_method_gas, |prj, cmd| {
+    prj.add_source(
+        "Store",
+        r#"
+contract Store {
+    uint256 public value;
+
+    function set() external {
+        value = 1;
+    }
+
+    function set(uint256 newValue) external {
+        value = newValue;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["inspect", "Store", "method-gas"]);
+    let method_gas: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    for (selector, signature) in
+        [("b8e010de", "set()"), ("60fe47b1", "set(uint256)"), ("3fa4f245", "value()")]
+    {
+        assert_eq!(method_gas[selector]["signature"], signature, "{method_gas}");
+        assert!(method_gas[selector]["gas"].is_string(), "{method_gas}");
+    }
+});
+
+// checks that the mock generated by `forge inspect` compiles and mocks the contract
+forgetest_init!(can_inspect_mock, |prj, cmd| {
+    cmd.args(["inspect", "src/Counter.sol:Counter", "mock"]);
+    let mock = cmd.stdout_lossy();
+    assert!(mock.contains("contract CounterMock {"), "{mock}");
+    prj.create_file("test/CounterMock.sol", &mock);
+
+    prj.add_test(
+        "MockTest.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+import {Counter} from "../src/Counter.sol";
+import {CounterMock} from "./CounterMock.sol";
+
+contract MockTest is Test {
+    function test_Mock() public {
+        CounterMock mock = new CounterMock();
+        mock.setNumberReturn(42);
+
+        Counter counter = Counter(address(mock));
+        assertEq(counter.number(), 42);
+        counter.setNumber(7);
+        assertEq(mock.callCount(Counter.setNumber.selector), 1);
+        assertEq(mock.callCount(Counter.number.selector), 0);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.forge_fuse().args(["test", "--mt", "test_Mock"]);
+    assert!(cmd.stdout_lossy().contains("[PASS] test_Mock()"));
+});
+
+// checks that `forge selectors lookup` marks the signatures matching a project contract
+forgetest_init!(can_lookup_selector, |_prj, cmd| {
+    cmd.args(["selectors", "lookup", "0xd09de08a"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("increment() (matches Counter)"), "{output}");
+
+    cmd.forge_fuse().args(["selectors", "lookup", "0xd09de0"]).assert_err();
+});
+
+// checks that `forge selectors lookup` prints the database signatures outside of a project
+forgetest!(can_lookup_selector_outside_project, |_prj, cmd| {
+    let dir = tempfile::tempdir().unwrap();
+    cmd.set_current_dir(dir.path());
+    cmd.args(["selectors", "lookup", "0xa9059cbb"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("transfer(address,uint256)"), "{output}");
+    assert!(!output.contains("matches"), "{output}");
+});
+
+// checks that `forge fmt --sort-imports` sorts the imports of every group
+forgetest!(can_fmt_sort_imports, |prj, cmd| {
+    let path = prj.create_file(
+        "src/Imports.sol",
+        r#"import {B, A as C} from "B.sol";
+import "A.sol";
+
+import {Z} from "Z.sol";
+import {Y} from "Y.sol";
+"#,
+    );
+
+    cmd.args(["fmt", "--sort-imports"]).arg(&path).assert_success();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        r#"import "A.sol";
+import {A as C, B} from "B.sol";
+
+import {Y} from "Y.sol";
+import {Z} from "Z.sol";
+"#
+    );
+});
+
+// checks that `forge fmt` formats the Solidity code blocks of Markdown files
+forgetest!(can_fmt_markdown, |prj, cmd| {
+    let path = prj.create_file(
+        "docs/README.md",
+        r#"# Counter
+
+The  counter:
+
+```solidity
+contract Counter { uint256  public number; }
+```
+
+```solidity
+number++;
+```
+"#,
+    );
+
+    cmd.args(["fmt", "--check"]).arg(&path);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("Diff in docs/README.md"), "{stdout}");
+    cmd.assert_err();
+
+    cmd.forge_fuse().arg("fmt").arg(&path).assert_success();
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        r#"# Counter
+
+The  counter:
+
+```solidity
+contract Counter {
+    uint256 public number;
+}
+```
+
+```solidity
+number++;
+```
+"#
+    );
+    cmd.forge_fuse().args(["fmt", "--check"]).arg(&path).assert_success();
+});
+
+// checks that a file cut off by `forge tree --depth` is still expanded where it appears at a
+// shallower depth
+forgetest!(can_print_tree_with_depth, |prj, cmd| {
+    prj.add_source("A.sol", r#"import "./B.sol"; import "./C.sol"; contract A {}"#).unwrap();
+    prj.add_source("B.sol", r#"import "./C.sol"; contract B {}"#).unwrap();
+    prj.add_source("C.sol", "contract C {}").unwrap();
+
+    cmd.args(["tree", "--depth", "1"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("src/A.sol\n├── src/B.sol\n└── src/C.sol\n"), "{stdout}");
+    assert!(stdout.contains("src/B.sol\n└── src/C.sol\n"), "{stdout}");
+    assert!(!stdout.contains("(*)"), "{stdout}");
+
+    // cycles through the root are cut off even without deduplication
+    prj.add_source("D.sol", r#"import "./E.sol"; contract D {}"#).unwrap();
+    prj.add_source("E.sol", r#"import "./D.sol"; contract E {}"#).unwrap();
+    cmd.forge_fuse().args(["tree", "--no-dedupe"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("src/D.sol\n└── src/E.sol\n    └── src/D.sol (*)\n"), "{stdout}");
+});
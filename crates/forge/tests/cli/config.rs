@@ -9,7 +9,7 @@ use foundry_compilers::{
 use foundry_config::{
     cache::{CachedChains, CachedEndpoints, StorageCachingConfig},
     fs_permissions::{FsAccessPermission, PathPermission},
-    Config, FsPermissions, FuzzConfig, InvariantConfig, SolcReq,
+    Config, FormatterConfig, FsPermissions, FuzzConfig, InvariantConfig, SolcReq,
 };
 use foundry_evm::opts::EvmOpts;
 use foundry_test_utils::{
@@ -19,6 +19,7 @@ use foundry_test_utils::{
 use path_slash::PathBufExt;
 use similar_asserts::assert_eq;
 use std::{
+    collections::BTreeMap,
     fs,
     path::{Path, PathBuf},
     str::FromStr,
@@ -131,6 +132,7 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         rpc_endpoints: Default::default(),
         build_info: false,
         build_info_path: None,
+        defines: Default::default(),
         fmt: Default::default(),
         doc: Default::default(),
         fs_permissions: Default::default(),
@@ -140,6 +142,7 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         unchecked_cheatcode_artifacts: false,
         create2_library_salt: Config::DEFAULT_CREATE2_LIBRARY_SALT,
         vyper: Default::default(),
+        build: Default::default(),
         skip: vec![],
         dependencies: Default::default(),
         warnings: vec![],
@@ -160,6 +163,16 @@ forgetest!(can_show_config, |prj, cmd| {
     assert_eq!(expected, cmd.stdout_lossy().trim().to_string());
 });
 
+// tests the formatter config gets printed to std out
+forgetest!(can_print_fmt_config, |prj, cmd| {
+    let fmt = FormatterConfig { line_length: 80, tab_width: 2, ..Default::default() };
+    prj.write_config(Config { fmt: fmt.clone(), ..Default::default() });
+
+    cmd.args(["fmt", "--print-config"]);
+    let printed: BTreeMap<String, FormatterConfig> = toml::from_str(&cmd.stdout_lossy()).unwrap();
+    assert_eq!(printed["fmt"], fmt);
+});
+
 // checks that config works
 // - foundry.toml is properly generated
 // - paths are resolved properly
@@ -698,3 +711,64 @@ forgetest!(normalize_config_evm_version, |_prj, cmd| {
     let config: Config = serde_json::from_str(&output).unwrap();
     assert_eq!(config.evm_version, EvmVersion::Istanbul);
 });
+
+// tests that the profiles of the config file are listed, with the selected one marked
+forgetest!(can_list_profiles, |prj, cmd| {
+    prj.create_file(
+        "foundry.toml",
+        r#"
+[profile.default]
+src = "src"
+
+[profile.ci]
+verbosity = 3
+fuzz = { runs = 10000 }
+"#,
+    );
+
+    cmd.env("FOUNDRY_PROFILE", "ci");
+    cmd.args(["config", "--list-profiles"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("  default: src\n"), "{output}");
+    assert!(output.contains("* ci: verbosity, fuzz\n"), "{output}");
+
+    cmd.arg("--json");
+    let profiles: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    let ci = profiles.as_array().unwrap().iter().find(|p| p["name"] == "ci").unwrap();
+    assert_eq!(ci["selected"], true);
+    assert_eq!(ci["keys"], serde_json::json!(["verbosity", "fuzz"]));
+});
+
+// checks that `forge config get` prints single values of the selected profile
+forgetest!(can_get_config_value, |prj, cmd| {
+    prj.create_file(
+        "foundry.toml",
+        r#"
+[profile.default]
+optimizer_runs = 200
+
+[profile.ci]
+optimizer_runs = 10000
+fuzz = { runs = 512 }
+"#,
+    );
+
+    cmd.args(["config", "get", "optimizer_runs"]);
+    assert_eq!(cmd.stdout_lossy().trim(), "200");
+
+    cmd.env("FOUNDRY_PROFILE", "ci");
+    assert_eq!(cmd.stdout_lossy().trim(), "10000");
+
+    cmd.forge_fuse().args(["config", "get", "fuzz.runs"]);
+    assert_eq!(cmd.stdout_lossy().trim(), "256");
+    cmd.env("FOUNDRY_PROFILE", "ci");
+    assert_eq!(cmd.stdout_lossy().trim(), "512");
+
+    cmd.forge_fuse().args(["config", "get", "src"]);
+    assert_eq!(cmd.stdout_lossy().trim(), "src");
+
+    cmd.forge_fuse().args(["config", "get", "fuzz.missing"]);
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("config key `fuzz.missing` does not exist"), "{stderr}");
+    cmd.assert_err();
+});
@@ -1,4 +1,5 @@
 use regex::Regex;
+use std::collections::BTreeMap;
 
 forgetest!(basic_coverage, |_prj, cmd| {
     cmd.args(["coverage"]);
@@ -188,3 +189,212 @@ contract BContractTest is DSTest {
     };
     assert!(!lcov_data.lines().any(valid_line), "{lcov_data}");
 });
+
+forgetest!(can_merge_raw_coverage, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    function testFoo() public {
+        AContract a = new AContract();
+        a.foo();
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    // Run the same suite twice, merging must add up the hit counts of both runs.
+    for shard in ["shard1.json", "shard2.json"] {
+        cmd.forge_fuse().args(["coverage", "--report", "raw", "--report-file", shard]);
+        cmd.assert_success();
+        assert!(prj.root().join(shard).exists());
+    }
+
+    cmd.forge_fuse().args([
+        "coverage",
+        "merge",
+        "shard1.json",
+        "shard2.json",
+        "--report",
+        "lcov",
+        "--report-file",
+        "lcov.info",
+    ]);
+    cmd.assert_success();
+
+    let lcov_data = std::fs::read_to_string(prj.root().join("lcov.info")).unwrap();
+    assert!(lcov_data.contains("FNDA:2,AContract.foo"), "{lcov_data}");
+});
+
+forgetest!(can_attribute_coverage_per_test, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo() public {
+        i = 1;
+    }
+
+    function bar() public {
+        i = 2;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    function testFoo() public {
+        AContract a = new AContract();
+        a.foo();
+    }
+
+    function testBar() public {
+        AContract a = new AContract();
+        a.bar();
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["coverage", "--per-test", "per-test.json"]);
+    cmd.assert_success();
+
+    let lines: BTreeMap<String, BTreeMap<usize, Vec<String>>> =
+        serde_json::from_str(&std::fs::read_to_string(prj.root().join("per-test.json")).unwrap())
+            .unwrap();
+    let (_, lines) = lines.iter().find(|(path, _)| path.ends_with("AContract.sol")).unwrap();
+    let test = |name: &str| format!("src/AContractTest.sol:AContractTest:{name}");
+    assert_eq!(lines[&6], vec![test("testFoo()")], "{lines:?}");
+    assert_eq!(lines[&10], vec![test("testBar()")], "{lines:?}");
+});
+
+forgetest!(can_enforce_min_coverage, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Counter.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+
+    function decrement() public {
+        number--;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "CounterTest.sol",
+        r#"
+import "./test.sol";
+import {Counter} from "./Counter.sol";
+
+contract CounterTest is DSTest {
+    function testIncrement() public {
+        Counter counter = new Counter();
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--min-coverage", "10"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("% Lines"), "{output}");
+
+    cmd.forge_fuse().args(["test", "--min-coverage", "100"]);
+    let (_, stderr) = cmd.unchecked_output_lossy();
+    assert!(stderr.contains("is below the minimum of 100.00%"), "{stderr}");
+    assert!(stderr.contains("short)"), "{stderr}");
+});
+
+forgetest!(can_ignore_coverage_sources, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Counter.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "generated/Untested.sol",
+        r#"
+contract Untested {
+    function get() public pure returns (uint256) {
+        return 1;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "CounterTest.sol",
+        r#"
+import "./test.sol";
+import {Counter} from "./Counter.sol";
+import {Untested} from "./generated/Untested.sol";
+
+contract CounterTest is DSTest {
+    function testIncrement() public {
+        Counter counter = new Counter();
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["coverage", "--ignore", "src/generated/**"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("src/Counter.sol"), "{output}");
+    assert!(!output.contains("Untested.sol"), "{output}");
+
+    cmd.forge_fuse().args(["test", "--min-coverage", "1", "--ignore", "src/generated/**"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("src/Counter.sol"), "{output}");
+    assert!(!output.contains("Untested.sol"), "{output}");
+});
@@ -161,6 +161,125 @@ forgetest_async!(can_create_template_contract, |prj, cmd| {
     );
 });
 
+// tests that we can deploy the template contract through the CREATE2 deployer
+forgetest_async!(can_create_with_salt, |prj, cmd| {
+    foundry_test_utils::util::initialize(prj.root());
+
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+    let wallet = handle.dev_wallets().next().unwrap();
+    let pk = hex::encode(wallet.credential().to_bytes());
+
+    cmd.forge_fuse().args([
+        "create",
+        format!("./src/{TEMPLATE_CONTRACT}.sol:{TEMPLATE_CONTRACT}").as_str(),
+        "--rpc-url",
+        rpc.as_str(),
+        "--private-key",
+        pk.as_str(),
+        "--salt",
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+    ]);
+
+    let stdout = cmd.stdout_lossy();
+    let predicted = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Predicted address: "))
+        .unwrap_or_else(|| panic!("{stdout}"));
+    assert!(stdout.contains(&format!("Deployed to: {predicted}")), "{stdout}");
+
+    // the same salt can't be used twice
+    cmd.assert_err();
+});
+
+// tests that `--confirm --yes` prints the estimated cost before deploying
+forgetest_async!(can_create_with_confirm, |prj, cmd| {
+    foundry_test_utils::util::initialize(prj.root());
+
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+    let wallet = handle.dev_wallets().next().unwrap();
+    let pk = hex::encode(wallet.credential().to_bytes());
+
+    cmd.forge_fuse().args([
+        "create",
+        format!("./src/{TEMPLATE_CONTRACT}.sol:{TEMPLATE_CONTRACT}").as_str(),
+        "--rpc-url",
+        rpc.as_str(),
+        "--private-key",
+        pk.as_str(),
+        "--confirm",
+        "--yes",
+    ]);
+
+    let stdout = cmd.stdout_lossy();
+    let estimate = stdout.find("Estimated cost: ").unwrap_or_else(|| panic!("{stdout}"));
+    let deployed = stdout.find("Deployed to: ").unwrap_or_else(|| panic!("{stdout}"));
+    assert!(estimate < deployed, "{stdout}");
+    assert!(stdout.contains("Estimated gas: "), "{stdout}");
+});
+
+// tests that the libraries of a contract are linked before deploying it
+forgetest_async!(can_create_with_libraries, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let rpc = handle.http_endpoint();
+    let wallet = handle.dev_wallets().next().unwrap();
+    let pk = hex::encode(wallet.credential().to_bytes());
+
+    prj.add_source(
+        "Linked",
+        r#"
+library Math {
+    function double(uint256 x) public pure returns (uint256) {
+        return 2 * x;
+    }
+}
+
+contract Linked {
+    function quadruple(uint256 x) public pure returns (uint256) {
+        return Math.double(Math.double(x));
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let create = |cmd: &mut TestCommand, contract: &str| {
+        cmd.forge_fuse().args([
+            "create",
+            contract,
+            "--rpc-url",
+            rpc.as_str(),
+            "--private-key",
+            pk.as_str(),
+        ]);
+    };
+
+    create(&mut cmd, "src/Linked.sol:Linked");
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("Math: "), "{stderr}");
+    assert!(stderr.contains("--libraries <file>:<lib>:<address>"), "{stderr}");
+
+    create(&mut cmd, "src/Linked.sol:Math");
+    let stdout = cmd.stdout_lossy();
+    let library = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Deployed to: "))
+        .unwrap_or_else(|| panic!("{stdout}"))
+        .to_string();
+
+    let libraries = prj.root().join("libraries.json");
+    std::fs::write(&libraries, format!(r#"{{"src/Linked.sol": {{"Math": "{library}"}}}}"#))
+        .unwrap();
+    create(&mut cmd, "src/Linked.sol:Linked");
+    cmd.arg("--libraries-file").arg(&libraries);
+    assert!(cmd.stdout_lossy().contains("Deployed to: "));
+
+    create(&mut cmd, "src/Linked.sol:Linked");
+    cmd.arg("--libraries").arg(format!("src/Linked.sol:Math:{library}"));
+    assert!(cmd.stdout_lossy().contains("Deployed to: "));
+});
+
 // tests that we can deploy the template contract
 forgetest_async!(can_create_using_unlocked, |prj, cmd| {
     foundry_test_utils::util::initialize(prj.root());
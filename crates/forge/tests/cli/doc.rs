@@ -1,4 +1,8 @@
-use foundry_test_utils::util::{setup_forge_remote, RemoteProject};
+use foundry_config::{Config, DocConfig};
+use foundry_test_utils::{
+    forgetest, forgetest_init,
+    util::{setup_forge_remote, RemoteProject},
+};
 
 #[test]
 fn can_generate_solmate_docs() {
@@ -6,3 +10,193 @@ fn can_generate_solmate_docs() {
         setup_forge_remote(RemoteProject::new("transmissions11/solmate").set_build(false));
     prj.forge_command().args(["doc", "--build"]).assert_success();
 }
+
+// checks that `--summary-only` only writes the summary fragment and the pages
+forgetest_init!(can_generate_summary_only, |prj, cmd| {
+    cmd.args(["doc", "--summary-only", "--summary-base", "contracts"]).assert_success();
+
+    let docs = prj.root().join("docs");
+    assert!(!docs.join("book.toml").exists());
+    assert!(docs.join("src/src/Counter.sol/contract.Counter.md").exists());
+
+    let summary = std::fs::read_to_string(docs.join("src/SUMMARY.md")).unwrap();
+    assert!(!summary.contains("# Summary"), "{summary}");
+    assert!(summary.contains("(contracts/src/Counter.sol/contract.Counter.md)"), "{summary}");
+});
+
+// checks that internal and private members are only documented with `--include-private`
+forgetest_init!(can_include_private_members, |prj, cmd| {
+    prj.add_source(
+        "Private.sol",
+        r"
+contract Private {
+    uint256 public count;
+    uint256 internal secret;
+
+    function increment() external {
+        count = _next();
+    }
+
+    /// @notice Computes the next count.
+    function _next() internal view returns (uint256) {
+        return count + secret + 1;
+    }
+}
+",
+    )
+    .unwrap();
+
+    let page = prj.root().join("docs/src/src/Private.sol/contract.Private.md");
+
+    cmd.args(["doc"]).assert_success();
+    let doc = std::fs::read_to_string(&page).unwrap();
+    assert!(doc.contains("### increment"), "{doc}");
+    assert!(!doc.contains("_next"), "{doc}");
+    assert!(!doc.contains("secret"), "{doc}");
+    assert!(!doc.contains("**Visibility:**"), "{doc}");
+
+    cmd.forge_fuse().args(["doc", "--include-private"]).assert_success();
+    let doc = std::fs::read_to_string(&page).unwrap();
+    assert!(doc.contains("### _next"), "{doc}");
+    assert!(doc.contains("Computes the next count."), "{doc}");
+    assert!(doc.contains("### secret"), "{doc}");
+    assert!(doc.contains("**Visibility:**\ninternal"), "{doc}");
+});
+
+// checks that the members inherited from base contracts are linked to, or inlined with
+// `--inline-inherited`
+forgetest!(can_document_inherited_members, |prj, cmd| {
+    prj.add_source(
+        "Inherited.sol",
+        r"
+contract Base {
+    /// Emitted on pings.
+    event Pinged();
+
+    /// @notice Pings the contract.
+    function ping() external virtual {}
+
+    function pong() external {}
+}
+
+contract Derived is Base {
+    function ping() external override {}
+}
+",
+    )
+    .unwrap();
+
+    let page = prj.root().join("docs/src/src/Inherited.sol/contract.Derived.md");
+
+    cmd.args(["doc"]).assert_success();
+    let doc = std::fs::read_to_string(&page).unwrap();
+    assert!(doc.contains("## Inherited Members"), "{doc}");
+    assert!(doc.contains("### From [Base](/src/Inherited.sol/contract.Base.md)"), "{doc}");
+    assert!(doc.contains("- function [pong](/src/Inherited.sol/contract.Base.md#pong)"), "{doc}");
+    assert!(doc.contains("- event [Pinged](/src/Inherited.sol/contract.Base.md#pinged)"), "{doc}");
+    assert!(!doc.contains("#ping)"), "{doc}");
+
+    cmd.forge_fuse().args(["doc", "--inline-inherited"]).assert_success();
+    let doc = std::fs::read_to_string(&page).unwrap();
+    assert!(doc.contains("### pong"), "{doc}");
+    assert!(doc.contains("Emitted on pings."), "{doc}");
+    assert!(!doc.contains("Pings the contract."), "{doc}");
+});
+
+// checks that inherited members come from the base imported by the derived contract when several
+// contracts share its name
+forgetest!(can_document_members_of_imported_base, |prj, cmd| {
+    prj.add_source(
+        "a/Base.sol",
+        r"
+contract Base {
+    function fromA() external {}
+}
+",
+    )
+    .unwrap();
+    prj.add_source(
+        "b/Base.sol",
+        r"
+contract Base {
+    function fromB() external {}
+}
+",
+    )
+    .unwrap();
+    prj.add_source(
+        "Derived.sol",
+        r#"
+import {Base} from "./b/Base.sol";
+
+contract Derived is Base {}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["doc"]).assert_success();
+    let doc =
+        std::fs::read_to_string(prj.root().join("docs/src/src/Derived.sol/contract.Derived.md"))
+            .unwrap();
+    assert!(doc.contains("### From [Base](/src/b/Base.sol/contract.Base.md)"), "{doc}");
+    assert!(doc.contains("fromB"), "{doc}");
+    assert!(!doc.contains("fromA"), "{doc}");
+});
+
+// checks that `--check-missing` fails on the public API lacking the required NatSpec tags
+forgetest!(can_check_missing_natspec, |prj, cmd| {
+    prj.add_source(
+        "Counter.sol",
+        r"
+contract Counter {
+    /// @notice The current count.
+    uint256 public count;
+
+    /// @notice Adds to the count.
+    /// @param amount The amount to add.
+    /// @return The new count.
+    function add(uint256 amount) external returns (uint256) {
+        count += amount;
+        return count;
+    }
+}
+",
+    )
+    .unwrap();
+
+    cmd.args(["doc", "--check-missing"]).assert_success();
+    assert!(!prj.root().join("docs").exists());
+
+    prj.add_source(
+        "Token.sol",
+        r"
+contract Token {
+    /// Emitted on transfers.
+    event Transfer(address from, address to);
+
+    /// @notice Transfers tokens.
+    function transfer(address to) external returns (bool) {}
+
+    function _move() internal {}
+}
+",
+    )
+    .unwrap();
+
+    let stderr = cmd.stderr_lossy();
+    assert!(
+        stderr.contains("src/Token.sol: function `Token.transfer` is missing @param to, @return"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("src/Token.sol: event `Token.Transfer` is missing @param from, @param to"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("_move"), "{stderr}");
+    assert!(stderr.contains("2 items are missing NatSpec documentation"), "{stderr}");
+    cmd.assert_err();
+
+    let doc = DocConfig { required_tags: vec!["notice".into()], ..Default::default() };
+    prj.write_config(Config { doc, ..Default::default() });
+    cmd.assert_success();
+});
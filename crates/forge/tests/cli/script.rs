@@ -60,6 +60,56 @@ contract Demo {
     );
 });
 
+// Tests that the decoded traces of a script can be written to a file
+forgetest!(can_write_script_traces, |prj, cmd| {
+    let script = prj
+        .add_source(
+            "Foo",
+            r#"
+contract Demo {
+    event log_string(string);
+    function run() external {
+        emit log_string("script ran");
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    let text = prj.root().join("traces.txt");
+    cmd.arg("script").arg(&script).arg("--trace-out").arg(&text).assert_success();
+    let traces = std::fs::read_to_string(&text).unwrap();
+    assert!(traces.contains("Demo::run()"), "{traces}");
+    assert!(traces.contains("emit log_string"), "{traces}");
+
+    let json = prj.root().join("traces.json");
+    cmd.forge_fuse().arg("script").arg(&script).arg("--trace-out").arg(&json).assert_success();
+    let traces: Value = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    assert_eq!(traces.as_array().unwrap().len(), 1);
+});
+
+// Tests that the traces of a failed script are written before its error is returned
+forgetest!(can_write_failed_script_traces, |prj, cmd| {
+    let script = prj
+        .add_source(
+            "Foo",
+            r#"
+contract Demo {
+    function run() external {
+        revert("script failed");
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    let text = prj.root().join("traces.txt");
+    cmd.arg("script").arg(&script).arg("--json").arg("--trace-out").arg(&text).assert_err();
+    let traces = std::fs::read_to_string(&text).unwrap();
+    assert!(traces.contains("Demo::run()"), "{traces}");
+    assert!(traces.contains("script failed"), "{traces}");
+});
+
 // Tests that the `run` command works correctly when path *and* script name is specified
 forgetest!(can_execute_script_command_fqn, |prj, cmd| {
     let script = prj
@@ -1451,3 +1501,71 @@ forgetest_async!(can_deploy_library_create2_different_sender, |prj, cmd| {
         .assert_nonce_increment(&[(2, 2)])
         .await;
 });
+
+// Tests that a batch file of calls can be simulated and broadcast without a script
+forgetest_async!(can_broadcast_batch_file, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let batch = prj.create_file(
+        "calls.json",
+        r#"[
+    {"to": "0x70997970C51812dc3A010C7d01b50e0d17dc79C8", "value": "1ether"},
+    {"to": "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC", "value": "1gwei", "sig": "0x"}
+]"#,
+    );
+
+    let mut tester =
+        ScriptTester::new(cmd, Some(&handle.http_endpoint()), prj.root(), batch.to_str().unwrap());
+    tester
+        .arg("--batch")
+        .load_private_keys(&[0])
+        .await
+        .simulate(ScriptOutcome::OkSimulation)
+        .broadcast(ScriptOutcome::OkBroadcast)
+        .assert_nonce_increment(&[(0, 2)])
+        .await;
+});
+
+// Asserts that `--safe-batch` writes the simulated transactions to a Safe batch file.
+forgetest_async!(can_write_safe_batch, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+
+    foundry_test_utils::util::initialize(prj.root());
+    prj.add_script(
+        "Safe",
+        r#"
+import "forge-std/Script.sol";
+
+contract SafeScript is Script {
+    function run() external {
+        vm.startBroadcast();
+        payable(0x70997970C51812dc3A010C7d01b50e0d17dc79C8).transfer(1 ether);
+        (bool success,) = address(0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC).call(hex"d09de08a");
+        require(success);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args([
+        "script",
+        "SafeScript",
+        "--fork-url",
+        &handle.http_endpoint(),
+        "--sender",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+        "--safe-batch",
+        "batch.json",
+    ]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("Wrote 2 transactions to the Safe batch"), "{stdout}");
+
+    let batch: Value = foundry_common::fs::read_json_file(&prj.root().join("batch.json")).unwrap();
+    assert_eq!(batch["chainId"], "31337");
+    let transactions = batch["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0]["value"], "1000000000000000000");
+    assert_eq!(transactions[0]["data"], "0x");
+    assert_eq!(transactions[1]["data"], "0xd09de08a");
+    assert_eq!(transactions[1]["operation"], 0);
+});
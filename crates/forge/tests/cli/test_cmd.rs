@@ -1,7 +1,8 @@
 //! Contains various tests for `forge test`.
 
-use alloy_primitives::U256;
-use foundry_config::{Config, FuzzConfig};
+use alloy_primitives::{address, B256, U256};
+use anvil::{spawn, NodeConfig};
+use foundry_config::{Config, FuzzConfig, RpcEndpoint, RpcEndpoints};
 use foundry_test_utils::{
     rpc,
     util::{OutputExt, OTHER_SOLC_VERSION, SOLC_VERSION},
@@ -123,6 +124,419 @@ contract ATest is DSTest {
     cmd.stdout_lossy().contains("[PASS]");
 });
 
+// tests that `@custom:gas-limit` fails tests exceeding their gas budget
+forgetest!(can_enforce_inline_gas_limit, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "GasLimitTest.t.sol",
+        r#"
+import "./test.sol";
+contract GasLimitTest is DSTest {
+    uint256[] values;
+
+    /// @custom:gas-limit 1000000
+    function testWithinLimit() public {
+        values.push(1);
+    }
+
+    /// @custom:gas-limit 1000
+    function testExceedsLimit() public {
+        values.push(1);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.arg("test");
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("[PASS] testWithinLimit()"), "{stdout}");
+    assert!(stdout.contains("[FAIL. Reason: gas limit exceeded"), "{stdout}");
+    assert!(stdout.contains("testExceedsLimit()"), "{stdout}");
+    cmd.assert_err();
+});
+
+// tests that `@custom:fuzz-runs` overrides the number of runs of `--fuzz-runs`
+forgetest!(can_override_fuzz_runs_inline, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "FuzzRunsTest.t.sol",
+        r#"
+import "./test.sol";
+contract FuzzRunsTest is DSTest {
+    /// @custom:fuzz-runs 300
+    function testFuzzMore(uint256) public {}
+
+    /// @custom:fuzz-runs 10
+    function testFuzzLess(uint256) public {}
+
+    function testFuzzDefault(uint256) public {}
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--fuzz-runs", "50"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("testFuzzMore(uint256) (runs: 300"), "{stdout}");
+    assert!(stdout.contains("testFuzzLess(uint256) (runs: 10"), "{stdout}");
+    assert!(stdout.contains("testFuzzDefault(uint256) (runs: 50"), "{stdout}");
+});
+
+// tests that `--fork-url` resolves the aliases of `[rpc_endpoints]`, expanding env vars
+forgetest_async!(can_fork_from_rpc_endpoint_alias, |prj, cmd| {
+    prj.insert_ds_test();
+
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let rpc_endpoints =
+        RpcEndpoints::new([("local", RpcEndpoint::Env("${ANVIL_FORK_URL}".to_string()))]);
+    prj.write_config(Config { rpc_endpoints, ..Default::default() });
+
+    prj.add_source(
+        "ForkAliasTest.t.sol",
+        r#"
+import "./test.sol";
+contract ForkAliasTest is DSTest {
+    function testChainId() public {
+        assertEq(block.chainid, 31337);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.env("ANVIL_FORK_URL", handle.http_endpoint());
+    cmd.args(["test", "--fork-url", "local"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[PASS] testChainId()"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--fork-url", "mainnet"]);
+    let stderr = cmd.stderr_lossy();
+    assert!(
+        stderr.contains("`mainnet` is not a URL, nor one of the configured RPC endpoints: `local`"),
+        "{stderr}"
+    );
+});
+
+// tests that `--evm-version` selects the opcodes available to the tests
+forgetest_init!(can_test_with_evm_version, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Push0.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract Push0Test is Test {
+    function testPush0() public {
+        address target = address(0x1234);
+        // PUSH0 PUSH0 RETURN
+        vm.etch(target, hex"5f5ff3");
+        (bool success,) = target.call("");
+        assertTrue(success);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--evm-version", "paris"]);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(
+        stdout.lines().any(|line| line.starts_with("[FAIL") && line.contains("testPush0()")),
+        "{stdout}"
+    );
+
+    cmd.forge_fuse().args(["test", "--evm-version", "shanghai"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[PASS] testPush0()"), "{stdout}");
+});
+
+// tests that `--json-lines` prints a line per test result followed by the summary
+forgetest!(can_stream_json_lines, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "JsonLinesTest.t.sol",
+        r#"
+import "./test.sol";
+contract JsonLinesTest is DSTest {
+    function testPass() public {}
+
+    function testOtherPass() public {}
+
+    function testFailing() public {
+        require(false);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--json-lines"]);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    let lines = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4, "{stdout}");
+
+    let suite = "src/JsonLinesTest.t.sol:JsonLinesTest";
+    let tests = &lines[..3];
+    assert!(tests.iter().all(|line| line["type"] == "test" && line["suite"] == suite));
+    let status = |test: &str| {
+        let line = tests.iter().find(|line| line["test"] == test).unwrap();
+        line["result"]["status"].clone()
+    };
+    assert_eq!(status("testPass()"), "Success");
+    assert_eq!(status("testOtherPass()"), "Success");
+    assert_eq!(status("testFailing()"), "Failure");
+
+    let summary = &lines[3];
+    assert_eq!(summary["type"], "summary");
+    assert_eq!(summary["passed"], 2);
+    assert_eq!(summary["failed"], 1);
+    assert_eq!(summary["skipped"], 0);
+});
+
+// tests that `--compact` prints a line per test contract and only the failed tests
+forgetest!(can_print_compact_results, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "SummaryTest.t.sol",
+        r#"
+import "./test.sol";
+contract SummaryTest is DSTest {
+    function testPass() public {}
+
+    function testFailing() public {
+        require(false, "failing");
+    }
+}
+
+contract OtherSummaryTest is DSTest {
+    function testOtherPass() public {}
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--compact"]);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(!stdout.contains("testPass()"), "{stdout}");
+    assert!(!stdout.contains("testOtherPass()"), "{stdout}");
+    assert!(stdout.contains("[FAIL. Reason: revert: failing] testFailing()"), "{stdout}");
+    assert!(!stdout.contains("Ran 2 tests for"), "{stdout}");
+    assert!(
+        stdout.contains(
+            "src/SummaryTest.t.sol:SummaryTest: Suite result: FAILED. 1 passed; 1 failed"
+        ),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("src/SummaryTest.t.sol:OtherSummaryTest: Suite result: ok. 1 passed"),
+        "{stdout}"
+    );
+});
+
+// tests that the decoded console logs are part of the JSON output, in emission order
+forgetest_init!(can_output_decoded_logs_json, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Logs.t.sol",
+        r#"
+import {Test, console} from "forge-std/Test.sol";
+
+contract LogsTest is Test {
+    function test_Logs() public {
+        console.log("first %s", uint256(1));
+        emit log_named_uint("second", 2);
+        console.log("third");
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--json"]);
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    let logs = &output["test/Logs.t.sol:LogsTest"]["test_results"]["test_Logs()"]["decoded_logs"];
+    assert_eq!(*logs, serde_json::json!(["first 1", "second: 2", "third"]));
+});
+
+// tests that the reverts expected with `vm.expectRevert` are marked in traces and counted
+forgetest_init!(can_count_expected_reverts, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Reverts.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract Reverter {
+    function fail() external pure {
+        revert("expected failure");
+    }
+}
+
+contract RevertsTest is Test {
+    Reverter reverter = new Reverter();
+
+    function test_ExpectedReverts() public {
+        vm.expectRevert("expected failure");
+        reverter.fail();
+        vm.expectRevert();
+        reverter.fail();
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "-vvvv"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[Revert] revert: expected failure (expected)"), "{stdout}");
+    assert!(stdout.contains("0 skipped; 2 expected reverts; finished in"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--json"]);
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    let test = &output["test/Reverts.t.sol:RevertsTest"]["test_results"]["test_ExpectedReverts()"];
+    assert_eq!(test["expected_reverts"], 2, "{test}");
+});
+
+// tests that the gas used by `setUp` is reported separately for every suite
+forgetest!(can_report_setup_gas, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "SetupGas.t.sol",
+        r#"
+import "./test.sol";
+contract WithSetupTest is DSTest {
+    uint256[] values;
+
+    function setUp() public {
+        for (uint256 i = 0; i < 10; i++) {
+            values.push(i);
+        }
+    }
+
+    function testValues() public {
+        assertEq(values.length, 10);
+    }
+}
+
+contract WithoutSetupTest is DSTest {
+    function testNothing() public {}
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--json"]);
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    let setup_gas = &output["src/SetupGas.t.sol:WithSetupTest"]["setup_gas"];
+    assert!(setup_gas.as_u64().unwrap() > 100_000, "{setup_gas}");
+    assert!(output["src/SetupGas.t.sol:WithoutSetupTest"]["setup_gas"].is_null());
+});
+
+// tests that `--kind` only runs tests of the given kinds, along with the other filters
+forgetest!(can_filter_test_kind, |prj, cmd| {
+    prj.insert_ds_test();
+
+    prj.add_source(
+        "ATest.t.sol",
+        r#"
+import "./test.sol";
+contract ATest is DSTest {
+    function testUnit() public {
+        assertTrue(true);
+    }
+
+    function testFuzz(uint256 x) public {
+        assertTrue(true);
+    }
+
+    function testOtherFuzz(uint256 x) public {
+        assertTrue(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--kind", "unit"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("testUnit()"), "{stdout}");
+    assert!(!stdout.contains("testFuzz(uint256)"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--kind", "fuzz", "--match-test", "testFuzz"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("testFuzz(uint256)"), "{stdout}");
+    assert!(!stdout.contains("testUnit()"), "{stdout}");
+    assert!(!stdout.contains("testOtherFuzz(uint256)"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--kind", "unit", "--kind", "fuzz"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("testUnit()"), "{stdout}");
+    assert!(stdout.contains("testOtherFuzz(uint256)"), "{stdout}");
+});
+
+// tests that `--show-skipped` lists the tests that were not run, with the reason
+forgetest_init!(can_show_skipped_tests, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "A.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract ATest is Test {
+    function testA() public {}
+
+    function testB() public {}
+
+    function testSkip() public {
+        vm.skip(true);
+    }
+}
+   "#,
+    )
+    .unwrap();
+    prj.add_test(
+        "Other.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract OtherTest is Test {
+    function testOther() public {}
+}
+   "#,
+    )
+    .unwrap();
+
+    let args = ["test", "--show-skipped", "--match-contract", "ATest", "--no-match-test", "testB"];
+    cmd.args(args);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("test/A.t.sol:ATest:testB() (filtered)"), "{stdout}");
+    assert!(stdout.contains("test/A.t.sol:ATest:testSkip() (skipped-at-runtime)"), "{stdout}");
+    assert!(stdout.contains("test/Other.t.sol (compile-excluded)"), "{stdout}");
+
+    cmd.forge_fuse().args(args).arg("--json");
+    let output: serde_json::Value = serde_json::from_str(&cmd.stdout_lossy()).unwrap();
+    assert!(output["results"]["test/A.t.sol:ATest"]["test_results"]["testA()"].is_object());
+    let skipped = output["skipped"].as_array().unwrap();
+    assert!(skipped.contains(&serde_json::json!({
+        "id": "test/A.t.sol:ATest",
+        "signature": "testB()",
+        "reason": "filtered",
+    })));
+});
+
 // tests that `bytecode_hash` will be sanitized
 forgetest!(can_test_pre_bytecode_hash, |prj, cmd| {
     prj.insert_ds_test();
@@ -679,3 +1093,226 @@ contract ReplayFailuresTest is Test {
             .join("tests/fixtures/replay_last_run_failures.stdout"),
     );
 });
+
+// tests that `--changed` only runs the test files affected by the changed files
+forgetest_init!(can_test_changed, |prj, cmd| {
+    prj.add_source("Unused.sol", "contract Unused {}").unwrap();
+    prj.add_test(
+        "Other.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract OtherTest is Test {
+    function test_other() public {}
+}
+   "#,
+    )
+    .unwrap();
+    let git = |args: &[&str]| {
+        let status =
+            std::process::Command::new("git").current_dir(prj.root()).args(args).status().unwrap();
+        assert!(status.success());
+    };
+    git(&["add", "-A"]);
+    git(&["commit", "-m", "add sources", "--no-gpg-sign"]);
+
+    let counter = prj.root().join("src/Counter.sol");
+    let source = std::fs::read_to_string(&counter).unwrap();
+    std::fs::write(&counter, source.replace("number++", "number += 1")).unwrap();
+    std::fs::write(prj.root().join("src/Unused.sol"), "contract Unused { uint256 x; }").unwrap();
+
+    cmd.args(["test", "--changed", "HEAD"]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("CounterTest"), "{output}");
+    assert!(!output.contains("OtherTest"), "{output}");
+    assert!(
+        output.contains("Note: src/Unused.sol changed but is not imported by any test"),
+        "{output}"
+    );
+});
+
+// tests that `--labels` labels the addresses of the traces
+forgetest_init!(can_test_with_labels, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Labels.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract LabelsTest is Test {
+    function test_labels() public {
+        (bool success,) = address(0xbeef).call("");
+        assertTrue(success);
+        (success,) = address(0xcafe).call("");
+        assertTrue(success);
+    }
+
+    function setUp() public {
+        vm.label(address(0xcafe), "Labeled");
+    }
+}
+   "#,
+    )
+    .unwrap();
+    let labels = prj.root().join("labels.json");
+    std::fs::write(
+        &labels,
+        r#"{
+            "0x000000000000000000000000000000000000beef": "Router",
+            "0x000000000000000000000000000000000000cafe": "Ignored"
+        }"#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "-vvvv", "--labels"]).arg(&labels);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("Router::"), "{output}");
+    assert!(output.contains("Labeled::"), "{output}");
+    assert!(!output.contains("Ignored"), "{output}");
+});
+
+// tests that `--snapshot-state` dumps the state at the end of the tests in a format anvil loads
+forgetest_async!(can_snapshot_state, |prj, cmd| {
+    prj.add_test(
+        "Counter.t.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number++;
+    }
+}
+
+contract CounterTest {
+    Counter counter;
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function test_increment() public {
+        counter.increment();
+    }
+
+    function testFuzz_increment(uint256) public {
+        counter.increment();
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    // the test contract deploys the counter with its first nonce
+    let test_contract = address!("7FA9385bE102ac3EAc297483Dd6233D62b3e1496");
+    let counter = test_contract.create(1);
+
+    let state = prj.root().join("state.json");
+    cmd.arg("test").arg("--snapshot-state").arg(&state).assert_success();
+    let dump: serde_json::Value = foundry_common::fs::read_json_file(&state).unwrap();
+    let account = &dump["accounts"][counter.to_string().to_lowercase()];
+    assert!(account["code"].as_str().is_some_and(|code| code.len() > 2), "{dump}");
+
+    let (api, _handle) = spawn(NodeConfig::test().with_init_state_path(&state)).await;
+    let number = api.storage_at(counter, U256::ZERO, None).await.unwrap();
+    assert_eq!(number, B256::with_last_byte(1));
+
+    let states = prj.root().join("states");
+    cmd.forge_fuse()
+        .arg("test")
+        .arg("--snapshot-state")
+        .arg(&states)
+        .arg("--snapshot-state-per-test")
+        .assert_success();
+    assert!(states.join("CounterTest.test_increment.json").exists());
+    assert!(!states.join("CounterTest.testFuzz_increment.json").exists());
+});
+
+// tests that only the failures of the tests matching `--allow-failure-match` are allowed
+forgetest_init!(can_allow_failures, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Flaky.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract FlakyTest is Test {
+    function testForkFlaky() public {
+        assertTrue(false);
+    }
+
+    function testShouldFail() public {
+        assertTrue(false);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--allow-failure-match", "testFork*", "--match-test", "testForkFlaky"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("Allowed failures:"), "{stdout}");
+    assert!(stdout.contains("0 failed, 1 allowed failures"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--allow-failure-match", "testFork*"]);
+    cmd.assert_err();
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("1 failed, 1 allowed failures"), "{stdout}");
+    assert!(stdout.contains("Encountered 1 failing test in"), "{stdout}");
+
+    // all the failures are allowed through the environment
+    cmd.forge_fuse().args(["test"]);
+    cmd.env("FORGE_ALLOW_FAILURE", "true");
+    cmd.assert_success();
+});
+
+// tests that failing tests are retried with `--retries`, and that flaky tests are reported
+forgetest_init!(can_retry_failed_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_test(
+        "Flaky.t.sol",
+        r#"
+import "forge-std/Test.sol";
+
+contract FlakyTest is Test {
+    function testFlaky() public {
+        // The environment of the process is kept between the attempts
+        if (!vm.envOr("FORGE_TEST_FLAKY_ATTEMPTED", false)) {
+            vm.setEnv("FORGE_TEST_FLAKY_ATTEMPTED", "true");
+            revert("transient error");
+        }
+    }
+
+    function testAlwaysFails() public {
+        revert("genuine error");
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--retries", "2", "--match-test", "testFlaky"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[PASS] testFlaky()"), "{stdout}");
+    assert!(stdout.contains("[flaky: passed after 2 attempts]"), "{stdout}");
+
+    cmd.forge_fuse().args(["test", "--retries", "2", "--match-test", "testAlwaysFails"]);
+    cmd.assert_err();
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("[failed 3 attempts]"), "{stdout}");
+});
+
+// tests that `--flamegraph` writes the gas used by the call stacks of the tests as folded stacks
+forgetest_init!(can_write_gas_flamegraph, |prj, cmd| {
+    let path = prj.root().join("gas.folded");
+    cmd.args(["test", "--match-test", "test_Increment", "--flamegraph"]).arg(&path);
+    cmd.assert_success();
+
+    let folded = std::fs::read_to_string(&path).unwrap();
+    let line = folded
+        .lines()
+        .find(|line| line.starts_with("CounterTest::test_Increment;Counter::increment "))
+        .unwrap_or_else(|| panic!("{folded}"));
+    let gas = line.rsplit(' ').next().unwrap();
+    assert!(gas.parse::<u64>().unwrap() > 0, "{folded}");
+});
@@ -93,6 +93,7 @@ impl ForgeTestProfile {
                 gas_report_samples: 256,
                 failure_persist_dir: Some(tempfile::tempdir().unwrap().into_path()),
                 failure_persist_file: Some("testfailure".to_string()),
+                fork_dictionary: false,
             })
             .invariant(InvariantConfig {
                 runs: 256,
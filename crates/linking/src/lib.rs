@@ -29,6 +29,8 @@ pub enum LinkerError {
     InvalidAddress(<Address as std::str::FromStr>::Err),
     #[error("cyclic dependency found, can't link libraries via CREATE2")]
     CyclicDependency,
+    #[error("cyclic dependency found between libraries: {0}")]
+    CyclicLibraries(String),
 }
 
 pub struct Linker<'a> {
@@ -100,6 +102,20 @@ impl<'a> Linker<'a> {
         target: &'a ArtifactId,
         deps: &mut BTreeSet<&'a ArtifactId>,
     ) -> Result<(), LinkerError> {
+        for id in self.direct_dependencies(target)? {
+            if deps.insert(id) {
+                self.collect_dependencies(id, deps)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the libraries referenced by the creation and runtime code of `target`.
+    fn direct_dependencies(
+        &'a self,
+        target: &'a ArtifactId,
+    ) -> Result<BTreeSet<&'a ArtifactId>, LinkerError> {
         let contract = self.contracts.get(target).ok_or(LinkerError::MissingTargetArtifact)?;
 
         let mut references = BTreeMap::new();
@@ -112,6 +128,7 @@ impl<'a> Linker<'a> {
             }
         }
 
+        let mut deps = BTreeSet::new();
         for (file, libs) in &references {
             for contract in libs.keys() {
                 let id = self
@@ -120,12 +137,60 @@ impl<'a> Linker<'a> {
                         file: file.to_string(),
                         name: contract.to_string(),
                     })?;
-                if deps.insert(id) {
-                    self.collect_dependencies(id, deps)?;
-                }
+                deps.insert(id);
             }
         }
 
+        Ok(deps)
+    }
+
+    /// Returns the libraries `target` links against, directly or through other libraries, in the
+    /// order in which they must be deployed: every library comes after the libraries it links
+    /// against.
+    ///
+    /// Fails if the libraries depend on each other in a cycle, which cannot be deployed.
+    pub fn deploy_order(
+        &'a self,
+        target: &'a ArtifactId,
+    ) -> Result<Vec<&'a ArtifactId>, LinkerError> {
+        let mut order = Vec::new();
+        self.visit_dependencies(target, &mut Vec::new(), &mut order)?;
+        // the target itself is deployed last
+        order.pop();
+        Ok(order)
+    }
+
+    /// Appends the dependencies of `id` in post-order to `order`, followed by `id`.
+    ///
+    /// `path` is the chain of artifacts which led to `id`, used to report cycles.
+    fn visit_dependencies(
+        &'a self,
+        id: &'a ArtifactId,
+        path: &mut Vec<&'a ArtifactId>,
+        order: &mut Vec<&'a ArtifactId>,
+    ) -> Result<(), LinkerError> {
+        if let Some(start) = path.iter().position(|visited| *visited == id) {
+            let cycle = path[start..]
+                .iter()
+                .chain([&id])
+                .map(|id| {
+                    let (file, name) = self.convert_artifact_id_to_lib_path(id);
+                    format!("{}:{name}", file.display())
+                })
+                .collect::<Vec<_>>();
+            return Err(LinkerError::CyclicLibraries(cycle.join(" -> ")));
+        }
+        if order.contains(&id) {
+            return Ok(());
+        }
+
+        path.push(id);
+        for dep in self.direct_dependencies(id)? {
+            self.visit_dependencies(dep, path, order)?;
+        }
+        path.pop();
+
+        order.push(id);
         Ok(())
     }
 
@@ -681,4 +746,27 @@ mod tests {
                 );
         });
     }
+
+    fn deploy_order(path: &str, target: &str) -> Result<Vec<String>, LinkerError> {
+        let test = LinkerTest::new(path, true);
+        let linker = Linker::new(test.project.root(), test.output.artifact_ids().collect());
+        let id = linker.contracts.keys().find(|id| id.name == target).unwrap();
+        let order = linker.deploy_order(id)?;
+        Ok(order.into_iter().map(|id| id.name.clone()).collect())
+    }
+
+    #[test]
+    fn deploy_order_nested() {
+        let path = "../../testdata/default/linking/nested";
+        assert_eq!(deploy_order(path, "Lib").unwrap(), Vec::<String>::new());
+        assert_eq!(deploy_order(path, "NestedLib").unwrap(), ["Lib"]);
+        assert_eq!(deploy_order(path, "LibraryConsumer").unwrap(), ["Lib", "NestedLib"]);
+    }
+
+    #[test]
+    fn deploy_order_cycle() {
+        let err = deploy_order("../../testdata/default/linking/cycle", "Foo").unwrap_err();
+        assert!(matches!(err, LinkerError::CyclicLibraries(_)), "{err}");
+        assert!(err.to_string().contains("Cycle.t.sol:Foo -> "), "{err}");
+    }
 }
@@ -0,0 +1,187 @@
+//! Batches of calls broadcast without a bespoke script.
+//!
+//! The calls of a batch file are turned into a generated script, which then goes through the
+//! regular script pipeline, so the simulation, broadcasting and resuming work the same.
+
+use alloy_primitives::{hex, Address, Bytes, U256};
+use eyre::{Context, Result};
+use foundry_cli::utils::parse_ether_value;
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    fs,
+};
+use foundry_config::Config;
+use serde::Deserialize;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+/// The name of the contract of the generated script.
+pub const BATCH_SCRIPT_CONTRACT: &str = "BatchScript";
+
+/// A call listed in a batch file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct BatchCall {
+    /// The address to call.
+    pub to: Address,
+    /// The signature of the function to call, e.g. `transfer(address,uint256)`, or raw calldata.
+    ///
+    /// No calldata is sent if omitted.
+    #[serde(default)]
+    pub sig: Option<String>,
+    /// The arguments of the function.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The value to send, in wei unless a unit is given, e.g. `1ether`.
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+impl BatchCall {
+    /// Returns the calldata of the call.
+    pub fn calldata(&self) -> Result<Bytes> {
+        let Some(sig) = &self.sig else {
+            if !self.args.is_empty() {
+                eyre::bail!("arguments were given for a call to {} without a signature", self.to);
+            }
+            return Ok(Bytes::new())
+        };
+
+        if let Ok(calldata) = hex::decode(sig) {
+            return Ok(calldata.into())
+        }
+
+        let func = get_func(sig)?;
+        if func.inputs.len() != self.args.len() {
+            eyre::bail!(
+                "`{sig}` takes {} arguments, but {} were given",
+                func.inputs.len(),
+                self.args.len()
+            );
+        }
+        Ok(encode_function_args(&func, &self.args)?.into())
+    }
+
+    /// Returns the value sent with the call.
+    pub fn value(&self) -> Result<U256> {
+        self.value.as_deref().map_or(Ok(U256::ZERO), parse_ether_value)
+    }
+}
+
+/// Reads the calls of a batch file.
+///
+/// The file is a JSON array of `{to, sig, args, value}` entries.
+pub fn read_batch(path: &Path) -> Result<Vec<BatchCall>> {
+    fs::read_json_file(path)
+        .wrap_err_with(|| format!("failed to read batch file {}", path.display()))
+}
+
+/// Writes the script broadcasting the calls of the batch file and returns its path.
+///
+/// The script is written to the cache directory, named after the batch file, so that the
+/// broadcasts of the same batch file can be resumed.
+pub fn write_batch_script(batch: &Path, config: &Config) -> Result<PathBuf> {
+    let calls = read_batch(batch)?;
+    if calls.is_empty() {
+        eyre::bail!("batch file {} has no calls", batch.display());
+    }
+
+    let name = batch.file_stem().unwrap_or_default().to_string_lossy();
+    let path = config.root.0.join(&config.cache_path).join("batch").join(format!("{name}.s.sol"));
+    let source = batch_script(&calls, &batch.display().to_string())?;
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, source)?;
+    Ok(path)
+}
+
+/// Generates the source of a script broadcasting the given calls, in order.
+pub fn batch_script(calls: &[BatchCall], batch: &str) -> Result<String> {
+    let mut body = String::new();
+    for (i, call) in calls.iter().enumerate() {
+        let calldata = call.calldata().wrap_err_with(|| format!("invalid call #{i}"))?;
+        let value = call.value().wrap_err_with(|| format!("invalid value of call #{i}"))?;
+        // Address literals must be checksummed
+        let to = call.to.to_checksum(None);
+        writeln!(body, "        _call({to}, {value}, hex\"{}\");", hex::encode(calldata))?;
+    }
+
+    Ok(format!(
+        r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity >=0.6.2;
+
+// Generated by `forge script --batch` from `{batch}`, do not edit.
+
+interface Vm {{
+    function startBroadcast() external;
+    function stopBroadcast() external;
+}}
+
+contract {BATCH_SCRIPT_CONTRACT} {{
+    Vm private constant vm = Vm(0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);
+
+    function run() external {{
+        vm.startBroadcast();
+{body}        vm.stopBroadcast();
+    }}
+
+    function _call(address to, uint256 value, bytes memory data) internal {{
+        (bool success, bytes memory returndata) = to.call{{value: value}}(data);
+        if (!success) {{
+            assembly {{
+                revert(add(returndata, 32), mload(returndata))
+            }}
+        }}
+    }}
+}}
+"#
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn can_encode_calls() {
+        let to = address!("00000000000000000000000000000000000000aa");
+        let call = BatchCall {
+            to,
+            sig: Some("transfer(address,uint256)".into()),
+            args: vec![to.to_string(), "1".into()],
+            value: Some("1gwei".into()),
+        };
+        let calldata = call.calldata().unwrap();
+        assert_eq!(calldata[..4], hex!("a9059cbb"));
+        assert_eq!(calldata.len(), 4 + 32 * 2);
+        assert_eq!(call.value().unwrap(), U256::from(1_000_000_000u64));
+
+        let raw = BatchCall { to, sig: Some("0xd09de08a".into()), ..Default::default() };
+        assert_eq!(raw.calldata().unwrap(), Bytes::from(hex!("d09de08a")));
+
+        let transfer = BatchCall { to, value: Some("1".into()), ..Default::default() };
+        assert!(transfer.calldata().unwrap().is_empty());
+
+        let missing =
+            BatchCall { to, sig: Some("increment(uint256)".into()), ..Default::default() };
+        assert!(missing.calldata().is_err());
+    }
+
+    #[test]
+    fn can_generate_batch_script() {
+        let calls: Vec<BatchCall> = serde_json::from_str(
+            r#"[
+                {"to": "0x00000000000000000000000000000000000000aa", "sig": "increment()"},
+                {"to": "0x00000000000000000000000000000000000000bb", "value": "2"}
+            ]"#,
+        )
+        .unwrap();
+        let source = batch_script(&calls, "calls.json").unwrap();
+        let first = format!("_call({}, 0, hex\"d09de08a\");\n", calls[0].to.to_checksum(None));
+        let second = format!("_call({}, 2, hex\"\");\n", calls[1].to.to_checksum(None));
+        assert!(source.contains(&first), "{source}");
+        assert!(source.contains(&second), "{source}");
+        assert!(source.find(&first) < source.find(&second));
+    }
+}
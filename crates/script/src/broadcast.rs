@@ -1,6 +1,6 @@
 use crate::{
-    build::LinkedBuildData, progress::ScriptProgress, sequence::ScriptSequenceKind,
-    verify::BroadcastedState, ScriptArgs, ScriptConfig,
+    build::LinkedBuildData, progress::ScriptProgress, safe::SafeBatch,
+    sequence::ScriptSequenceKind, verify::BroadcastedState, ScriptArgs, ScriptConfig,
 };
 use alloy_chains::Chain;
 use alloy_eips::eip2718::Encodable2718;
@@ -23,6 +23,7 @@ use futures::{future::join_all, StreamExt};
 use itertools::Itertools;
 use std::{
     collections::{HashMap, HashSet},
+    path::Path,
     sync::Arc,
 };
 
@@ -89,7 +90,13 @@ pub async fn send_transaction(
         SendTransactionKind::Raw(signer) => {
             debug!("sending transaction: {:?}", tx);
 
-            let signed = tx.build(signer).await?;
+            let signed = tx.build(signer).await.wrap_err_with(|| {
+                format!(
+                    "Failed to sign transaction from {from}. \
+                     If you are using a hardware wallet, make sure it is unlocked, \
+                     the Ethereum app is open and the transaction was approved on the device."
+                )
+            })?;
 
             // Submit the raw transaction
             provider.send_raw_transaction(signed.encoded_2718().as_ref()).await?
@@ -414,4 +421,20 @@ impl BundledState {
 
         Ok(())
     }
+
+    /// Writes the transactions to a Safe Transaction Builder batch instead of broadcasting them.
+    pub fn write_safe_batch(&self, path: &Path) -> Result<()> {
+        let [sequence] = self.sequence.sequences() else {
+            bail!("Safe batches are only supported for scripts sending transactions to one chain");
+        };
+
+        let name = self.build_data.build_data.target.name.clone();
+        SafeBatch::new(sequence, name)?.write(path)?;
+        shell::println(format!(
+            "\nWrote {} transactions to the Safe batch {}",
+            sequence.transactions.len(),
+            path.display()
+        ))?;
+        Ok(())
+    }
 }
@@ -18,6 +18,7 @@ use foundry_cli::{
 };
 use foundry_common::{
     fmt::{format_token, format_token_raw},
+    fs,
     provider::get_http_provider,
     shell, ContractsByArtifact,
 };
@@ -32,10 +33,13 @@ use foundry_evm::{
         render_trace_arena, CallTraceDecoder, CallTraceDecoderBuilder, TraceKind,
     },
 };
-use foundry_tweak::{build_tweak_data, tweak_backend, TweakData};
+use foundry_tweak::{build_tweak_data, tweak_backend, TweakData, TweakOptions};
 use futures::future::join_all;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
 use yansi::Paint;
 
 /// State after linking, contains the linked build data along with library addresses and optional
@@ -97,7 +101,7 @@ impl LinkedState {
                 ..Default::default()
             };
 
-            build_tweak_data(&cloned_projects, &rpc, args.tweak_quick).await?
+            build_tweak_data(&cloned_projects, &rpc, &TweakOptions::new(args.tweak_quick)).await?
         } else {
             TweakData::new()
         };
@@ -444,6 +448,32 @@ impl PreSimulationState {
         Ok(())
     }
 
+    /// Writes the decoded execution traces to `path`, as JSON if it has a `.json` extension and
+    /// rendered as text otherwise.
+    ///
+    /// All the traces are written if the script failed, like when printing them.
+    pub async fn write_traces(&self, path: &Path) -> Result<()> {
+        let result = &self.execution_result;
+        let decoder = &self.execution_artifacts.decoder;
+
+        let mut traces = Vec::new();
+        for (kind, trace) in &result.traces {
+            if kind.is_execution() || !result.success {
+                let mut trace = trace.clone();
+                decode_trace_arena(&mut trace, decoder).await?;
+                traces.push((*kind, trace));
+            }
+        }
+
+        let content = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::to_string(&traces)?
+        } else {
+            traces.iter().map(|(_, trace)| render_trace_arena(trace)).join("\n")
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
     pub async fn show_traces(&self) -> Result<()> {
         let verbosity = self.script_config.evm_opts.verbosity;
         let func = &self.execution_data.func;
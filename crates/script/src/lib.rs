@@ -47,9 +47,13 @@ use foundry_evm::{
 };
 use foundry_wallets::MultiWalletOpts;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 use yansi::Paint;
 
+mod batch;
 mod broadcast;
 mod build;
 mod execute;
@@ -58,6 +62,7 @@ mod progress;
 mod providers;
 mod receipts;
 mod runner;
+mod safe;
 mod sequence;
 mod simulate;
 mod transaction;
@@ -79,6 +84,14 @@ pub struct ScriptArgs {
     /// Arguments to pass to the script function.
     pub args: Vec<String>,
 
+    /// Treat the path as a batch file of calls to broadcast, instead of a script.
+    ///
+    /// The file is a JSON array of `{"to", "sig", "args", "value"}` entries, e.g.
+    /// `[{"to": "0x...", "sig": "transfer(address,uint256)", "args": ["0x...", "1"]}]`.
+    /// The calls are simulated and broadcast in order, like the calls of a script.
+    #[arg(long, conflicts_with_all = ["target_contract", "tweak"])]
+    pub batch: bool,
+
     /// The name of the contract you want to run.
     #[arg(long, visible_alias = "tc", value_name = "CONTRACT_NAME")]
     pub target_contract: Option<String>,
@@ -96,6 +109,15 @@ pub struct ScriptArgs {
     )]
     pub priority_gas_price: Option<U256>,
 
+    /// Max priority fee per gas for EIP1559 transactions, in gwei.
+    #[arg(
+        long,
+        conflicts_with = "priority_gas_price",
+        value_parser = foundry_cli::utils::parse_gwei_value,
+        value_name = "GWEI"
+    )]
+    pub priority_gas_price_gwei: Option<U256>,
+
     /// Use legacy transactions instead of EIP1559 ones.
     ///
     /// This is auto-enabled for common networks without EIP1559.
@@ -169,6 +191,13 @@ pub struct ScriptArgs {
     #[arg(long)]
     pub json: bool,
 
+    /// Write the decoded execution traces of the script to the given file.
+    ///
+    /// The traces are the same as the ones printed with `-vvvv`, and are written as JSON if the
+    /// file has a `.json` extension, or rendered as text otherwise.
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub trace_out: Option<PathBuf>,
+
     /// Gas price for legacy transactions, or max fee per gas for EIP1559 transactions.
     #[arg(
         long,
@@ -178,6 +207,15 @@ pub struct ScriptArgs {
     )]
     pub with_gas_price: Option<U256>,
 
+    /// Gas price for legacy transactions, or max fee per gas for EIP1559 transactions, in gwei.
+    #[arg(
+        long,
+        conflicts_with = "with_gas_price",
+        value_parser = foundry_cli::utils::parse_gwei_value,
+        value_name = "GWEI"
+    )]
+    pub gas_price_gwei: Option<U256>,
+
     /// One `forge clone`d project that will be used to tweak the code of the corresponding
     /// on-chain contract.
     ///
@@ -192,6 +230,19 @@ pub struct ScriptArgs {
     )]
     pub tweak: Vec<PathBuf>,
 
+    /// Write the simulated transactions to the given file as a Safe Transaction Builder batch,
+    /// instead of broadcasting them.
+    ///
+    /// The batch can be imported into the Safe UI to be executed by the multisig, which should be
+    /// the `--sender` of the script.
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        conflicts_with_all = ["broadcast", "resume", "skip_simulation", "multi", "verify"]
+    )]
+    pub safe_batch: Option<PathBuf>,
+
     /// When tweaking the contract, use the quick mode where we replay the creation transaction
     /// only with the state from the previous block.
     ///
@@ -216,12 +267,23 @@ pub struct ScriptArgs {
 }
 
 impl ScriptArgs {
-    async fn preprocess(self) -> Result<PreprocessedState> {
+    async fn preprocess(mut self) -> Result<PreprocessedState> {
+        self.check_sender()?;
+
         let script_wallets =
             ScriptWallets::new(self.wallets.get_multi_wallet().await?, self.evm_opts.sender);
 
         let (config, mut evm_opts) = self.load_config_and_evm_opts_emit_warnings()?;
 
+        // Batches run as a generated script instead.
+        if self.batch {
+            let script = batch::write_batch_script(Path::new(&self.path), &config)?;
+            self.path = script.to_string_lossy().to_string();
+            self.target_contract = Some(batch::BATCH_SCRIPT_CONTRACT.to_string());
+            self.sig = "run()".to_string();
+            self.args.clear();
+        }
+
         if let Some(sender) = self.maybe_load_private_key()? {
             evm_opts.sender = sender;
         }
@@ -232,9 +294,13 @@ impl ScriptArgs {
     }
 
     /// Executes the script
-    pub async fn run_script(self) -> Result<()> {
+    pub async fn run_script(mut self) -> Result<()> {
         trace!(target: "script", "executing script command");
 
+        // The gas prices are only read in wei from here on.
+        self.with_gas_price = self.gas_price_gwei.or(self.with_gas_price);
+        self.priority_gas_price = self.priority_gas_price_gwei.or(self.priority_gas_price);
+
         let compiled = self.preprocess().await?.compile()?;
 
         // Move from `CompiledState` to `BundledState` either by resuming or executing and
@@ -258,6 +324,11 @@ impl ScriptArgs {
                 return pre_simulation.run_debugger()
             }
 
+            // The traces are written first, since showing a failed script returns its error.
+            if let Some(path) = &pre_simulation.args.trace_out {
+                pre_simulation.write_traces(path).await?;
+            }
+
             if pre_simulation.args.json {
                 pre_simulation.show_json()?;
             } else {
@@ -289,6 +360,10 @@ impl ScriptArgs {
             pre_simulation.fill_metadata().await?.bundle().await?
         };
 
+        if let Some(path) = &bundled.args.safe_batch {
+            return bundled.write_safe_batch(path)
+        }
+
         // Exit early in case user didn't provide any broadcast/verify related flags.
         if !bundled.args.broadcast && !bundled.args.resume && !bundled.args.verify {
             shell::println("\nSIMULATION COMPLETE. To broadcast these transactions, add --broadcast and wallet configuration(s) to the previous command. See forge script --help for more.")?;
@@ -321,6 +396,35 @@ impl ScriptArgs {
         Ok(maybe_sender)
     }
 
+    /// Ensures that `--sender`, if provided along with private keys or mnemonics, matches one of
+    /// them.
+    ///
+    /// Otherwise the script would be simulated with one sender and broadcast with another.
+    fn check_sender(&self) -> Result<()> {
+        let Some(sender) = self.evm_opts.sender else { return Ok(()) };
+        if self.unlocked || self.skip_simulation {
+            return Ok(())
+        }
+
+        let signers = self
+            .wallets
+            .private_keys()?
+            .into_iter()
+            .chain(self.wallets.mnemonics()?)
+            .flatten()
+            .map(|signer| signer.address())
+            .collect::<Vec<_>>();
+        if signers.is_empty() || signers.contains(&sender) {
+            return Ok(())
+        }
+
+        eyre::bail!(
+            "The sender {sender} does not match the address of the provided signer(s): {}\n\
+             Either remove `--sender` or pass the key of the sender.",
+            signers.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+        )
+    }
+
     /// Returns the Function and calldata based on the signature
     ///
     /// If the `sig` is a valid human-readable function we find the corresponding function in the
@@ -660,6 +764,42 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn can_check_sender() {
+        let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "Contract.sol",
+            "--sender",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "--private-key",
+            key,
+        ]);
+        assert!(args.check_sender().is_ok());
+
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "Contract.sol",
+            "--sender",
+            "0x4e59b44847b379578588920ca78fbf26c0b4956c",
+            "--private-key",
+            key,
+        ]);
+        let err = args.check_sender().unwrap_err().to_string();
+        assert!(err.contains("does not match"), "{err}");
+
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "Contract.sol",
+            "--sender",
+            "0x4e59b44847b379578588920ca78fbf26c0b4956c",
+            "--private-key",
+            key,
+            "--skip-simulation",
+        ]);
+        assert!(args.check_sender().is_ok());
+    }
+
     #[test]
     fn can_merge_script_config() {
         let args = ScriptArgs::parse_from([
@@ -881,4 +1021,28 @@ mod tests {
         ]);
         assert!(args.with_gas_price.unwrap().is_zero());
     }
+
+    #[test]
+    fn can_parse_gwei_gas_prices() {
+        let args = ScriptArgs::parse_from([
+            "foundry-cli",
+            "DeployV1",
+            "--gas-price-gwei",
+            "1.5",
+            "--priority-gas-price-gwei",
+            "0.1",
+        ]);
+        assert_eq!(args.gas_price_gwei, Some(U256::from(1_500_000_000u64)));
+        assert_eq!(args.priority_gas_price_gwei, Some(U256::from(100_000_000u64)));
+
+        let args = ScriptArgs::try_parse_from([
+            "foundry-cli",
+            "DeployV1",
+            "--gas-price-gwei",
+            "1",
+            "--with-gas-price",
+            "1000000000",
+        ]);
+        assert!(args.is_err());
+    }
 }
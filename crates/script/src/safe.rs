@@ -0,0 +1,168 @@
+//! Safe Transaction Builder batches of the transactions of a script.
+//!
+//! Instead of broadcasting the transactions, they are written in the JSON format imported by the
+//! Transaction Builder app of the Safe UI, to be executed by a multisig.
+
+use crate::{sequence::ScriptSequence, transaction::TransactionWithMetadata};
+use alloy_primitives::{Address, Bytes, TxKind, U256};
+use eyre::Result;
+use foundry_common::fs;
+use serde::Serialize;
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The version of the batch file format.
+const SAFE_BATCH_VERSION: &str = "1.0";
+
+/// A batch of transactions, in the format of the Safe Transaction Builder.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatch {
+    pub version: String,
+    pub chain_id: String,
+    /// The creation time of the batch, in milliseconds since the epoch.
+    pub created_at: u64,
+    pub meta: SafeBatchMeta,
+    pub transactions: Vec<SafeTransaction>,
+}
+
+/// The metadata of a [`SafeBatch`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchMeta {
+    pub name: String,
+    pub description: String,
+    /// The Safe the transactions are sent from, i.e. the sender of the script.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_from_safe_address: Option<Address>,
+}
+
+/// A transaction of a [`SafeBatch`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SafeTransaction {
+    pub to: Address,
+    /// The value sent with the transaction, in wei.
+    pub value: String,
+    pub data: Bytes,
+    /// `0` for a call, Safe batches of scripts never contain delegate calls.
+    pub operation: u8,
+}
+
+impl SafeTransaction {
+    /// Converts a transaction of the script.
+    ///
+    /// Contract creations cannot be executed by a Safe, the contracts must be deployed through a
+    /// CREATE2 factory instead, e.g. with `new Contract{salt: ...}()`.
+    pub fn new(tx: &TransactionWithMetadata) -> Result<Self> {
+        let tx = &tx.transaction;
+        let Some(TxKind::Call(to)) = tx.to else {
+            eyre::bail!(
+                "Safe batches cannot contain contract creations, deploy the contract with CREATE2 \
+                 instead, e.g. with `new Contract{{salt: ...}}()`"
+            );
+        };
+        Ok(Self {
+            to,
+            value: tx.value.unwrap_or(U256::ZERO).to_string(),
+            data: tx.input.input().cloned().unwrap_or_default(),
+            operation: 0,
+        })
+    }
+}
+
+impl SafeBatch {
+    /// Creates the batch of the transactions of the sequence, in order.
+    pub fn new(sequence: &ScriptSequence, name: String) -> Result<Self> {
+        let transactions = sequence
+            .transactions
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                SafeTransaction::new(tx).map_err(|err| err.wrap_err(format!("transaction #{i}")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let from = sequence.transactions.front().and_then(|tx| tx.transaction.from);
+
+        Ok(Self {
+            version: SAFE_BATCH_VERSION.to_string(),
+            chain_id: sequence.chain.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or_default(),
+            meta: SafeBatchMeta {
+                name,
+                description: String::new(),
+                created_from_safe_address: from,
+            },
+            transactions,
+        })
+    }
+
+    /// Writes the batch to the given file.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write_json_file(path, self)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_serde::WithOtherFields;
+
+    fn transaction(to: Option<TxKind>) -> TransactionWithMetadata {
+        TransactionWithMetadata {
+            transaction: WithOtherFields::new(TransactionRequest {
+                from: Some(address!("00000000000000000000000000000000000000aa")),
+                to,
+                value: Some(U256::from(1)),
+                input: Bytes::from_static(&[0xd0, 0x9d, 0xe0, 0x8a]).into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn can_create_safe_batch() {
+        let to = address!("00000000000000000000000000000000000000bb");
+        let sequence = ScriptSequence {
+            transactions: [transaction(Some(TxKind::Call(to)))].into(),
+            chain: 1,
+            ..Default::default()
+        };
+        let batch = SafeBatch::new(&sequence, "Deploy".into()).unwrap();
+        assert_eq!(batch.chain_id, "1");
+        assert_eq!(
+            batch.meta.created_from_safe_address,
+            Some(address!("00000000000000000000000000000000000000aa"))
+        );
+        assert_eq!(
+            batch.transactions,
+            [SafeTransaction {
+                to,
+                value: "1".into(),
+                data: Bytes::from_static(&[0xd0, 0x9d, 0xe0, 0x8a]),
+                operation: 0,
+            }]
+        );
+
+        let json = serde_json::to_value(&batch).unwrap();
+        assert!(json["meta"]["createdFromSafeAddress"].is_string(), "{json}");
+        assert_eq!(json["transactions"][0]["data"], "0xd09de08a");
+    }
+
+    #[test]
+    fn rejects_contract_creations() {
+        let sequence = ScriptSequence {
+            transactions: [transaction(Some(TxKind::Create))].into(),
+            ..Default::default()
+        };
+        assert!(SafeBatch::new(&sequence, "Deploy".into()).is_err());
+    }
+}
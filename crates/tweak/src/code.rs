@@ -9,7 +9,7 @@ use alloy_rpc_types::{
     BlockTransactionsKind, Log, Transaction, TransactionReceipt,
 };
 use eyre::{eyre, Context, Result};
-use foundry_cli::{opts::RpcOpts, p_println, utils::init_progress};
+use foundry_cli::{opts::RpcOpts, utils::init_progress};
 use foundry_common::{
     cli_warn, is_known_system_sender, provider::ProviderBuilder, SYSTEM_TRANSACTION_TYPE,
 };
@@ -23,13 +23,14 @@ use foundry_evm::{
     InspectorExt,
 };
 use revm::{
+    inspectors::NoOpInspector,
     interpreter::{CreateInputs, CreateOutcome, Interpreter, OpCode},
-    primitives::{BlockEnv, EnvWithHandlerCfg, HashSet, SpecId},
+    primitives::{BlockEnv, EnvWithHandlerCfg, ExecutionResult, HashSet, Output, SpecId},
     EvmContext, Inspector,
 };
 use std::borrow::Cow;
 
-use crate::{constants::NonStandardPrecompiled, ClonedProject};
+use crate::{constants::NonStandardPrecompiled, ClonedProject, TweakOptions};
 
 #[derive(Debug)]
 struct TweakInspctor {
@@ -189,16 +190,82 @@ impl Inspector<&mut Backend> for TweakInspctor {
 pub async fn generate_tweaked_code(
     rpc: &RpcOpts,
     project: &ClonedProject,
-    quick: bool,
+    opts: &TweakOptions,
 ) -> Result<Bytes> {
-    println!("Tweaking the contract at {}...", project.metadata.address);
-    p_println!(!quick => "It may take time if the RPC has rate limits.");
+    // progress goes to stderr, so that the tweaked code can be piped
+    eprintln!("Tweaking the contract at {}...", project.metadata.address);
+    if !opts.quick {
+        eprintln!("It may take time if the RPC has rate limits.");
+    }
     // prepare the deployment bytecode (w/ parameters)
     let artifact = project.main_artifact()?;
     let tweaked_creation_code = prepare_tweaked_creation_code(project, &artifact)?;
 
     // let's tweak!
-    tweak(rpc, project, tweaked_creation_code, quick).await
+    tweak(rpc, project, tweaked_creation_code, opts).await
+}
+
+/// Generates the tweaked code by replaying the creation transaction itself, with the recompiled
+/// init code in place of the original one.
+///
+/// The transaction is sent by the original deployer with its original nonce, so the contract is
+/// created at its original address and its constructor sees the same `address(this)` and
+/// `msg.sender`, without hooking any opcode. Contracts created by a factory cannot be replayed this
+/// way, they are tweaked like [`generate_tweaked_code`] does.
+pub async fn generate_tweaked_code_from_creation_tx(
+    rpc: &RpcOpts,
+    project: &ClonedProject,
+    opts: &TweakOptions,
+) -> Result<Bytes> {
+    eprintln!("Replaying the creation transaction of {}...", project.metadata.address);
+    if !opts.quick {
+        eprintln!("It may take time if the RPC has rate limits.");
+    }
+    let artifact = project.main_artifact()?;
+    let tweaked_creation_code = prepare_tweaked_creation_code(project, &artifact)?;
+
+    replay_creation_tx(rpc, project, tweaked_creation_code, opts).await
+}
+
+// replay the creation transaction with the tweaked creation code as its input
+async fn replay_creation_tx(
+    rpc: &RpcOpts,
+    project: &ClonedProject,
+    tweaked_creation_code: Bytes,
+    opts: &TweakOptions,
+) -> Result<Bytes> {
+    let (mut db, mut env) = prepare_backend(rpc, project, opts).await?;
+    if env.tx.transact_to.is_call() {
+        // the contract is created by a factory, hook into its creation instead
+        return tweak_with_backend(db, env, project, tweaked_creation_code)
+    }
+
+    let deployer = project.metadata.deployer;
+    // older metadata may not record the deployer
+    eyre::ensure!(
+        deployer.is_zero() || env.tx.caller == deployer,
+        "the creation transaction is sent by {}, not by the deployer {deployer}",
+        env.tx.caller
+    );
+
+    env.tx.data = tweaked_creation_code;
+    relax_gas(&mut env)?;
+    let rv = db.inspect(&mut env, NoOpInspector)?;
+    let address = match rv.result {
+        ExecutionResult::Success { output: Output::Create(_, Some(address)), .. } => address,
+        result => eyre::bail!("failed to replay the creation transaction: {result:?}"),
+    };
+    eyre::ensure!(
+        address == project.metadata.address,
+        "the creation transaction created {address} instead of {}",
+        project.metadata.address
+    );
+
+    rv.state
+        .get(&address)
+        .and_then(|account| account.info.code.as_ref())
+        .map(|code| code.original_bytes())
+        .ok_or(eyre!("the tweaked code is not generated"))
 }
 
 // tweak the contract creation code
@@ -206,11 +273,20 @@ async fn tweak(
     rpc: &RpcOpts,
     project: &ClonedProject,
     tweaked_creation_code: Bytes,
-    quick: bool,
+    opts: &TweakOptions,
 ) -> Result<Bytes> {
     // prepare the execution backend
-    let (mut db, mut env) = prepare_backend(rpc, project, quick).await?;
+    let (db, env) = prepare_backend(rpc, project, opts).await?;
+    tweak_with_backend(db, env, project, tweaked_creation_code)
+}
 
+// tweak the contract creation code, on the state right before the creation transaction
+fn tweak_with_backend(
+    mut db: Backend,
+    mut env: EnvWithHandlerCfg,
+    project: &ClonedProject,
+    tweaked_creation_code: Bytes,
+) -> Result<Bytes> {
     // let hook into the creation process
     let mut inspector =
         TweakInspctor::new(Some(project.metadata.address), Some(tweaked_creation_code));
@@ -231,14 +307,7 @@ async fn tweak(
 
     // round 2: tweak the creation code
     inspector.prepare_for_tweak()?;
-    // disable gas_limit and decrease gas_fee for the inspector
-    env.cfg.disable_block_gas_limit = true;
-    env.cfg.disable_base_fee = true;
-    // increase gas_limit and decrease gas_price for the transaction
-    env.tx.gas_limit = env.tx.gas_limit.checked_mul(2).ok_or(eyre!("gas limit overflow"))?;
-    env.tx.gas_price =
-        env.tx.gas_price.checked_div(U256::from(2)).ok_or(eyre!("divided by zero"))?;
-    env.tx.gas_priority_fee = Some(U256::ZERO);
+    relax_gas(&mut env)?;
     // we do not care about the execution result in this round
     db.inspect(&mut env, &mut inspector)?;
     eyre::ensure!(
@@ -249,6 +318,19 @@ async fn tweak(
     inspector.tweaked_code.ok_or(eyre!("the tweaked code is not generated"))
 }
 
+// the tweaked creation code may consume more gas than the original one
+fn relax_gas(env: &mut EnvWithHandlerCfg) -> Result<()> {
+    // disable gas_limit and decrease gas_fee for the inspector
+    env.cfg.disable_block_gas_limit = true;
+    env.cfg.disable_base_fee = true;
+    // increase gas_limit and decrease gas_price for the transaction
+    env.tx.gas_limit = env.tx.gas_limit.checked_mul(2).ok_or(eyre!("gas limit overflow"))?;
+    env.tx.gas_price =
+        env.tx.gas_price.checked_div(U256::from(2)).ok_or(eyre!("divided by zero"))?;
+    env.tx.gas_priority_fee = Some(U256::ZERO);
+    Ok(())
+}
+
 fn prepare_tweaked_creation_code(
     project: &ClonedProject,
     artifact: &ConfigurableContractArtifact,
@@ -268,8 +350,10 @@ fn prepare_tweaked_creation_code(
 async fn prepare_backend(
     rpc: &RpcOpts,
     project: &ClonedProject,
-    quick: bool,
+    opts: &TweakOptions,
 ) -> Result<(Backend, EnvWithHandlerCfg)> {
+    let TweakOptions { quick, retry } = *opts;
+
     // get rpc_url
     let rpc_url =
         &rpc.url(Some(&project.config))?.unwrap_or(Cow::Borrowed("http://localhost:8545"));
@@ -283,15 +367,17 @@ async fn prepare_backend(
         .build()?;
 
     // get block number
-    let tx_receipt = provider
-        .get_transaction_receipt(project.metadata.creation_transaction)
+    let tx_receipt = retry
+        .retry(|| provider.get_transaction_receipt(project.metadata.creation_transaction))
         .await?
         .ok_or(eyre!("the transaction is not mined"))?;
     let block_number = tx_receipt.block_number.ok_or(eyre!("the transaction is not mined"))?;
 
     // then, we are going to replay all transactions before the creation transaction
-    let block = provider
-        .get_block(BlockId::Number(block_number.into()), BlockTransactionsKind::Full)
+    let block = retry
+        .retry(|| {
+            provider.get_block(BlockId::Number(block_number.into()), BlockTransactionsKind::Full)
+        })
         .await?
         .ok_or(eyre!("block not found"))?;
 
@@ -317,7 +403,9 @@ async fn prepare_backend(
         let BlockTransactions::Full(txs) = block.transactions else {
             return Err(eyre::eyre!("block transactions not found"));
         };
-        let Some(recipts) = provider.get_block_receipts(block_number.into()).await? else {
+        let Some(recipts) =
+            retry.retry(|| provider.get_block_receipts(block_number.into())).await?
+        else {
             return Err(eyre::eyre!("block receipts not found"));
         };
         txs.into_iter()
@@ -326,8 +414,8 @@ async fn prepare_backend(
             .collect()
     } else {
         vec![(
-            provider
-                .get_transaction_by_hash(project.metadata.creation_transaction)
+            retry
+                .retry(|| provider.get_transaction_by_hash(project.metadata.creation_transaction))
                 .await?
                 .ok_or(eyre!("the transaction is not mined"))?,
             tx_receipt,
@@ -344,6 +432,10 @@ async fn prepare_backend(
     let mut evm_opts = figment.extract::<EvmOpts>()?;
     evm_opts.fork_url = Some(rpc_url.to_string());
     evm_opts.fork_block_number = Some(block_number - 1);
+    // the forked state is read with the same retry policy, unless configured otherwise
+    evm_opts.fork_retries = evm_opts.fork_retries.or(Some(retry.max_retries));
+    evm_opts.fork_retry_backoff =
+        evm_opts.fork_retry_backoff.or(Some(retry.initial_backoff.as_millis() as u64));
 
     // get an updated config
     let config = Config::try_from(figment)?.sanitized();
@@ -508,7 +600,11 @@ fn probe_evm_version(
 mod tests {
     use std::str::FromStr;
 
-    use crate::{code::tweak, metadata::CloneMetadata, ClonedProject};
+    use crate::{
+        code::{replay_creation_tx, tweak},
+        metadata::CloneMetadata,
+        ClonedProject, TweakOptions,
+    };
 
     use alloy_primitives::Bytes;
     use foundry_cli::opts::RpcOpts;
@@ -530,6 +626,7 @@ mod tests {
                 deployer: Default::default(),
                 constructor_arguments: Default::default(),
                 storage_layout: Default::default(),
+                compiler_version: None,
             },
             ..Default::default()
         }
@@ -546,9 +643,41 @@ mod tests {
 
         let tweaked_code = format!(
             "{:?}",
-            tweak(&rpc, &fake_project, Bytes::from_str(FAKE_CREATION_CODE).unwrap(), false)
-                .await
-                .unwrap()
+            tweak(
+                &rpc,
+                &fake_project,
+                Bytes::from_str(FAKE_CREATION_CODE).unwrap(),
+                &TweakOptions::default()
+            )
+            .await
+            .unwrap()
+        );
+
+        assert!(
+            tweaked_code.starts_with(FAKE_DEPLOYED_CODE),
+            "the created code is not the same as the expected one"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_replay_direct_deployment() {
+        let fake_project = get_fake_project(
+            "0x8B3D32cf2bb4d0D16656f4c0b04Fa546274f1545",
+            "0x79820495643caf5a1e7e96578361c9ddba0e0735cd684ada7450254f6fd58f51",
+        );
+        let rpc_url = std::env::var("ETH_RPC_URL").unwrap_or("http://localhost:8545".to_string());
+        let rpc = RpcOpts { url: Some(rpc_url), ..Default::default() };
+
+        let tweaked_code = format!(
+            "{:?}",
+            replay_creation_tx(
+                &rpc,
+                &fake_project,
+                Bytes::from_str(FAKE_CREATION_CODE).unwrap(),
+                &TweakOptions::default()
+            )
+            .await
+            .unwrap()
         );
 
         assert!(
@@ -568,9 +697,14 @@ mod tests {
 
         let tweaked_code = format!(
             "{:?}",
-            tweak(&rpc, &fake_project, Bytes::from_str(FAKE_CREATION_CODE).unwrap(), false)
-                .await
-                .unwrap()
+            tweak(
+                &rpc,
+                &fake_project,
+                Bytes::from_str(FAKE_CREATION_CODE).unwrap(),
+                &TweakOptions::default()
+            )
+            .await
+            .unwrap()
         );
 
         assert!(
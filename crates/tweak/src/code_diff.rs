@@ -0,0 +1,271 @@
+//! Diffing of the runtime code compiled from the edited sources of a cloned project against the
+//! code compiled from its pristine sources.
+//! The pristine sources are the sources of the project at a git revision, usually the commit
+//! created by the `forge clone` command.
+
+use std::{fmt, ops::Range, path::Path};
+
+use alloy_primitives::Bytes;
+use eyre::{eyre, Result};
+use foundry_cli::utils::{CommandUtils, Git};
+use foundry_compilers::{
+    artifacts::{ConfigurableContractArtifact, Source},
+    project::ProjectCompiler,
+    Artifact,
+};
+
+use crate::{
+    error::{TweakError, TweakResult},
+    metadata::ClonedProject,
+};
+
+/// The differences between the runtime code of the main contract compiled from the pristine
+/// sources and from the edited sources of a cloned project.
+///
+/// The CBOR metadata appended by solc is not compared byte by byte, since any edit of the sources
+/// changes it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CodeDiff {
+    /// The length of the pristine runtime code, without its metadata.
+    pub pristine_len: usize,
+    /// The length of the edited runtime code, without its metadata.
+    pub edited_len: usize,
+    /// The byte ranges of the edited runtime code which differ from the pristine one.
+    ///
+    /// When the lengths differ, the bytes past the end of the shorter code are changed.
+    pub changed: Vec<Range<usize>>,
+    /// Whether the metadata changed.
+    pub metadata_changed: bool,
+}
+
+impl CodeDiff {
+    /// Compares the pristine and the edited runtime code.
+    pub fn new(pristine: &[u8], edited: &[u8]) -> Self {
+        let (pristine_code, pristine_metadata) = split_metadata(pristine);
+        let (edited_code, edited_metadata) = split_metadata(edited);
+
+        let mut changed: Vec<Range<usize>> = Vec::new();
+        for i in 0..pristine_code.len().max(edited_code.len()) {
+            if pristine_code.get(i) == edited_code.get(i) {
+                continue;
+            }
+            match changed.last_mut() {
+                Some(range) if range.end == i => range.end += 1,
+                _ => changed.push(i..i + 1),
+            }
+        }
+
+        Self {
+            pristine_len: pristine_code.len(),
+            edited_len: edited_code.len(),
+            changed,
+            metadata_changed: pristine_metadata != edited_metadata,
+        }
+    }
+
+    /// Returns `true` if the runtime code, ignoring its metadata, is unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty()
+    }
+
+    /// Returns the number of changed bytes.
+    pub fn changed_bytes(&self) -> usize {
+        self.changed.iter().map(|range| range.len()).sum()
+    }
+}
+
+impl fmt::Display for CodeDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            write!(f, "the runtime code is unchanged")?;
+            if self.metadata_changed {
+                write!(f, ", only its metadata changed")?;
+            }
+            return writeln!(f);
+        }
+
+        writeln!(
+            f,
+            "{} bytes of the runtime code changed ({} bytes before, {} bytes after):",
+            self.changed_bytes(),
+            self.pristine_len,
+            self.edited_len
+        )?;
+        for range in &self.changed {
+            writeln!(f, "- [{:#x}, {:#x})", range.start, range.end)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the runtime code of the main contract of the project with the one compiled from its
+/// sources at the git revision `baseline`.
+///
+/// Errors of the edited sources are reported like for tweaking, the ones of the pristine sources
+/// as [`TweakError::Baseline`].
+pub fn diff_code(project: &ClonedProject, baseline: &str) -> TweakResult<CodeDiff> {
+    let edited = deployed_code(&project.main_artifact()?, &project.metadata.target_contract)
+        .map_err(TweakError::Compilation)?;
+    let pristine = pristine_code(project, baseline).map_err(TweakError::Baseline)?;
+    Ok(CodeDiff::new(&pristine, &edited))
+}
+
+/// Compiles the sources of the project at the git revision `baseline` and returns the runtime code
+/// of its main contract.
+///
+/// The working tree is left untouched: the sources changed since the revision are replaced by
+/// their content at the revision in memory, and nothing is written to the cache or artifacts.
+fn pristine_code(project: &ClonedProject, baseline: &str) -> Result<Bytes> {
+    let root = &project.root;
+    let git = Git::new(root);
+    let changed = git
+        .cmd()
+        .args(["diff", "--name-only", "--relative", "--no-renames", baseline, "--"])
+        .get_stdout_lossy()?;
+
+    // the solc pin is already warned about when compiling the edited sources
+    let (mut config, _) = project.compile_config();
+    config.force = false;
+    let solc_project = config.ephemeral_no_artifacts_project()?;
+    let mut sources = solc_project.paths.read_input_files()?;
+    for path in changed.lines().map(Path::new) {
+        if path.extension().map_or(true, |ext| ext != "sol") {
+            continue;
+        }
+        let file = root.join(path);
+        let output =
+            git.cmd().arg("show").arg(format!("{baseline}:./{}", path.display())).output()?;
+        if output.status.success() {
+            sources.insert(file, Source::new(String::from_utf8_lossy(&output.stdout)));
+        } else {
+            // the file did not exist at the revision
+            sources.remove(&file);
+        }
+    }
+
+    let output = ProjectCompiler::with_sources(&solc_project, sources)?.compile()?;
+    if output.has_compiler_errors() {
+        eyre::bail!("{output}");
+    }
+    let name = &project.metadata.target_contract;
+    let (_, _, artifact) = output
+        .artifacts_with_files()
+        .find(|(_, contract_name, _)| *contract_name == name)
+        .ok_or_else(|| eyre!("the contract {name} is not found at {baseline}"))?;
+    deployed_code(artifact, name)
+}
+
+fn deployed_code(artifact: &ConfigurableContractArtifact, name: &str) -> Result<Bytes> {
+    artifact
+        .get_deployed_bytecode_bytes()
+        .map(|code| code.into_owned())
+        .ok_or_else(|| eyre!("the runtime code of the contract {name} is not linked"))
+}
+
+/// Splits the runtime code into the code and the CBOR metadata appended by solc, whose length is
+/// encoded in the last two bytes.
+fn split_metadata(code: &[u8]) -> (&[u8], &[u8]) {
+    let [.., hi, lo] = code else { return (code, &[]) };
+    let len = u16::from_be_bytes([*hi, *lo]) as usize + 2;
+    // the metadata is a CBOR map
+    match code.len().checked_sub(len) {
+        Some(start) if (0xa1..=0xb7).contains(&code[start]) => code.split_at(start),
+        _ => (code, &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::CloneMetadata;
+    use foundry_config::Config;
+    use semver::Version;
+
+    // `a1 64 "solc" 43 <version>`, followed by its length
+    const METADATA: [u8; 12] =
+        [0xa1, 0x64, 0x73, 0x6f, 0x6c, 0x63, 0x43, 0x00, 0x08, 0x13, 0x00, 0x0a];
+
+    fn with_metadata(code: &[u8], version: u8) -> Vec<u8> {
+        let mut metadata = METADATA;
+        metadata[9] = version;
+        [code, &metadata].concat()
+    }
+
+    #[test]
+    fn can_split_metadata() {
+        let code = with_metadata(&[0x60, 0x80], 0x13);
+        assert_eq!(split_metadata(&code), (&code[..2], &code[2..]));
+        assert_eq!(split_metadata(&[0x60, 0x80]), (&[0x60, 0x80][..], &[][..]));
+        assert_eq!(split_metadata(&[]), (&[][..], &[][..]));
+    }
+
+    #[test]
+    fn can_diff_code() {
+        let pristine = with_metadata(&[0x60, 0x80, 0x60, 0x40, 0x52, 0x00], 0x13);
+
+        let diff =
+            CodeDiff::new(&pristine, &with_metadata(&[0x60, 0x80, 0x60, 0x40, 0x52, 0x00], 0x14));
+        assert!(diff.is_empty());
+        assert!(diff.metadata_changed);
+
+        let edited = with_metadata(&[0x60, 0x81, 0x60, 0x40, 0x53, 0xfe, 0x00], 0x13);
+        let diff = CodeDiff::new(&pristine, &edited);
+        assert_eq!(diff.changed, vec![1..2, 4..7]);
+        assert_eq!(diff.changed_bytes(), 4);
+        assert_eq!((diff.pristine_len, diff.edited_len), (6, 7));
+        assert!(!diff.metadata_changed);
+        assert!(diff.to_string().contains("- [0x4, 0x7)"), "{diff}");
+    }
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .current_dir(root)
+            .args(["-c", "user.name=test", "-c", "user.email=test@test.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn can_diff_against_git_revision() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(tmp.path()).unwrap();
+        let counter = root.join("src/Counter.sol");
+        std::fs::create_dir_all(counter.parent().unwrap()).unwrap();
+        std::fs::write(
+            &counter,
+            "contract Counter { uint256 public number; function increment() public { number++; } }",
+        )
+        .unwrap();
+        git(&root, &["init", "-q"]);
+        git(&root, &["add", "."]);
+        git(&root, &["commit", "-q", "-m", "clone"]);
+
+        let project = ClonedProject {
+            config: Config::load_with_root(&root),
+            metadata: CloneMetadata {
+                path: "src/Counter.sol".into(),
+                target_contract: "Counter".to_string(),
+                compiler_version: Some(Version::new(0, 8, 19)),
+                ..Default::default()
+            },
+            root: root.clone(),
+            ..Default::default()
+        };
+        std::fs::write(
+            &counter,
+            "contract Counter { uint256 public number; function increment() public { number += 2; } }",
+        )
+        .unwrap();
+
+        let diff = project.code_diff("HEAD").unwrap();
+        assert!(!diff.is_empty(), "{diff}");
+        // the working tree is left untouched
+        assert!(std::fs::read_to_string(&counter).unwrap().contains("number += 2"));
+
+        let err = project.code_diff("no-such-revision").unwrap_err();
+        assert!(matches!(err, TweakError::Baseline(_)), "{err}");
+    }
+}
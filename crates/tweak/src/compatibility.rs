@@ -3,68 +3,121 @@
 //! Users may modify the source code of the cloned project, but the storage layout should remain the
 //! same as the original contract.
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, fmt};
 
-use foundry_compilers::artifacts::StorageLayout;
+use foundry_compilers::artifacts::{Storage, StorageLayout};
 
-use crate::metadata::ClonedProject;
+use crate::{
+    error::{TweakError, TweakResult},
+    metadata::ClonedProject,
+};
+
+/// The differences between the original storage layout and the current one which make them
+/// incompatible.
+#[derive(Clone, Debug, Default)]
+pub struct StorageLayoutDiff {
+    /// State variables of the original layout that are missing in the current layout.
+    pub missing: Vec<Storage>,
+    /// State variables whose slot or offset changed, as `(original, current)` pairs.
+    pub relocated: Vec<(Storage, Storage)>,
+}
+
+impl StorageLayoutDiff {
+    /// Returns `true` if the two layouts are compatible.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.relocated.is_empty()
+    }
+}
+
+impl fmt::Display for StorageLayoutDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for var in &self.missing {
+            writeln!(
+                f,
+                "- {}:{} (slot {}, offset {}) is missing",
+                var.contract, var.label, var.slot, var.offset
+            )?;
+        }
+        for (original, current) in &self.relocated {
+            writeln!(
+                f,
+                "- {}:{} moved from (slot {}, offset {}) to (slot {}, offset {})",
+                original.contract,
+                original.label,
+                original.slot,
+                original.offset,
+                current.slot,
+                current.offset
+            )?;
+        }
+        Ok(())
+    }
+}
 
 /// Check the tweak compatibility of the project with the given root.
 /// The project is compatible if:
 /// - the project's storage layout is the same as the original contract.
 ///
 /// If the project is not compatible, an error is returned.
-pub fn check_storage_compatibility(cloned_project: &ClonedProject) -> eyre::Result<()> {
+pub fn check_storage_compatibility(cloned_project: &ClonedProject) -> TweakResult<()> {
     // to check the storage layout compatibility, we need to download the original contract's code
     // from etherscan and compile.
     let original_layout = cloned_project.metadata.storage_layout.to_owned();
-    let current_layout = cloned_project
-        .main_artifact()?
-        .storage_layout
-        .to_owned()
-        .expect("storage layout is missing");
+    let current_layout =
+        cloned_project.main_artifact()?.storage_layout.to_owned().ok_or_else(|| {
+            TweakError::MissingStorageLayout(cloned_project.metadata.target_contract.clone())
+        })?;
     check_storage_layout_compatibility(&original_layout, &current_layout)
 }
 
 /// Check that the current storage layout is compatible with the original storage layout.
 /// Each state variable in the original storage layout should have the same slot in the current
 /// storage layout.
+///
+/// On incompatibility, all offending state variables are reported in a [`StorageLayoutDiff`].
 pub fn check_storage_layout_compatibility(
     original: &StorageLayout,
     current: &StorageLayout,
-) -> eyre::Result<()> {
+) -> TweakResult<()> {
+    let diff = diff_storage_layout(original, current);
+    if diff.is_empty() {
+        Ok(())
+    } else {
+        Err(TweakError::StorageIncompatible(diff))
+    }
+}
+
+/// Compute the differences between the original and the current storage layout.
+pub fn diff_storage_layout(original: &StorageLayout, current: &StorageLayout) -> StorageLayoutDiff {
     // TODO: need a more sophisticated comparison algorithm.
     // The current implementation is a naive one that only checks the slot and offset of each
     // storage variables. check storage variables
     let current_storage_var_map = BTreeMap::from_iter(
         current.storage.iter().map(|v| (format!("{}:{}", v.contract, v.label), v)),
     );
+    let mut diff = StorageLayoutDiff::default();
     for original_var in original.storage.iter() {
-        let current_var = current_storage_var_map
-            .get(&format!("{}:{}", original_var.contract, original_var.label));
-        if current_var.is_none() {
-            return Err(eyre::eyre!(
-                "the storage variable {} is missing in the current contract",
-                original_var.label
-            ));
-        }
-        let current_var = current_var.unwrap();
+        let Some(current_var) = current_storage_var_map
+            .get(&format!("{}:{}", original_var.contract, original_var.label))
+        else {
+            diff.missing.push(original_var.clone());
+            continue;
+        };
         // offset, slot, type should be the same
         if original_var.offset != current_var.offset || original_var.slot != current_var.slot {
-            return Err(eyre::eyre!(
-                "the storage variable {} has different layout in the current contract",
-                original_var.label
-            ));
+            diff.relocated.push((original_var.clone(), (*current_var).clone()));
         }
     }
 
-    Ok(())
+    diff
 }
 
 #[cfg(test)]
 mod tests {
     use foundry_compilers::artifacts::StorageLayout;
 
+    use crate::TweakError;
+
     fn load_json_layout(json_str: &str) -> StorageLayout {
         serde_json::from_str(json_str).unwrap()
     }
@@ -153,7 +206,15 @@ mod tests {
             }
         }"#,
         );
-        assert!(super::check_storage_layout_compatibility(&original, &current).is_err());
+        let Err(TweakError::StorageIncompatible(diff)) =
+            super::check_storage_layout_compatibility(&original, &current)
+        else {
+            panic!("expected a storage incompatibility error");
+        };
+        assert!(diff.missing.is_empty());
+        assert_eq!(diff.relocated.len(), 1);
+        assert_eq!(diff.relocated[0].0.slot, "0");
+        assert_eq!(diff.relocated[0].1.slot, "1");
     }
 
     #[test]
@@ -0,0 +1,50 @@
+//! Errors that can occur when tweaking an on-chain contract with a cloned project.
+
+use alloy_primitives::ChainId;
+use foundry_compilers::error::SolcError;
+use foundry_evm::backend::DatabaseError;
+
+use crate::compatibility::StorageLayoutDiff;
+
+/// Result alias used throughout the tweak crate.
+pub type TweakResult<T, E = TweakError> = std::result::Result<T, E>;
+
+/// Errors that can occur when loading, compiling or tweaking a cloned project.
+#[derive(Debug, thiserror::Error)]
+pub enum TweakError {
+    /// The chain of the cloned project differs from the chain of the on-chain contract.
+    #[error("the chain id of the project ({project}) is different from the chain id of the on-chain contract ({onchain})")]
+    ChainMismatch { project: ChainId, onchain: ChainId },
+    /// The storage layout of the cloned project is not compatible with the on-chain contract.
+    #[error(
+        "the storage layout of the current contract is incompatible with the original one:\n{0}"
+    )]
+    StorageIncompatible(StorageLayoutDiff),
+    /// The target contract is not found in the compile output.
+    #[error("the contract {0} is not found in the project")]
+    MissingArtifact(String),
+    /// The target contract artifact does not carry a storage layout.
+    #[error("the storage layout of the contract {0} is missing")]
+    MissingStorageLayout(String),
+    /// The cloned project failed to compile.
+    #[error("failed to compile the cloned project: {0}")]
+    Compilation(eyre::Report),
+    /// The tweaked code could not be generated by replaying the creation transaction.
+    #[error("failed to generate the tweaked code: {0}")]
+    CodeGeneration(eyre::Report),
+    /// The pristine sources of the cloned project could not be compiled.
+    #[error("failed to compile the pristine sources of the cloned project: {0}")]
+    Baseline(eyre::Report),
+    /// The transaction could not be simulated against the original or the tweaked code.
+    #[error("failed to preview the transaction: {0}")]
+    Preview(eyre::Report),
+    /// The `.clone.meta` file could not be parsed.
+    #[error("invalid clone metadata: {0}")]
+    Metadata(#[from] serde_json::Error),
+    #[error(transparent)]
+    Solc(#[from] SolcError),
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
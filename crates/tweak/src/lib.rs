@@ -1,39 +1,66 @@
 pub mod code;
+mod code_diff;
 pub mod compatibility;
 mod constants;
+mod error;
 mod metadata;
+mod preview;
+mod retry;
 
 use std::collections::BTreeMap;
 
 use alloy_primitives::{keccak256, Address, Bytes, B256};
-use eyre::Result;
-
 use foundry_cli::opts::RpcOpts;
 use foundry_evm::{backend::Backend, fork::CreateFork};
-pub use metadata::ClonedProject;
 use revm::{
     primitives::{Bytecode, KECCAK_EMPTY},
     Database,
 };
 
+pub use code_diff::CodeDiff;
+pub use compatibility::StorageLayoutDiff;
+pub use error::{TweakError, TweakResult};
+pub use metadata::ClonedProject;
+pub use preview::{AccountDiff, StateDiff, StateDiffPreview};
+pub use retry::RetryPolicy;
+
 pub type TweakData = BTreeMap<Address, Bytes>;
 
+/// Options controlling how the tweaked code is generated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TweakOptions {
+    /// Skip replaying the transactions preceding the creation transaction in its block.
+    pub quick: bool,
+    /// The retry policy of the RPC reads.
+    pub retry: RetryPolicy,
+}
+
+impl TweakOptions {
+    /// Creates the default options, with the given `quick` flag.
+    pub fn new(quick: bool) -> Self {
+        Self { quick, ..Default::default() }
+    }
+}
+
 pub async fn build_tweak_data(
     projects: &Vec<ClonedProject>,
     rpc: &RpcOpts,
-    quick: bool,
-) -> Result<TweakData> {
+    opts: &TweakOptions,
+) -> TweakResult<TweakData> {
     let mut tweak_data = BTreeMap::new();
     for project in projects {
         let metadata = &project.metadata;
         let address = metadata.address;
-        let code = project.tweaked_code(rpc, quick).await?;
+        let code = project.tweaked_code(rpc, opts).await?;
         tweak_data.insert(address, code);
     }
     Ok(tweak_data)
 }
 
-pub fn build_tweaked_backend(fork: Option<CreateFork>, tweak_data: &TweakData) -> Result<Backend> {
+pub fn build_tweaked_backend(
+    fork: Option<CreateFork>,
+    tweak_data: &TweakData,
+) -> TweakResult<Backend> {
     let mut backend = Backend::spawn(fork);
     for (address, code) in tweak_data {
         tweak_backend_once(&mut backend, *address, code.clone())?;
@@ -46,7 +73,7 @@ pub fn tweak_backend_once(
     backend: &mut Backend,
     tweak_address: Address,
     tweaked_code: Bytes,
-) -> Result<()> {
+) -> TweakResult<()> {
     let mut info = backend.basic(tweak_address)?.unwrap_or_default();
     let code_hash = if tweaked_code.as_ref().is_empty() {
         KECCAK_EMPTY
@@ -59,7 +86,7 @@ pub fn tweak_backend_once(
     Ok(())
 }
 
-pub fn tweak_backend(backend: &mut Backend, tweak_data: &TweakData) -> Result<()> {
+pub fn tweak_backend(backend: &mut Backend, tweak_data: &TweakData) -> TweakResult<()> {
     for (tweak_address, tweaked_code) in tweak_data {
         let mut info = backend.basic(*tweak_address)?.unwrap_or_default();
         let code_hash = if tweaked_code.as_ref().is_empty() {
@@ -4,16 +4,22 @@ use std::{
 };
 
 use alloy_primitives::{Address, Bytes, ChainId, TxHash};
-use eyre::{eyre, Result};
+use alloy_rpc_types::TransactionRequest;
 use foundry_cli::opts::RpcOpts;
-use foundry_common::compile::ProjectCompiler;
+use foundry_common::{cli_warn, compile::ProjectCompiler};
 use foundry_compilers::{
     artifacts::{
         output_selection::ContractOutputSelection, ConfigurableContractArtifact, StorageLayout,
     },
     ProjectCompileOutput,
 };
-use foundry_config::Config;
+use foundry_config::{Config, SolcReq};
+use semver::Version;
+
+use crate::{
+    error::{TweakError, TweakResult},
+    CodeDiff, StateDiffPreview, TweakOptions,
+};
 
 /// ClonedProject represents a foundry project that is cloned by the `forge clone` command.
 /// It couples with an on-chain contract instance.
@@ -66,7 +72,7 @@ impl ClonedProject {
     /// Load the cloned project from the root directory of the project.
     /// If the clone metadata file does not exist, an error is returned.
     /// The root should be an absolute path.
-    pub fn load_with_root(root: impl Into<PathBuf>) -> Result<ClonedProject> {
+    pub fn load_with_root(root: impl Into<PathBuf>) -> TweakResult<ClonedProject> {
         let root = root.into();
         assert!(root.is_absolute());
         let cwd = std::env::current_dir()?;
@@ -85,34 +91,49 @@ impl ClonedProject {
 
     /// Compile the project and return the artifacts.
     /// The compile output is cached.
-    /// A workaround for the insufficient implementation of Config::load_with_root.
-    pub fn compile_safe(&self) -> Result<ProjectCompileOutput> {
+    /// The current working directory is not used, so cloned projects can be compiled concurrently.
+    pub fn compile_safe(&self) -> TweakResult<ProjectCompileOutput> {
         // check the cache
         if Self::is_cached(self._compile_output.clone()) {
             return Ok(Self::get_cache(self._compile_output.clone()));
         }
 
-        // load the foundry config
-        // XXX (ZZ): some insufficient implementation of Config::project_paths(). It depends on the
-        // current working directory, preventiong us from invoking this function directly
-        let cwd = std::env::current_dir()?;
-        std::env::set_current_dir(&self.root)?;
-
         // compile the project to get the current artifacts
-        let mut config = self.config.clone();
-        config.extra_output.push(ContractOutputSelection::StorageLayout);
+        let (config, configured) = self.compile_config();
+        if let (Some(configured), Some(version)) = (configured, &self.metadata.compiler_version) {
+            cli_warn!(
+                "the cloned project is configured with solc {configured}, but the contract was deployed with solc {version}; compiling with solc {version}"
+            );
+        }
         let project = config.project()?;
-        let output = ProjectCompiler::new().compile(&project)?;
-
-        std::env::set_current_dir(cwd)?;
+        // the compiler output would be mixed with the tweaked code on stdout, errors are still
+        // returned
+        let output = ProjectCompiler::new()
+            .quiet(true)
+            .compile_with_paths(&project)
+            .map_err(TweakError::Compilation)?;
 
         // cache the output
         Self::set_cache(self._compile_output.clone(), output);
         Ok(Self::get_cache(self._compile_output.clone()))
     }
 
+    /// Returns the config the project is compiled with, and the solc configured for the project
+    /// if it is replaced by the one the contract was deployed with.
+    pub(crate) fn compile_config(&self) -> (Config, Option<String>) {
+        let mut config = self.config.clone();
+        config.extra_output.push(ContractOutputSelection::StorageLayout);
+        // the bytecode only matches the on-chain one with the solc version it was deployed with
+        let configured = self
+            .metadata
+            .compiler_version
+            .as_ref()
+            .and_then(|version| pin_compiler_version(&mut config, version));
+        (config, configured)
+    }
+
     /// Get the artifact of the main contract of the project.
-    pub fn main_artifact(&self) -> Result<ConfigurableContractArtifact> {
+    pub fn main_artifact(&self) -> TweakResult<ConfigurableContractArtifact> {
         // check the cache
         if Self::is_cached(self._main_artifact.clone()) {
             return Ok(Self::get_cache(self._main_artifact.clone()));
@@ -122,9 +143,7 @@ impl ClonedProject {
         let (_, _, artifact) = output
             .artifacts_with_files()
             .find(|(_, contract_name, _)| **contract_name == self.metadata.target_contract)
-            .ok_or_else(|| {
-                eyre!("the contract {} is not found in the project", self.metadata.target_contract)
-            })?;
+            .ok_or_else(|| TweakError::MissingArtifact(self.metadata.target_contract.clone()))?;
 
         // cache the artifact
         Self::set_cache(self._main_artifact.clone(), artifact.clone());
@@ -132,28 +151,90 @@ impl ClonedProject {
     }
 
     /// Get the tweaked code of the main contract of the project.
-    pub async fn tweaked_code(&self, rpc: &RpcOpts, quick: bool) -> Result<Bytes> {
+    pub async fn tweaked_code(&self, rpc: &RpcOpts, opts: &TweakOptions) -> TweakResult<Bytes> {
+        self.check_tweakable()?;
+
+        // get tweaked code
+        super::code::generate_tweaked_code(rpc, self, opts)
+            .await
+            .map_err(TweakError::CodeGeneration)
+    }
+
+    /// Get the tweaked code of the main contract of the project, by replaying its creation
+    /// transaction sent by its deployer with the recompiled init code.
+    ///
+    /// This is more faithful than [`ClonedProject::tweaked_code`] for constructors reading their
+    /// own address or their caller, as the contract is created in its original context.
+    pub async fn tweaked_code_from_creation_tx(
+        &self,
+        rpc: &RpcOpts,
+        opts: &TweakOptions,
+    ) -> TweakResult<Bytes> {
+        self.check_tweakable()?;
+
+        super::code::generate_tweaked_code_from_creation_tx(rpc, self, opts)
+            .await
+            .map_err(TweakError::CodeGeneration)
+    }
+
+    /// Checks that the project is on the chain of the on-chain contract and that its storage
+    /// layout is compatible with it.
+    fn check_tweakable(&self) -> TweakResult<()> {
         // check chain id
-        if self.config.chain.unwrap_or_default().id() != self.metadata.chain_id {
-            return Err(eyre!(
-                "the chain id of the project ({}) is different from the chain id of the on-chain contract ({})",
-                self.config.chain.unwrap_or_default().id(),
-                self.metadata.chain_id
-            ));
+        let project_chain_id = self.config.chain.unwrap_or_default().id();
+        if project_chain_id != self.metadata.chain_id {
+            return Err(TweakError::ChainMismatch {
+                project: project_chain_id,
+                onchain: self.metadata.chain_id,
+            });
         }
         // check the storage compatibility
-        super::compatibility::check_storage_compatibility(self)?;
+        super::compatibility::check_storage_compatibility(self)
+    }
 
-        // get tweaked code
-        let code = super::code::generate_tweaked_code(rpc, self, quick).await?;
-        Ok(code)
+    /// Compare the runtime code of the main contract with the one compiled from the sources of the
+    /// project at the git revision `baseline`, e.g. `HEAD` for the commit created by `forge clone`
+    /// if the edits were not committed.
+    /// This confirms that the edits changed the code, and where, before tweaking the contract.
+    pub fn code_diff(&self, baseline: &str) -> TweakResult<CodeDiff> {
+        super::code_diff::diff_code(self, baseline)
+    }
+
+    /// Simulate the transaction on the latest state of the chain, against both the on-chain code
+    /// and the tweaked code of the main contract, and return the resulting state diffs.
+    /// Nothing is committed, so this is a dry-run of what the tweak would change.
+    pub async fn preview_state_diff(
+        &self,
+        rpc: &RpcOpts,
+        opts: &TweakOptions,
+        tx: &TransactionRequest,
+    ) -> TweakResult<StateDiffPreview> {
+        let tweaked_code = self.tweaked_code(rpc, opts).await?;
+        super::preview::preview_state_diff(rpc, self, tweaked_code, opts, tx)
+            .await
+            .map_err(TweakError::Preview)
     }
 }
 
+/// Pins the solc version of the config to the given version.
+///
+/// Returns the configured solc, if it was a different one.
+fn pin_compiler_version(config: &mut Config, version: &Version) -> Option<String> {
+    let configured = match config.solc.take() {
+        Some(SolcReq::Version(configured)) if configured == *version => None,
+        Some(SolcReq::Version(configured)) => Some(configured.to_string()),
+        Some(SolcReq::Local(path)) => Some(path.display().to_string()),
+        None => None,
+    };
+    config.solc = Some(SolcReq::Version(version.clone()));
+    config.auto_detect_solc = false;
+    configured
+}
+
 /// CloneMetadata stores the metadata that are not included by `foundry.toml` but necessary for a
 /// cloned contract. This struct is the twin of the `CloneMetadata` struct in the `clone` command of
 /// `forge` crate.
-#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CloneMetadata {
     /// The path to the source file that contains the contract declaration.
@@ -173,15 +254,93 @@ pub struct CloneMetadata {
     pub constructor_arguments: Bytes,
     /// The storage layout of the contract.
     pub storage_layout: StorageLayout,
+    /// The version of solc the contract was deployed with.
+    ///
+    /// Missing from the metadata of projects cloned before the version was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compiler_version: Option<Version>,
 }
 
 impl CloneMetadata {
     /// Load the metadata from the `clone.toml` file in the root directory of the project.
     /// If the file does not exist, an error is returned.
-    pub fn load_with_root(root: impl Into<PathBuf>) -> Result<CloneMetadata> {
+    pub fn load_with_root(root: impl Into<PathBuf>) -> TweakResult<CloneMetadata> {
         let path = root.into().join(".clone.meta");
         let metadata = std::fs::read_to_string(path)?;
         let metadata = serde_json::from_str(&metadata)?;
         Ok(metadata)
     }
+
+    /// Save the metadata to the `.clone.meta` file in the root directory of the project, in the
+    /// format read by [`load_with_root`](Self::load_with_root).
+    pub fn save_to_root(&self, root: impl Into<PathBuf>) -> TweakResult<()> {
+        let path = root.into().join(".clone.meta");
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, bytes};
+
+    #[test]
+    fn clone_metadata_round_trips() {
+        let metadata = CloneMetadata {
+            path: "src/Counter.sol".into(),
+            target_contract: "Counter".to_string(),
+            address: address!("00000000000000000000000000000000000000aa"),
+            chain_id: 1,
+            creation_transaction: b256!(
+                "0000000000000000000000000000000000000000000000000000000000000001"
+            ),
+            deployer: address!("00000000000000000000000000000000000000bb"),
+            constructor_arguments: bytes!("0102"),
+            storage_layout: StorageLayout::default(),
+            compiler_version: Some(Version::new(0, 8, 19)),
+        };
+
+        let root = tempfile::tempdir().unwrap();
+        metadata.save_to_root(root.path()).unwrap();
+        let raw: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(root.path().join(".clone.meta")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(raw["targetContract"], "Counter");
+        assert_eq!(raw["chainId"], 1);
+        assert_eq!(raw["constructorArguments"], "0x0102");
+        assert_eq!(raw["compilerVersion"], "0.8.19");
+        assert!(raw["storageLayout"].is_object(), "{raw}");
+
+        let loaded = CloneMetadata::load_with_root(root.path()).unwrap();
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), raw);
+        assert_eq!(loaded.path, metadata.path);
+        assert_eq!(loaded.address, metadata.address);
+        assert_eq!(loaded.creation_transaction, metadata.creation_transaction);
+        assert_eq!(loaded.compiler_version, metadata.compiler_version);
+
+        // metadata written before the compiler version was recorded
+        let mut legacy = raw;
+        legacy.as_object_mut().unwrap().remove("compilerVersion");
+        let legacy: CloneMetadata = serde_json::from_value(legacy).unwrap();
+        assert_eq!(legacy.compiler_version, None);
+    }
+
+    #[test]
+    fn can_pin_compiler_version() {
+        let version = Version::new(0, 8, 19);
+
+        let mut config = Config::default();
+        assert_eq!(pin_compiler_version(&mut config, &version), None);
+        assert_eq!(config.solc, Some(SolcReq::Version(version.clone())));
+        assert!(!config.auto_detect_solc);
+
+        assert_eq!(pin_compiler_version(&mut config, &version), None);
+
+        let mut config =
+            Config { solc: Some(SolcReq::Version(Version::new(0, 8, 20))), ..Default::default() };
+        assert_eq!(pin_compiler_version(&mut config, &version), Some("0.8.20".to_string()));
+        assert_eq!(config.solc, Some(SolcReq::Version(version)));
+    }
 }
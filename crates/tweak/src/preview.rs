@@ -0,0 +1,175 @@
+//! Preview of the state changes of a transaction against the original and the tweaked code.
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use alloy_primitives::{Address, Bytes, Log, TxKind, U256};
+use alloy_rpc_types::TransactionRequest;
+use eyre::{eyre, Result};
+use foundry_cli::opts::RpcOpts;
+use foundry_config::{figment::Figment, Config};
+use foundry_evm::{
+    backend::Backend,
+    executors::{Executor, ExecutorBuilder, RawCallResult},
+    opts::EvmOpts,
+};
+use revm::DatabaseRef;
+
+use crate::{tweak_backend_once, ClonedProject, TweakOptions};
+
+/// The changes of a single account caused by a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The balance before and after the transaction, if it changed.
+    pub balance: Option<(U256, U256)>,
+    /// The changed storage slots, with their value before and after the transaction.
+    pub storage: BTreeMap<U256, (U256, U256)>,
+}
+
+/// The state changes and events produced by a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// The changed accounts.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+    /// The emitted events, in emission order.
+    pub logs: Vec<Log>,
+}
+
+/// The state diffs of a transaction run against the original and the tweaked code.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiffPreview {
+    /// The state diff with the on-chain code.
+    pub original: StateDiff,
+    /// The state diff with the tweaked code.
+    pub tweaked: StateDiff,
+}
+
+/// Simulates the transaction on top of the latest forked state, once with the original code of the
+/// cloned contract and once with the tweaked code.
+///
+/// The simulation is never committed, so the previews of several transactions are independent.
+pub(crate) async fn preview_state_diff(
+    rpc: &RpcOpts,
+    project: &ClonedProject,
+    tweaked_code: Bytes,
+    opts: &TweakOptions,
+    tx: &TransactionRequest,
+) -> Result<StateDiffPreview> {
+    let Some(TxKind::Call(to)) = tx.to else {
+        return Err(eyre!("only calls can be previewed, not contract creations"));
+    };
+    let from = tx.from.unwrap_or_default();
+    let data = tx.input.input().cloned().unwrap_or_default();
+    let value = tx.value.unwrap_or_default();
+
+    let original = fork_executor(rpc, project, opts).await?;
+    let mut tweaked = fork_executor(rpc, project, opts).await?;
+    tweak_backend_once(tweaked.backend_mut(), project.metadata.address, tweaked_code)?;
+
+    let original_result = original.call_raw(from, to, data.clone(), value)?;
+    let tweaked_result = tweaked.call_raw(from, to, data, value)?;
+    Ok(StateDiffPreview {
+        original: state_diff(original.backend(), original_result),
+        tweaked: state_diff(tweaked.backend(), tweaked_result),
+    })
+}
+
+/// Creates an executor on top of the latest state of the chain of the cloned project.
+async fn fork_executor(
+    rpc: &RpcOpts,
+    project: &ClonedProject,
+    opts: &TweakOptions,
+) -> Result<Executor> {
+    let rpc_url =
+        &rpc.url(Some(&project.config))?.unwrap_or(Cow::Borrowed("http://localhost:8545"));
+
+    let figment: Figment = project.config.clone().into();
+    let figment = figment.merge(rpc);
+
+    let mut evm_opts = figment.extract::<EvmOpts>()?;
+    evm_opts.fork_url = Some(rpc_url.to_string());
+    evm_opts.fork_retries = evm_opts.fork_retries.or(Some(opts.retry.max_retries));
+    evm_opts.fork_retry_backoff =
+        evm_opts.fork_retry_backoff.or(Some(opts.retry.initial_backoff.as_millis() as u64));
+
+    let config = Config::try_from(figment)?.sanitized();
+    let env = evm_opts.evm_env().await?;
+    let db = Backend::spawn(evm_opts.get_fork(&config, env.clone()));
+    Ok(ExecutorBuilder::new().spec(config.evm_spec_id()).build(env, db))
+}
+
+/// Collects the changes of the call result, relative to the state of the backend it ran on.
+fn state_diff(backend: &Backend, result: RawCallResult) -> StateDiff {
+    let mut accounts = BTreeMap::new();
+    for (address, account) in result.state_changeset {
+        if !account.is_touched() {
+            continue
+        }
+
+        let before =
+            backend.basic_ref(address).ok().flatten().map(|info| info.balance).unwrap_or_default();
+        let balance = (before != account.info.balance).then_some((before, account.info.balance));
+        let storage = account
+            .storage
+            .iter()
+            .filter(|(_, slot)| slot.is_changed())
+            .map(|(slot, value)| (*slot, (value.original_value, value.present_value)))
+            .collect::<BTreeMap<_, _>>();
+
+        if balance.is_some() || !storage.is_empty() {
+            accounts.insert(address, AccountDiff { balance, storage });
+        }
+    }
+    StateDiff { success: !result.reverted, accounts, logs: result.logs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use revm::primitives::{Account, AccountInfo, EvmStorageSlot, HashMap};
+
+    #[test]
+    fn collects_changed_balances_and_storage() {
+        let changed = address!("00000000000000000000000000000000000000aa");
+        let unchanged = address!("00000000000000000000000000000000000000bb");
+
+        let mut backend = Backend::spawn(None);
+        backend.insert_account_info(
+            changed,
+            AccountInfo { balance: U256::from(10), ..Default::default() },
+        );
+
+        let mut account =
+            Account::from(AccountInfo { balance: U256::from(7), ..Default::default() });
+        account.storage = HashMap::from([
+            (U256::from(1), EvmStorageSlot::new_changed(U256::ZERO, U256::from(42))),
+            (U256::from(2), EvmStorageSlot::new(U256::from(3))),
+        ]);
+        account.mark_touch();
+        let mut untouched = Account::from(AccountInfo::default());
+        untouched.storage = HashMap::from([(
+            U256::from(1),
+            EvmStorageSlot::new_changed(U256::ZERO, U256::from(1)),
+        )]);
+
+        let result = RawCallResult {
+            state_changeset: HashMap::from([(changed, account), (unchanged, untouched)]),
+            ..Default::default()
+        };
+        let diff = state_diff(&backend, result);
+
+        assert!(diff.success);
+        assert_eq!(
+            diff.accounts,
+            BTreeMap::from([(
+                changed,
+                AccountDiff {
+                    balance: Some((U256::from(10), U256::from(7))),
+                    storage: BTreeMap::from([(U256::from(1), (U256::ZERO, U256::from(42)))]),
+                }
+            )])
+        );
+    }
+}
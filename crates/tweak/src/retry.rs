@@ -0,0 +1,98 @@
+//! Retry policy for the RPC reads performed while tweaking.
+
+use alloy_transport::{RpcError, TransportError};
+use std::{future::Future, time::Duration};
+
+/// Bounded retry with exponential backoff.
+///
+/// Only transport errors (e.g. connection resets, timeouts) are retried, errors returned by the
+/// RPC node itself are surfaced right away.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// The backoff before the first retry, doubled after every retry.
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self { max_retries: 5, initial_backoff: Duration::from_millis(500) }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub const NONE: Self = Self { max_retries: 0, initial_backoff: Duration::ZERO };
+
+    /// Runs the given request, retrying it on transport errors.
+    pub async fn retry<T, F, Fut>(&self, mut request: F) -> Result<T, TransportError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, TransportError>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+        loop {
+            match request().await {
+                Err(RpcError::Transport(_)) if retries < self.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::TransportErrorKind;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy { max_retries: 2, initial_backoff: Duration::ZERO }
+    }
+
+    #[tokio::test]
+    async fn retries_transport_errors() {
+        let mut attempts = 0;
+        let res = policy()
+            .retry(|| {
+                attempts += 1;
+                let res =
+                    if attempts < 3 { Err(TransportErrorKind::backend_gone()) } else { Ok(1) };
+                async move { res }
+            })
+            .await;
+        assert_eq!(res.unwrap(), 1);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let mut attempts = 0;
+        let res: Result<(), _> = policy()
+            .retry(|| {
+                attempts += 1;
+                async { Err(TransportErrorKind::backend_gone()) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_other_errors() {
+        let mut attempts = 0;
+        let res: Result<(), _> = policy()
+            .retry(|| {
+                attempts += 1;
+                async { Err(RpcError::NullResp) }
+            })
+            .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
@@ -292,6 +292,13 @@ impl EtherscanVerificationProvider {
         args: &VerifyArgs,
         context: &VerificationContext,
     ) -> Result<VerifyContract> {
+        if context.is_vyper() {
+            eyre::bail!(
+                "Verifying Vyper contracts is not supported by {}, use `--verifier sourcify` instead",
+                args.verifier.verifier
+            );
+        }
+
         let (source, contract_name, code_format) =
             self.source_provider(args).source(args, context)?;
 
@@ -555,6 +562,44 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn resolves_vyper_context_from_extension() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+
+        let config = r"
+                [profile.default]
+                cache = false
+            ";
+
+        let toml_file = root.join(Config::FILE_NAME);
+        fs::write(toml_file, config).unwrap();
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/Counter.vy"), "").unwrap();
+
+        let args = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0xd8509bee9c9bf012282ad33aba0d87241baf5064",
+            "src/Counter.vy:Counter",
+            "--compiler-version",
+            "0.3.10",
+            "--root",
+            root.as_os_str().to_str().unwrap(),
+        ]);
+
+        // Vyper contracts never resolve a solc installation, whether Vyper is available or not
+        match args.resolve_context().await {
+            Ok(context) => {
+                assert!(context.is_vyper());
+                let mut etherscan = EtherscanVerificationProvider::default();
+                let err = etherscan.create_verify_request(&args, &context).await.unwrap_err();
+                assert!(err.to_string().contains("use `--verifier sourcify`"), "{err}");
+            }
+            Err(err) => assert!(err.to_string().contains("Vyper"), "{err}"),
+        }
+    }
+
     forgetest_async!(respects_path_for_duplicate, |prj, cmd| {
         prj.add_source("Counter1", "contract Counter {}").unwrap();
         prj.add_source("Counter2", "contract Counter {}").unwrap();
@@ -23,7 +23,7 @@ use itertools::Itertools;
 use provider::VerificationProviderType;
 use reqwest::Url;
 use revm_primitives::HashSet;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 mod etherscan;
 use etherscan::EtherscanVerificationProvider;
@@ -43,20 +43,64 @@ use crate::provider::VerificationContext;
 #[derive(Clone, Debug, Parser)]
 pub struct VerifierArgs {
     /// The contract verification provider to use.
+    ///
+    /// Vyper contracts can only be verified with sourcify.
     #[arg(long, help_heading = "Verifier options", default_value = "etherscan", value_enum)]
     pub verifier: VerificationProviderType,
 
     /// The verifier URL, if using a custom provider
     #[arg(long, help_heading = "Verifier options", env = "VERIFIER_URL")]
     pub verifier_url: Option<String>,
+
+    /// The API key of the custom verifier.
+    ///
+    /// Takes precedence over the Etherscan API key.
+    #[arg(
+        long,
+        help_heading = "Verifier options",
+        env = "VERIFIER_API_KEY",
+        requires = "verifier_url",
+        value_name = "KEY"
+    )]
+    pub verifier_api_key: Option<String>,
 }
 
 impl Default for VerifierArgs {
     fn default() -> Self {
-        Self { verifier: VerificationProviderType::Etherscan, verifier_url: None }
+        Self {
+            verifier: VerificationProviderType::Etherscan,
+            verifier_url: None,
+            verifier_api_key: None,
+        }
+    }
+}
+
+impl VerifierArgs {
+    /// Returns the API key to send to the verifier, falling back to the given Etherscan API key.
+    pub fn api_key(&self, etherscan_key: Option<String>) -> Option<String> {
+        self.verifier_api_key.clone().or(etherscan_key)
+    }
+
+    /// Checks that the custom verifier URL, if any, is valid and that its server is reachable.
+    ///
+    /// Any response counts as reachable, the API itself is only checked by the submission.
+    pub async fn check_verifier_url(&self) -> Result<()> {
+        let Some(verifier_url) = &self.verifier_url else { return Ok(()) };
+        let url = Url::parse(verifier_url)
+            .map_err(|err| eyre::eyre!("Invalid verifier URL `{verifier_url}`: {err}"))?;
+        reqwest::Client::new()
+            .get(url)
+            .timeout(Duration::from_secs(VERIFIER_URL_TIMEOUT_SECS))
+            .send()
+            .await
+            .map_err(|err| eyre::eyre!("Verifier URL `{verifier_url}` is not reachable: {err}"))?;
+        Ok(())
     }
 }
 
+/// The timeout of the reachability check of custom verifier URLs.
+const VERIFIER_URL_TIMEOUT_SECS: u64 = 10;
+
 /// CLI arguments for `forge verify`.
 #[derive(Clone, Debug, Parser)]
 pub struct VerifyArgs {
@@ -192,6 +236,10 @@ impl VerifyArgs {
             )
         }
 
+        if !self.show_standard_json_input {
+            self.verifier.check_verifier_url().await?;
+        }
+
         // If chain is not set, we try to get it from the RPC
         // If RPC is not set, the default chain is used
         let chain = match config.get_rpc_url() {
@@ -205,7 +253,9 @@ impl VerifyArgs {
         let context = self.resolve_context().await?;
 
         self.etherscan.chain = Some(chain);
-        self.etherscan.key = config.get_etherscan_config_with_chain(Some(chain))?.map(|c| c.key);
+        self.etherscan.key = self
+            .verifier
+            .api_key(config.get_etherscan_config_with_chain(Some(chain))?.map(|c| c.key));
 
         if self.show_standard_json_input {
             let args = EtherscanVerificationProvider::default()
@@ -218,24 +268,14 @@ impl VerifyArgs {
         let verifier_url = self.verifier.verifier_url.clone();
         println!("Start verifying contract `{}` deployed on {chain}", self.address);
         self.verifier.verifier.client(&self.etherscan.key())?.verify(self, context).await.map_err(|err| {
-            if let Some(verifier_url) = verifier_url {
-                 match Url::parse(&verifier_url) {
-                    Ok(url) => {
-                        if is_host_only(&url) {
-                            return err.wrap_err(format!(
-                                "Provided URL `{verifier_url}` is host only.\n Did you mean to use the API endpoint`{verifier_url}/api` ?"
-                            ))
-                        }
-                    }
-                    Err(url_err) => {
-                        return err.wrap_err(format!(
-                            "Invalid URL {verifier_url} provided: {url_err}"
-                        ))
-                    }
+            match verifier_url {
+                Some(verifier_url) if Url::parse(&verifier_url).is_ok_and(|url| is_host_only(&url)) => {
+                    err.wrap_err(format!(
+                        "Provided URL `{verifier_url}` is host only.\n Did you mean to use the API endpoint`{verifier_url}/api` ?"
+                    ))
                 }
+                _ => err,
             }
-
-            err
         })
     }
 
@@ -259,9 +299,11 @@ impl VerifyArgs {
                 project.find_contract_path(&contract.name)?
             };
 
+            // `solc` settings don't apply to Vyper contracts
+            let is_vyper = provider::is_vyper_source(&contract_path);
             let version = if let Some(ref version) = self.compiler_version {
-                version.trim_start_matches('v').parse()?
-            } else if let Some(ref solc) = config.solc {
+                version.trim_start_matches('v').trim_start_matches("vyper:").parse()?
+            } else if let Some(solc) = config.solc.as_ref().filter(|_| !is_vyper) {
                 match solc {
                     SolcReq::Version(version) => version.to_owned(),
                     SolcReq::Local(solc) => Solc::new(solc)?.version,
@@ -288,6 +330,8 @@ impl VerifyArgs {
                 }
 
                 unique_versions.into_iter().next().unwrap().to_owned()
+            } else if let Some(vyper) = project.compiler.vyper.as_ref().filter(|_| is_vyper) {
+                vyper.version.clone()
             } else {
                 eyre::bail!("If cache is disabled, compiler version must be either provided with `--compiler-version` option or set in foundry.toml")
             };
@@ -346,7 +390,9 @@ impl_figment_convert_cast!(VerifyCheckArgs);
 
 impl VerifyCheckArgs {
     /// Run the verify command to submit the contract's source code for verification on etherscan
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(mut self) -> Result<()> {
+        self.verifier.check_verifier_url().await?;
+        self.etherscan.key = self.verifier.api_key(self.etherscan.key());
         println!("Checking verification status on {}", self.etherscan.chain.unwrap_or_default());
         self.verifier.verifier.client(&self.etherscan.key())?.check(self).await
     }
@@ -383,6 +429,38 @@ mod tests {
         assert!(is_host_only(&Url::parse("https://blockscout.net").unwrap()));
     }
 
+    #[test]
+    fn verifier_api_key_takes_precedence() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "--verifier-url",
+            "https://explorer.example.com/api",
+            "--verifier-api-key",
+            "verifier",
+        ]);
+        assert_eq!(args.verifier.api_key(Some("etherscan".into())).as_deref(), Some("verifier"));
+
+        let args = VerifierArgs::default();
+        assert_eq!(args.api_key(Some("etherscan".into())).as_deref(), Some("etherscan"));
+
+        // The key is meaningless without a custom verifier
+        let res = VerifyArgs::try_parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "--verifier-api-key",
+            "verifier",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_verifier_url() {
+        let args = VerifierArgs { verifier_url: Some("not a url".into()), ..Default::default() };
+        let err = args.check_verifier_url().await.unwrap_err();
+        assert!(err.to_string().contains("Invalid verifier URL"), "{err}");
+    }
+
     #[test]
     fn can_parse_verify_contract() {
         let args: VerifyArgs = VerifyArgs::parse_from([
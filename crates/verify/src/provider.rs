@@ -8,13 +8,17 @@ use eyre::{OptionExt, Result};
 use foundry_common::compile::ProjectCompiler;
 use foundry_compilers::{
     artifacts::{output_selection::OutputSelection, Metadata, Source},
-    compilers::{multi::MultiCompilerParsedSource, solc::SolcCompiler, CompilerSettings},
+    compilers::{multi::MultiCompilerParsedSource, solc::SolcCompiler, CompilerSettings, Language},
     solc::Solc,
-    Graph, Project,
+    Graph, Project, VyperLanguage,
 };
 use foundry_config::Config;
 use semver::Version;
-use std::{fmt, path::PathBuf, str::FromStr};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 /// Container with data required for contract verification.
 #[derive(Debug, Clone)]
@@ -24,6 +28,8 @@ pub struct VerificationContext {
     pub target_path: PathBuf,
     pub target_name: String,
     pub compiler_version: Version,
+    /// Whether the target contract is written in Vyper.
+    pub is_vyper: bool,
 }
 
 impl VerificationContext {
@@ -36,10 +42,31 @@ impl VerificationContext {
         let mut project = config.project()?;
         project.no_artifacts = true;
 
-        let solc = Solc::find_or_install(&compiler_version)?;
-        project.compiler.solc = SolcCompiler::Specific(solc);
+        let is_vyper = is_vyper_source(&target_path);
+        if is_vyper {
+            let vyper = project.compiler.vyper.as_ref().ok_or_eyre(
+                "Vyper compiler not found, set `vyper.path` in foundry.toml or add it to PATH",
+            )?;
+            let version = &vyper.version;
+            if (version.major, version.minor, version.patch) !=
+                (compiler_version.major, compiler_version.minor, compiler_version.patch)
+            {
+                eyre::bail!(
+                    "The contract was compiled with Vyper {compiler_version}, but the available \
+                     Vyper compiler is {version}"
+                );
+            }
+        } else {
+            let solc = Solc::find_or_install(&compiler_version)?;
+            project.compiler.solc = SolcCompiler::Specific(solc);
+        }
 
-        Ok(Self { config, project, target_name, target_path, compiler_version })
+        Ok(Self { config, project, target_name, target_path, compiler_version, is_vyper })
+    }
+
+    /// Returns whether the target contract is written in Vyper.
+    pub fn is_vyper(&self) -> bool {
+        self.is_vyper
     }
 
     /// Compiles target contract requesting only ABI and returns it.
@@ -91,6 +118,13 @@ impl VerificationContext {
     }
 }
 
+/// Returns whether the given source file is a Vyper file.
+pub fn is_vyper_source(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map_or(false, |ext| VyperLanguage::FILE_EXTENSIONS.contains(&ext))
+}
+
 /// An abstraction for various verification providers such as etherscan, sourcify, blockscout
 #[async_trait]
 pub trait VerificationProvider {
@@ -163,7 +197,7 @@ impl VerificationProviderType {
         match self {
             Self::Etherscan => {
                 if key.as_ref().map_or(true, |key| key.is_empty()) {
-                    eyre::bail!("ETHERSCAN_API_KEY must be set")
+                    eyre::bail!("ETHERSCAN_API_KEY must be set, or `--verifier-api-key` for a custom verifier")
                 }
                 Ok(Box::<EtherscanVerificationProvider>::default())
             }
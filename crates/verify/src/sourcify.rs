@@ -6,7 +6,7 @@ use foundry_common::{fs, retry::Retry};
 use futures::FutureExt;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, path::Path, str::FromStr};
 
 pub static SOURCIFY_URL: &str = "https://sourcify.dev/server/";
 
@@ -22,14 +22,25 @@ impl VerificationProvider for SourcifyVerificationProvider {
         args: VerifyArgs,
         context: VerificationContext,
     ) -> Result<()> {
-        let _ = self.prepare_request(&args, &context)?;
+        if context.is_vyper() {
+            let _ = self.prepare_vyper_request(&args, &context)?;
+        } else {
+            let _ = self.prepare_request(&args, &context)?;
+        }
         Ok(())
     }
 
     async fn verify(&mut self, args: VerifyArgs, context: VerificationContext) -> Result<()> {
-        let body = self.prepare_request(&args, &context)?;
-
-        trace!("submitting verification request {:?}", body);
+        let url = Url::from_str(args.verifier.verifier_url.as_deref().unwrap_or(SOURCIFY_URL))?;
+        let (url, body) = if context.is_vyper() {
+            let body = self.prepare_vyper_request(&args, &context)?;
+            trace!("submitting verification request {:?}", body);
+            (vyper_verify_url(url)?, serde_json::to_string(&body)?)
+        } else {
+            let body = self.prepare_request(&args, &context)?;
+            trace!("submitting verification request {:?}", body);
+            (url, serde_json::to_string(&body)?)
+        };
 
         let client = reqwest::Client::new();
 
@@ -43,9 +54,9 @@ impl VerificationProvider for SourcifyVerificationProvider {
                         args.address.to_string()
                     );
                     let response = client
-                        .post(args.verifier.verifier_url.as_deref().unwrap_or(SOURCIFY_URL))
+                        .post(url.clone())
                         .header("Content-Type", "application/json")
-                        .body(serde_json::to_string(&body)?)
+                        .body(body.clone())
                         .send()
                         .await?;
 
@@ -134,6 +145,40 @@ impl SourcifyVerificationProvider {
         Ok(req)
     }
 
+    /// Configures the API request to the sourcify Vyper endpoint, which compiles the sources with
+    /// the given compiler version and settings instead of reading them from the solc metadata.
+    fn prepare_vyper_request(
+        &self,
+        args: &VerifyArgs,
+        context: &VerificationContext,
+    ) -> Result<SourcifyVyperVerifyRequest> {
+        let root = context.project.root();
+        let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).display().to_string();
+
+        let imports = context.get_target_imports()?;
+        let mut files = HashMap::with_capacity(1 + imports.len());
+        files.insert(relative(&context.target_path), fs::read_to_string(&context.target_path)?);
+        for import in imports {
+            files.insert(relative(&import), fs::read_to_string(&import)?);
+        }
+
+        // The output selection is chosen by sourcify
+        let mut compiler_settings = serde_json::to_value(&context.project.settings.vyper)?;
+        if let Some(settings) = compiler_settings.as_object_mut() {
+            settings.remove("outputSelection");
+        }
+
+        Ok(SourcifyVyperVerifyRequest {
+            address: args.address.to_string(),
+            chain: args.etherscan.chain.unwrap_or_default().id().to_string(),
+            files,
+            contract_path: relative(&context.target_path),
+            contract_name: context.target_name.clone(),
+            compiler_version: context.compiler_version.to_string(),
+            compiler_settings,
+        })
+    }
+
     fn process_sourcify_response(
         &self,
         response: Option<Vec<SourcifyResponseElement>>,
@@ -166,6 +211,29 @@ pub struct SourcifyVerifyRequest {
     chosen_contract: Option<String>,
 }
 
+/// Returns the URL of the Vyper endpoint of the sourcify server at `url`.
+///
+/// The configured URL may lack a trailing slash, which would make [Url::join] replace its last
+/// path segment.
+fn vyper_verify_url(mut url: Url) -> Result<Url> {
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    Ok(url.join("verify/vyper")?)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyVyperVerifyRequest {
+    address: String,
+    chain: String,
+    files: HashMap<String, String>,
+    contract_path: String,
+    contract_name: String,
+    compiler_version: String,
+    compiler_settings: serde_json::Value,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SourcifyVerificationResponse {
     result: Vec<SourcifyResponseElement>,
@@ -182,6 +250,21 @@ pub struct SourcifyResponseElement {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_vyper_verify_url() {
+        let url = Url::from_str(SOURCIFY_URL).unwrap();
+        assert_eq!(
+            vyper_verify_url(url).unwrap().as_str(),
+            "https://sourcify.dev/server/verify/vyper"
+        );
+
+        let url = Url::from_str("https://sourcify.dev/server").unwrap();
+        assert_eq!(
+            vyper_verify_url(url).unwrap().as_str(),
+            "https://sourcify.dev/server/verify/vyper"
+        );
+    }
+
     #[test]
     fn test_check_addresses_url() {
         let url = Url::from_str("https://server-verify.hashscan.io").unwrap();
@@ -143,7 +143,7 @@ pub struct MultiWalletOpts {
     /// Works with both --mnemonic-path and hardware wallets.
     #[arg(
         long = "mnemonic-derivation-paths",
-        alias = "hd-paths",
+        aliases = ["hd-paths", "hd-path"],
         help_heading = "Wallet options - raw",
         value_name = "PATH"
     )]
@@ -168,7 +168,8 @@ pub struct MultiWalletOpts {
         visible_alias = "keystores",
         help_heading = "Wallet options - keystore",
         value_name = "PATHS",
-        env = "ETH_KEYSTORE"
+        env = "ETH_KEYSTORE",
+        group = "keystores"
     )]
     #[builder(default = "None")]
     pub keystore_paths: Option<Vec<String>>,
@@ -180,18 +181,19 @@ pub struct MultiWalletOpts {
         help_heading = "Wallet options - keystore",
         value_name = "ACCOUNT_NAMES",
         env = "ETH_KEYSTORE_ACCOUNT",
-        conflicts_with = "keystore_paths"
+        conflicts_with = "keystore_paths",
+        group = "keystores"
     )]
     #[builder(default = "None")]
     pub keystore_account_names: Option<Vec<String>>,
 
     /// The keystore password.
     ///
-    /// Used with --keystore.
+    /// Used with --keystore or --account.
     #[arg(
         long = "password",
         help_heading = "Wallet options - keystore",
-        requires = "keystore_paths",
+        requires = "keystores",
         value_name = "PASSWORDS"
     )]
     #[builder(default = "None")]
@@ -199,11 +201,11 @@ pub struct MultiWalletOpts {
 
     /// The keystore password file path.
     ///
-    /// Used with --keystore.
+    /// Used with --keystore or --account.
     #[arg(
         long = "password-file",
         help_heading = "Wallet options - keystore",
-        requires = "keystore_paths",
+        requires = "keystores",
         value_name = "PATHS",
         env = "ETH_PASSWORD"
     )]
@@ -443,6 +445,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_account_password_file() {
+        let args: MultiWalletOpts = MultiWalletOpts::parse_from([
+            "foundry-cli",
+            "--account",
+            "deployer",
+            "--password-file",
+            "deployer.password",
+        ]);
+        assert_eq!(args.keystore_account_names, Some(vec!["deployer".to_string()]));
+        assert_eq!(args.keystore_password_files, Some(vec!["deployer.password".to_string()]));
+
+        let err = MultiWalletOpts::try_parse_from([
+            "foundry-cli",
+            "--password-file",
+            "deployer.password",
+        ]);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn parse_ledger_hd_path() {
+        let args: MultiWalletOpts = MultiWalletOpts::parse_from([
+            "foundry-cli",
+            "--ledger",
+            "--hd-path",
+            "m/44'/60'/0'/0/0",
+        ]);
+        assert!(args.ledger);
+        assert_eq!(args.hd_paths, Some(vec!["m/44'/60'/0'/0/0".to_string()]));
+    }
+
     // https://github.com/foundry-rs/foundry/issues/5179
     #[test]
     fn should_not_require_the_mnemonics_flag_with_mnemonic_indexes() {
@@ -64,7 +64,7 @@ pub async fn create_ledger_signer(
     WalletSigner::from_ledger_path(derivation).await.wrap_err_with(|| {
         "\
 Could not connect to Ledger device.
-Make sure it's connected and unlocked, with no other desktop wallet apps open."
+Make sure it's connected and unlocked, the Ethereum app is open, and no other desktop wallet apps are open."
     })
 }
 
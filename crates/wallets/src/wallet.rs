@@ -33,7 +33,8 @@ pub struct WalletOpts {
         long = "keystore",
         help_heading = "Wallet options - keystore",
         value_name = "PATH",
-        env = "ETH_KEYSTORE"
+        env = "ETH_KEYSTORE",
+        group = "keystore"
     )]
     pub keystore_path: Option<String>,
 
@@ -43,28 +44,29 @@ pub struct WalletOpts {
         help_heading = "Wallet options - keystore",
         value_name = "ACCOUNT_NAME",
         env = "ETH_KEYSTORE_ACCOUNT",
-        conflicts_with = "keystore_path"
+        conflicts_with = "keystore_path",
+        group = "keystore"
     )]
     pub keystore_account_name: Option<String>,
 
     /// The keystore password.
     ///
-    /// Used with --keystore.
+    /// Used with --keystore or --account.
     #[arg(
         long = "password",
         help_heading = "Wallet options - keystore",
-        requires = "keystore_path",
+        requires = "keystore",
         value_name = "PASSWORD"
     )]
     pub keystore_password: Option<String>,
 
     /// The keystore password file path.
     ///
-    /// Used with --keystore.
+    /// Used with --keystore or --account.
     #[arg(
         long = "password-file",
         help_heading = "Wallet options - keystore",
-        requires = "keystore_path",
+        requires = "keystore",
         value_name = "PASSWORD_FILE",
         env = "ETH_PASSWORD"
     )]